@@ -0,0 +1,294 @@
+use crate::commands::data::{read_app_data, write_app_data, DATA_MUTEX};
+use crate::commands::file_items::{installed_at, is_markdown_file, move_file_to_trash, update_file_item_metadata};
+use crate::types::{Agent, AgentMetadata};
+use crate::utils::{expand_path, is_symlink, is_symlink_into, parse_skill_md};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+fn parse_agent_file(
+    agent_path: &Path,
+    scope: &str,
+    metadata_map: &HashMap<String, AgentMetadata>,
+) -> Result<Agent, String> {
+    let content = fs::read_to_string(agent_path).map_err(|e| e.to_string())?;
+    let (frontmatter, instructions) = parse_skill_md(&content);
+
+    let id = agent_path.to_string_lossy().to_string();
+    let metadata = metadata_map.get(&id);
+    let file_name = agent_path
+        .file_stem()
+        .and_then(|n| n.to_str())
+        .unwrap_or("unknown")
+        .to_string();
+
+    Ok(Agent {
+        id: id.clone(),
+        name: frontmatter.name.unwrap_or(file_name),
+        description: frontmatter.description.unwrap_or_default(),
+        category: metadata.map(|m| m.category.clone()).unwrap_or_default(),
+        tags: metadata.map(|m| m.tags.clone()).unwrap_or_default(),
+        enabled: metadata.map(|m| m.enabled).unwrap_or(true),
+        source_path: id,
+        scope: scope.to_string(),
+        tools: frontmatter.tools,
+        model: frontmatter.model,
+        instructions,
+        last_used: metadata.and_then(|m| m.last_used.clone()),
+        usage_count: metadata.map(|m| m.usage_count).unwrap_or(0),
+        icon: metadata.and_then(|m| m.icon.clone()),
+        installed_at: installed_at(agent_path),
+    })
+}
+
+/// Parse every agent file in `dir`; with `skip_links`, symlinks (agents
+/// deployed from the library) are left out
+fn read_agents_dir(
+    dir: &Path,
+    scope: &str,
+    skip_links: bool,
+    metadata_map: &HashMap<String, AgentMetadata>,
+) -> Vec<Agent> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut agents: Vec<Agent> = entries
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|path| is_markdown_file(path) && !(skip_links && is_symlink(path)))
+        .filter_map(|path| parse_agent_file(&path, scope, metadata_map).ok())
+        .collect();
+    agents.sort_by(|a, b| a.name.cmp(&b.name));
+    agents
+}
+
+fn load_agent_metadata() -> HashMap<String, AgentMetadata> {
+    read_app_data().map(|data| data.agent_metadata).unwrap_or_default()
+}
+
+/// Symlink agent files into a project's .claude/agents directory
+///
+/// Links into the `library` agents directory that are no longer wanted are
+/// removed; regular files and other links are the user's own and are never
+/// replaced. Returns the deployed file names.
+pub(crate) fn deploy_agents(
+    agents_dir: &Path,
+    agent_paths: &[PathBuf],
+    library: &Path,
+) -> Result<Vec<String>, String> {
+    let wanted: HashMap<String, &PathBuf> = agent_paths
+        .iter()
+        .filter_map(|p| Some((p.file_name()?.to_string_lossy().to_string(), p)))
+        .collect();
+
+    if agents_dir.exists() {
+        for entry in fs::read_dir(agents_dir).map_err(|e| e.to_string())?.filter_map(|e| e.ok()) {
+            let path = entry.path();
+            let name = entry.file_name().to_string_lossy().to_string();
+            let current = fs::read_link(&path).ok();
+            if is_symlink_into(&path, library) && current.as_ref() != wanted.get(&name).copied() {
+                fs::remove_file(&path).map_err(|e| e.to_string())?;
+            }
+        }
+    }
+    if wanted.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    fs::create_dir_all(agents_dir).map_err(|e| e.to_string())?;
+    let mut deployed = Vec::new();
+    for (name, source) in wanted {
+        let target = agents_dir.join(&name);
+        if is_symlink(&target) {
+            deployed.push(name);
+            continue;
+        }
+        if target.exists() {
+            log::warn!("Skipping agent {}: {} is not managed by Ensemble", name, target.display());
+            continue;
+        }
+        if !source.exists() {
+            return Err(format!("Agent not found: {}", source.display()));
+        }
+        #[cfg(unix)]
+        std::os::unix::fs::symlink(source, &target).map_err(|e| e.to_string())?;
+        #[cfg(windows)]
+        std::os::windows::fs::symlink_file(source, &target).map_err(|e| e.to_string())?;
+        deployed.push(name);
+    }
+    deployed.sort();
+    Ok(deployed)
+}
+
+/// Remove agent symlinks into the `library` agents directory from a
+/// .claude/agents directory
+pub(crate) fn remove_deployed_agents(agents_dir: &Path, library: &Path) -> Result<(), String> {
+    deploy_agents(agents_dir, &[], library).map(|_| ())
+}
+
+/// Number of agents linked from the `library` agents directory
+pub(crate) fn count_deployed_agents(agents_dir: &Path, library: &Path) -> u32 {
+    fs::read_dir(agents_dir)
+        .map(|entries| {
+            entries
+                .filter_map(|e| e.ok())
+                .filter(|e| is_symlink_into(&e.path(), library))
+                .count() as u32
+        })
+        .unwrap_or(0)
+}
+
+// ============================================================================
+// Commands
+// ============================================================================
+
+/// Scan the agent library directory and return its subagents
+#[tauri::command]
+pub fn scan_agents(source_dir: String) -> Result<Vec<Agent>, String> {
+    let path = expand_path(&source_dir);
+    Ok(read_agents_dir(&path, "user", false, &load_agent_metadata()))
+}
+
+/// Get a single agent by ID
+#[tauri::command]
+pub fn get_agent(source_dir: String, agent_id: String) -> Result<Option<Agent>, String> {
+    let agents = scan_agents(source_dir)?;
+    Ok(agents.into_iter().find(|a| a.id == agent_id))
+}
+
+/// Update agent metadata (category, tags, enabled status, icon)
+#[tauri::command]
+pub fn update_agent_metadata(
+    agent_id: String,
+    category: Option<String>,
+    tags: Option<Vec<String>>,
+    enabled: Option<bool>,
+    icon: Option<String>,
+) -> Result<(), String> {
+    update_file_item_metadata(|data| &mut data.agent_metadata, agent_id, category, tags, enabled, icon)
+}
+
+/// Find subagents defined outside the library, for import
+///
+/// Lists ~/.claude/agents ("user" scope) and, when given, the project's
+/// .claude/agents ("project" scope). Agents deployed from the library are
+/// symlinks and are left out.
+#[tauri::command]
+pub fn detect_claude_agents(claude_config_dir: String, project_path: Option<String>) -> Result<Vec<Agent>, String> {
+    let metadata = HashMap::new();
+    let mut agents = read_agents_dir(&expand_path(&claude_config_dir).join("agents"), "user", true, &metadata);
+    if let Some(project) = project_path {
+        let dir = expand_path(&project).join(".claude").join("agents");
+        agents.extend(read_agents_dir(&dir, "project", true, &metadata));
+    }
+    Ok(agents)
+}
+
+/// Copy agent files into the library
+///
+/// Agents whose file name is already in the library are skipped. Returns
+/// the number of agents imported.
+#[tauri::command]
+pub fn import_agents(agent_paths: Vec<String>, source_dir: String) -> Result<u32, String> {
+    let library = expand_path(&source_dir);
+    fs::create_dir_all(&library).map_err(|e| format!("Failed to create agents directory: {}", e))?;
+
+    let mut imported = 0;
+    for agent_path in agent_paths {
+        let source = expand_path(&agent_path);
+        if !is_markdown_file(&source) {
+            return Err(format!("Not an agent file: {}", agent_path));
+        }
+        let file_name = source.file_name().ok_or("Invalid agent path")?;
+        let dest = library.join(file_name);
+        if dest.exists() {
+            log::warn!("Agent {} already exists in the library, skipping", dest.display());
+            continue;
+        }
+        fs::copy(&source, &dest).map_err(|e| format!("Failed to import agent {}: {}", agent_path, e))?;
+        imported += 1;
+    }
+    Ok(imported)
+}
+
+/// Delete an agent by moving it to the trash directory
+///
+/// The file is moved to {ensemble}/trash/agents/ so it can be restored;
+/// its metadata and Scene memberships are removed.
+#[tauri::command]
+pub fn delete_agent(agent_id: String, ensemble_dir: String) -> Result<(), String> {
+    let _guard = DATA_MUTEX.lock().map_err(|e| e.to_string())?;
+    let agent_path = Path::new(&agent_id);
+    if !agent_path.exists() {
+        return Err(format!("Agent not found: {}", agent_id));
+    }
+
+    move_file_to_trash(agent_path, &expand_path(&ensemble_dir).join("trash").join("agents"))?;
+
+    let mut app_data = read_app_data()?;
+    app_data.agent_metadata.remove(&agent_id);
+    for scene in app_data.scenes.iter_mut() {
+        scene.agent_ids.retain(|id| *id != agent_id);
+    }
+    write_app_data(app_data)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_read_agents_dir_parses_frontmatter() {
+        let tmp = TempDir::new().unwrap();
+        fs::write(
+            tmp.path().join("reviewer.md"),
+            "---\nname: code-reviewer\ndescription: Reviews diffs\ntools: Read, Grep\nmodel: sonnet\n---\nYou review code.",
+        )
+        .unwrap();
+        fs::write(tmp.path().join("plain.md"), "No frontmatter").unwrap();
+        fs::write(tmp.path().join("notes.txt"), "ignored").unwrap();
+
+        let agents = read_agents_dir(tmp.path(), "user", false, &HashMap::new());
+        assert_eq!(agents.len(), 2);
+        let reviewer = &agents[0];
+        assert_eq!(reviewer.name, "code-reviewer");
+        assert_eq!(reviewer.tools, Some(vec!["Read".to_string(), "Grep".to_string()]));
+        assert_eq!(reviewer.model.as_deref(), Some("sonnet"));
+        assert_eq!(reviewer.instructions, "You review code.");
+        assert_eq!(agents[1].name, "plain");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_deploy_agents_links_and_prunes() {
+        let tmp = TempDir::new().unwrap();
+        let library = tmp.path().join("library");
+        let agents_dir = tmp.path().join("project/.claude/agents");
+        fs::create_dir_all(&library).unwrap();
+        for name in ["a.md", "b.md"] {
+            fs::write(library.join(name), "---\nname: x\n---\n").unwrap();
+        }
+        fs::create_dir_all(&agents_dir).unwrap();
+        fs::write(agents_dir.join("mine.md"), "user agent").unwrap();
+        let dotfiles = tmp.path().join("dotfiles/shared.md");
+        fs::create_dir_all(dotfiles.parent().unwrap()).unwrap();
+        fs::write(&dotfiles, "dotfiles agent").unwrap();
+        std::os::unix::fs::symlink(&dotfiles, agents_dir.join("shared.md")).unwrap();
+
+        let both = [library.join("a.md"), library.join("b.md")];
+        assert_eq!(deploy_agents(&agents_dir, &both, &library).unwrap(), vec!["a.md", "b.md"]);
+        assert_eq!(fs::read_link(agents_dir.join("a.md")).unwrap(), library.join("a.md"));
+        assert_eq!(count_deployed_agents(&agents_dir, &library), 2);
+
+        deploy_agents(&agents_dir, &both[1..], &library).unwrap();
+        assert!(agents_dir.join("a.md").symlink_metadata().is_err());
+        assert!(is_symlink(&agents_dir.join("b.md")));
+
+        remove_deployed_agents(&agents_dir, &library).unwrap();
+        let mut left: Vec<_> = fs::read_dir(&agents_dir).unwrap().map(|e| e.unwrap().file_name()).collect();
+        left.sort();
+        assert_eq!(left, vec!["mine.md", "shared.md"]);
+        assert_eq!(count_deployed_agents(&agents_dir, &library), 0);
+    }
+}
@@ -1,16 +1,24 @@
-use crate::commands::data::{read_app_data, write_app_data, DATA_MUTEX};
+use crate::commands::claude_md_history::save_revision;
+use crate::commands::claude_md_imports::{
+    collect_imports, inline_imports, ImportCopier, DISTRIBUTED_IMPORTS_DIR, MANAGED_IMPORTS_DIR,
+};
+use crate::commands::claude_md_sync::{forget_distributions, record_distribution};
+use crate::commands::data::{apply_reorder, find_project_scene, read_app_data, read_settings, write_app_data, DATA_MUTEX};
+use crate::commands::library::get_library_dir;
 use crate::types::{
-    ClaudeMdConflictResolution, ClaudeMdDistributionOptions, ClaudeMdDistributionPath,
-    ClaudeMdDistributionResult, ClaudeMdFile, ClaudeMdImportOptions, ClaudeMdImportResult,
-    ClaudeMdScanItem, ClaudeMdScanResult, ClaudeMdType, SetGlobalResult,
+    AppData, ClaudeMdConflictResolution, ClaudeMdDistributionOptions, ClaudeMdDistributionPath,
+    ClaudeMdDistributionResult, ClaudeMdFile, ClaudeMdImportMode, ClaudeMdImportOptions,
+    ClaudeMdImportResult, ClaudeMdPreview, ClaudeMdType, GlobalClaudeMdEntry, SetGlobalResult,
+    DEFAULT_CLAUDE_MD_HISTORY_LIMIT,
 };
-use crate::utils::{expand_path, get_app_data_dir};
+use crate::utils::claude_md::{apply_managed_region, compose_sections, ClaudeMdSection};
+use crate::utils::{expand_path, find_repo_root, validate_subdirectory};
+use crate::utils::template::render_template;
 use chrono::Utc;
+use std::collections::HashMap;
 use std::fs;
 use std::path::{Path, PathBuf};
-use std::time::Instant;
 use uuid::Uuid;
-use walkdir::WalkDir;
 
 // ============================================================================
 // Constants
@@ -18,7 +26,7 @@ use walkdir::WalkDir;
 
 /// Global backup directory (~/.ensemble/claude-md/global-backup/)
 fn get_global_backup_dir() -> PathBuf {
-    get_app_data_dir().join("claude-md").join("global-backup")
+    get_claude_md_storage_dir().join("global-backup")
 }
 
 // ============================================================================
@@ -26,8 +34,10 @@ fn get_global_backup_dir() -> PathBuf {
 // ============================================================================
 
 /// Get CLAUDE.md storage root directory (~/.ensemble/claude-md/)
-fn get_claude_md_storage_dir() -> PathBuf {
-    get_app_data_dir().join("claude-md")
+///
+/// Follows the library root when the library has been relocated.
+pub(crate) fn get_claude_md_storage_dir() -> PathBuf {
+    get_library_dir().join("claude-md")
 }
 
 /// Get directory for a specific CLAUDE.md file (~/.ensemble/claude-md/{id}/)
@@ -58,249 +68,45 @@ fn write_claude_md_content(id: &str, content: &str) -> Result<(), String> {
         .map_err(|e| format!("Failed to write content: {}", e))
 }
 
-/// Excluded directory names
-const EXCLUDED_DIRS: &[&str] = &[
-    "node_modules",
-    ".git",
-    ".svn",
-    ".hg",
-    "target",
-    "build",
-    "dist",
-    ".cache",
-    "__pycache__",
-    ".venv",
-    "venv",
-    ".idea",
-    ".vscode",
-];
-
-/// Max scan depth
-const MAX_SCAN_DEPTH: usize = 10;
-
-/// Preview character count
-const PREVIEW_LENGTH: usize = 500;
-
-// ============================================================================
-// Scan commands
-// ============================================================================
-
-/// Scan system for CLAUDE.md files
-///
-/// # Arguments
-/// * `scan_paths` - Paths to scan (optional, defaults to HOME directories)
-/// * `include_home` - Whether to include ~/Documents, ~/Projects, etc.
-///
-/// # Returns
-/// * `ClaudeMdScanResult` - Scan result
-#[tauri::command]
-pub fn scan_claude_md_files(
-    scan_paths: Option<Vec<String>>,
-    include_home: Option<bool>,
-) -> Result<ClaudeMdScanResult, String> {
-    let start = Instant::now();
-    let mut items: Vec<ClaudeMdScanItem> = Vec::new();
-    let mut errors: Vec<String> = Vec::new();
-    let mut scanned_dirs: u32 = 0;
-
-    // Read imported file list
-    let app_data = read_app_data().unwrap_or_default();
-    let imported_paths: Vec<String> = app_data
-        .claude_md_files
-        .iter()
-        .map(|f| f.source_path.clone())
-        .collect();
-
-    // Determine paths to scan
-    let mut paths_to_scan: Vec<PathBuf> = Vec::new();
-
-    // 1. User-level global: ~/.claude/CLAUDE.md
-    let home = dirs::home_dir().ok_or("Cannot get home directory")?;
-    let user_claude_md = home.join(".claude").join("CLAUDE.md");
-    if user_claude_md.exists() {
-        if let Some(item) =
-            scan_single_file(&user_claude_md, ClaudeMdType::Global, &imported_paths, &app_data)
-        {
-            items.push(item);
-        }
-    }
-
-    // 2. Custom paths
-    if let Some(custom_paths) = scan_paths {
-        for path_str in custom_paths {
-            let path = expand_path(&path_str);
-            if path.exists() && path.is_dir() {
-                paths_to_scan.push(path);
-            }
+/// Directories `@` imports of a managed file are resolved against: its
+/// storage directory first, then the directory of the original source file
+pub(crate) fn import_bases(id: &str, source_path: &str) -> Vec<PathBuf> {
+    let mut bases = vec![get_claude_md_file_dir(id)];
+    if let Some(parent) = Path::new(source_path).parent() {
+        if !parent.as_os_str().is_empty() {
+            bases.push(parent.to_path_buf());
         }
     }
-
-    // 3. Default scan paths
-    if include_home.unwrap_or(true) {
-        let default_dirs = vec![
-            home.join("Documents"),
-            home.join("Projects"),
-            home.join("Developer"),
-            home.join("Code"),
-            home.join("Workspace"),
-            home.join("repos"),
-        ];
-        for dir in default_dirs {
-            if dir.exists() && dir.is_dir() {
-                paths_to_scan.push(dir);
-            }
-        }
-    }
-
-    // Scan all paths
-    for base_path in paths_to_scan {
-        match scan_directory(&base_path, &imported_paths, &app_data, &mut scanned_dirs) {
-            Ok(mut found_items) => items.append(&mut found_items),
-            Err(e) => errors.push(format!("{}: {}", base_path.display(), e)),
-        }
-    }
-
-    // Deduplicate (based on path)
-    items.sort_by(|a, b| a.path.cmp(&b.path));
-    items.dedup_by(|a, b| a.path == b.path);
-
-    let duration = start.elapsed().as_millis() as u64;
-
-    Ok(ClaudeMdScanResult {
-        items,
-        scanned_dirs,
-        duration,
-        errors,
-    })
+    bases
 }
 
-/// Scan a single directory
-fn scan_directory(
-    base_path: &Path,
-    imported_paths: &[String],
-    app_data: &crate::types::AppData,
-    scanned_dirs: &mut u32,
-) -> Result<Vec<ClaudeMdScanItem>, String> {
-    let mut items: Vec<ClaudeMdScanItem> = Vec::new();
-
-    for entry in WalkDir::new(base_path)
-        .max_depth(MAX_SCAN_DEPTH)
-        .follow_links(false)
-        .into_iter()
-        .filter_entry(|e| !is_excluded_dir(e))
-    {
-        let entry = match entry {
-            Ok(e) => e,
-            Err(_) => continue,
-        };
-
-        if entry.file_type().is_dir() {
-            *scanned_dirs += 1;
-        }
-
-        let path = entry.path();
-        let file_name = match path.file_name().and_then(|n| n.to_str()) {
-            Some(n) => n,
-            None => continue,
-        };
-
-        // Check if it's a CLAUDE.md file
-        let file_type = match file_name {
-            "CLAUDE.md" => {
-                // Distinguish ./.claude/CLAUDE.md and ./CLAUDE.md
-                ClaudeMdType::Project
-            }
-            "CLAUDE.local.md" => ClaudeMdType::Local,
-            _ => continue,
-        };
-
-        if let Some(item) = scan_single_file(path, file_type, imported_paths, app_data) {
-            items.push(item);
-        }
+/// Replace the stored content of a managed file, refreshing its size and
+/// import tracking (does not touch `updated_at`)
+///
+/// The content being replaced is kept as a revision, labelled with `note`.
+pub(crate) fn store_claude_md_content(
+    file: &mut ClaudeMdFile,
+    content: &str,
+    note: Option<&str>,
+) -> Result<(), String> {
+    let limit = read_settings()
+        .map(|s| s.claude_md_history_limit)
+        .unwrap_or(DEFAULT_CLAUDE_MD_HISTORY_LIMIT);
+    save_revision(&get_claude_md_file_dir(&file.id), content, note, limit)?;
+
+    file.size = content.len() as u64;
+    // Write content to independent file
+    write_claude_md_content(&file.id, content)?;
+    file.imports = collect_imports(
+        content,
+        &import_bases(&file.id, &file.source_path),
+        &get_claude_md_file_dir(&file.id),
+    );
+    // Update managed_path if not set (migrating old data on update)
+    if file.managed_path.is_none() {
+        file.managed_path = Some(get_claude_md_file_path(&file.id).to_string_lossy().to_string());
     }
-
-    Ok(items)
-}
-
-/// Scan a single file
-fn scan_single_file(
-    path: &Path,
-    file_type: ClaudeMdType,
-    imported_paths: &[String],
-    app_data: &crate::types::AppData,
-) -> Option<ClaudeMdScanItem> {
-    let path_str = path.to_string_lossy().to_string();
-
-    let metadata = path.metadata().ok()?;
-    let size = metadata.len();
-    let modified_at = metadata
-        .modified()
-        .ok()
-        .map(|t| chrono::DateTime::<Utc>::from(t).to_rfc3339())
-        .unwrap_or_default();
-
-    // Check if already imported
-    let is_imported = imported_paths.contains(&path_str);
-    let imported_id = if is_imported {
-        app_data
-            .claude_md_files
-            .iter()
-            .find(|f| f.source_path == path_str)
-            .map(|f| f.id.clone())
-    } else {
-        None
-    };
-
-    // Read preview content
-    // Use char_indices to safely handle UTF-8 multi-byte characters
-    let preview = fs::read_to_string(path).ok().map(|content| {
-        if content.chars().count() > PREVIEW_LENGTH {
-            // Find the byte index of the character at PREVIEW_LENGTH position
-            let byte_index = content
-                .char_indices()
-                .nth(PREVIEW_LENGTH)
-                .map(|(idx, _)| idx)
-                .unwrap_or(content.len());
-            format!("{}...", &content[..byte_index])
-        } else {
-            content
-        }
-    });
-
-    // Infer project name
-    let project_name = path
-        .parent()
-        .and_then(|p| {
-            // If parent directory is .claude, get parent's parent directory name
-            if p.file_name().map(|n| n == ".claude").unwrap_or(false) {
-                p.parent().and_then(|pp| pp.file_name())
-            } else {
-                p.file_name()
-            }
-        })
-        .and_then(|n| n.to_str())
-        .map(|s| s.to_string());
-
-    Some(ClaudeMdScanItem {
-        path: path_str,
-        file_type,
-        size,
-        modified_at,
-        is_imported,
-        imported_id,
-        preview,
-        project_name,
-    })
-}
-
-/// Check if directory should be excluded
-fn is_excluded_dir(entry: &walkdir::DirEntry) -> bool {
-    entry.file_type().is_dir()
-        && entry
-            .file_name()
-            .to_str()
-            .map(|name| EXCLUDED_DIRS.contains(&name) || name.starts_with('.'))
-            .unwrap_or(false)
+    Ok(())
 }
 
 // ============================================================================
@@ -334,7 +140,7 @@ pub fn import_claude_md(options: ClaudeMdImportOptions) -> Result<ClaudeMdImport
     }
 
     // Read file content
-    let content =
+    let mut content =
         fs::read_to_string(&source_path).map_err(|e| format!("Failed to read file: {}", e))?;
     println!("[import_claude_md] Read content, length: {}", content.len());
 
@@ -351,9 +157,25 @@ pub fn import_claude_md(options: ClaudeMdImportOptions) -> Result<ClaudeMdImport
 
     // Generate UUID for the file
     let id = Uuid::new_v4().to_string();
+    let source_str = source_path.to_string_lossy().to_string();
+
+    // Bring referenced files into storage, pointing the references at the copies
+    if options.include_imports {
+        let file_dir = get_claude_md_file_dir(&id);
+        let mut copier = ImportCopier::new(file_dir.join(MANAGED_IMPORTS_DIR));
+        let prefix = format!("{}/", MANAGED_IMPORTS_DIR);
+        content = match copier.copy(&content, &import_bases(&id, &source_str), &prefix) {
+            Ok(content) => content,
+            Err(e) => {
+                let _ = fs::remove_dir_all(&file_dir);
+                return Err(e);
+            }
+        };
+    }
 
     // Write content to independent file
     write_claude_md_content(&id, &content)?;
+    let imports = collect_imports(&content, &import_bases(&id, &source_str), &get_claude_md_file_dir(&id));
     let managed_path = get_claude_md_file_path(&id).to_string_lossy().to_string();
     println!("[import_claude_md] Written to managed path: {}", managed_path);
 
@@ -363,7 +185,7 @@ pub fn import_claude_md(options: ClaudeMdImportOptions) -> Result<ClaudeMdImport
         id,
         name,
         description: options.description.unwrap_or_default(),
-        source_path: source_path.to_string_lossy().to_string(),
+        source_path: source_str,
         source_type,
         content: String::new(), // Content stored in independent file
         managed_path: Some(managed_path),
@@ -374,6 +196,7 @@ pub fn import_claude_md(options: ClaudeMdImportOptions) -> Result<ClaudeMdImport
         updated_at: now,
         size,
         icon: None,
+        imports,
     };
     println!("[import_claude_md] Created file with id: {}", file.id);
 
@@ -399,7 +222,7 @@ pub fn import_claude_md(options: ClaudeMdImportOptions) -> Result<ClaudeMdImport
 }
 
 /// Infer file type
-fn infer_claude_md_type(path: &Path) -> ClaudeMdType {
+pub(crate) fn infer_claude_md_type(path: &Path) -> ClaudeMdType {
     let file_name = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
 
     if file_name == "CLAUDE.local.md" {
@@ -411,9 +234,31 @@ fn infer_claude_md_type(path: &Path) -> ClaudeMdType {
                 return ClaudeMdType::Global;
             }
         }
-        ClaudeMdType::Project
+        project_or_subdirectory(path)
+    } else {
+        project_or_subdirectory(path)
+    }
+}
+
+/// Directory a CLAUDE.md applies to (the parent of `.claude/` for
+/// `.claude/CLAUDE.md`)
+pub(crate) fn owning_dir(path: &Path) -> Option<&Path> {
+    let parent = path.parent()?;
+    if parent.file_name().map(|n| n == ".claude").unwrap_or(false) {
+        parent.parent()
     } else {
-        ClaudeMdType::Project
+        Some(parent)
+    }
+}
+
+/// Subdirectory when the file lives below the root of a git repository
+fn project_or_subdirectory(path: &Path) -> ClaudeMdType {
+    let Some(dir) = owning_dir(path) else {
+        return ClaudeMdType::Project;
+    };
+    match find_repo_root(dir) {
+        Some(root) if root != dir => ClaudeMdType::Subdirectory,
+        _ => ClaudeMdType::Project,
     }
 }
 
@@ -521,13 +366,7 @@ pub fn update_claude_md(
 
     // Update content - write to independent file
     if let Some(c) = content {
-        file.size = c.len() as u64;
-        // Write content to independent file
-        write_claude_md_content(&id, &c)?;
-        // Update managed_path if not set (migrating old data on update)
-        if file.managed_path.is_none() {
-            file.managed_path = Some(get_claude_md_file_path(&id).to_string_lossy().to_string());
-        }
+        store_claude_md_content(file, &c, None)?;
         updated_content = Some(c);
     }
 
@@ -569,8 +408,8 @@ pub fn update_claude_md(
 /// Delete CLAUDE.md file (remove from management)
 ///
 /// Note: This is a soft delete - files are moved to trash, not permanently deleted.
-/// If the file is currently set as global, we only remove it from management
-/// but keep ~/.claude/CLAUDE.md intact.
+/// If the file is in the global stack, ~/.claude/CLAUDE.md is recomposed from
+/// the remaining entries; if it was the only entry, the file is kept intact.
 #[tauri::command]
 pub fn delete_claude_md(id: String) -> Result<(), String> {
     let _guard = DATA_MUTEX.lock().map_err(|e| e.to_string())?;
@@ -579,19 +418,22 @@ pub fn delete_claude_md(id: String) -> Result<(), String> {
     // Find the file metadata before deletion (for saving to info.json)
     let file_metadata = app_data.claude_md_files.iter().find(|f| f.id == id).cloned();
 
-    // If it's the current global, just unset the global status
-    // but DO NOT delete ~/.claude/CLAUDE.md
-    if app_data.global_claude_md_id.as_ref() == Some(&id) {
-        app_data.global_claude_md_id = None;
-        // Also unset isGlobal on the file
-        if let Some(file) = app_data.claude_md_files.iter_mut().find(|f| f.id == id) {
-            file.is_global = false;
+    // If it's in the global stack, take it out. Remaining enabled entries
+    // are recomposed; when it was the only one, ~/.claude/CLAUDE.md is kept
+    migrate_global_entries(&mut app_data);
+    if app_data.global_claude_md_entries.iter().any(|e| e.claude_md_id == id) {
+        app_data.global_claude_md_entries.retain(|e| e.claude_md_id != id);
+        if app_data.global_claude_md_entries.iter().any(|e| e.enabled) {
+            write_global_stack(&mut app_data)?;
+        } else {
+            sync_global_flags(&mut app_data);
         }
     }
 
     // Remove from Scene references
     for scene in app_data.scenes.iter_mut() {
         scene.claude_md_ids.retain(|cid| cid != &id);
+        scene.claude_md_subdirectories.remove(&id);
     }
 
     // Soft delete: move to trash instead of permanent deletion
@@ -607,7 +449,7 @@ pub fn delete_claude_md(id: String) -> Result<(), String> {
             }
         }
 
-        let trash_dir = get_app_data_dir().join("trash").join("claude-md");
+        let trash_dir = get_library_dir().join("trash").join("claude-md");
         if let Err(e) = fs::create_dir_all(&trash_dir) {
             println!("[delete_claude_md] Warning: Failed to create trash directory: {}", e);
         } else {
@@ -629,6 +471,7 @@ pub fn delete_claude_md(id: String) -> Result<(), String> {
 
     // Delete from data.json
     app_data.claude_md_files.retain(|f| f.id != id);
+    forget_distributions(&mut app_data, |d| d.claude_md_ids.contains(&id));
 
     write_app_data(app_data)?;
     Ok(())
@@ -638,124 +481,189 @@ pub fn delete_claude_md(id: String) -> Result<(), String> {
 // Global setting commands
 // ============================================================================
 
-/// Set a CLAUDE.md as global
+/// Path of the user-level global CLAUDE.md (~/.claude/CLAUDE.md)
+fn get_global_claude_md_path() -> Result<PathBuf, String> {
+    let home = dirs::home_dir().ok_or("Cannot get home directory")?;
+    Ok(home.join(".claude").join("CLAUDE.md"))
+}
+
+/// Carry a single global file from older data over into the stack
+pub(crate) fn migrate_global_entries(app_data: &mut AppData) {
+    if app_data.global_claude_md_entries.is_empty() {
+        if let Some(id) = &app_data.global_claude_md_id {
+            app_data.global_claude_md_entries.push(GlobalClaudeMdEntry {
+                claude_md_id: id.clone(),
+                enabled: true,
+            });
+        }
+    }
+}
+
+/// Update `is_global` flags and `global_claude_md_id` from the stack
+fn sync_global_flags(app_data: &mut AppData) {
+    let enabled: Vec<String> = app_data
+        .global_claude_md_entries
+        .iter()
+        .filter(|e| e.enabled)
+        .map(|e| e.claude_md_id.clone())
+        .collect();
+    let now = Utc::now().to_rfc3339();
+    for file in app_data.claude_md_files.iter_mut() {
+        let is_global = enabled.contains(&file.id);
+        if file.is_global != is_global {
+            file.is_global = is_global;
+            file.updated_at = now.clone();
+        }
+    }
+    app_data.global_claude_md_id = enabled.into_iter().next();
+}
+
+/// Compose the enabled global entries into ~/.claude/CLAUDE.md
 ///
-/// Flow:
-/// 1. Read current ~/.claude/CLAUDE.md
-/// 2. If exists and not managed by us, backup to ~/.ensemble/claude-md/global-backup/
-/// 3. Unset previous global file's isGlobal flag
-/// 4. Copy new file content to ~/.claude/CLAUDE.md
-/// 5. Set new file's isGlobal to true
+/// With no enabled entries the file is removed.
+fn write_global_stack(app_data: &mut AppData) -> Result<(), String> {
+    sync_global_flags(app_data);
+    let global_path = get_global_claude_md_path()?;
+
+    let mut files = Vec::new();
+    for entry in app_data.global_claude_md_entries.iter().filter(|e| e.enabled) {
+        let file = app_data
+            .claude_md_files
+            .iter()
+            .find(|f| f.id == entry.claude_md_id)
+            .ok_or_else(|| format!("CLAUDE.md file not found: {}", entry.claude_md_id))?;
+        files.push((file, read_stored_content(file)?));
+    }
+
+    if files.is_empty() {
+        if global_path.exists() {
+            fs::remove_file(&global_path).map_err(|e| e.to_string())?;
+        }
+        forget_distributions(app_data, |d| Path::new(&d.target_path) == global_path);
+        return Ok(());
+    }
+
+    // Render template variables (no project context for the global file)
+    let (content, _) = build_distributed_content(
+        app_data,
+        &files,
+        None,
+        &ClaudeMdImportMode::Keep,
+        &mut ImportCopier::dry_run(PathBuf::new()),
+    )?;
+    let ids: Vec<String> = files.iter().map(|(file, _)| file.id.clone()).collect();
+
+    // Ensure ~/.claude directory exists
+    if let Some(claude_dir) = global_path.parent() {
+        fs::create_dir_all(claude_dir).map_err(|e| e.to_string())?;
+    }
+    fs::write(&global_path, &content).map_err(|e| e.to_string())?;
+    record_distribution(
+        app_data,
+        ids,
+        &global_path,
+        None,
+        false,
+        ClaudeMdImportMode::Keep,
+        &content,
+    )
+}
+
+/// Keep an unmanaged ~/.claude/CLAUDE.md as the first entry of the stack
 ///
-/// # Arguments
-/// * `id` - ClaudeMdFile ID to set as global
-#[tauri::command]
-pub fn set_global_claude_md(id: String) -> Result<SetGlobalResult, String> {
-    let _guard = DATA_MUTEX.lock().map_err(|e| e.to_string())?;
-    let mut app_data = read_app_data()?;
+/// Before the first entry is added, existing content that Ensemble did not
+/// write is imported as "Original Global" (and backed up) so composing the
+/// stack does not displace it. Returns the imported ID and backup path.
+fn preserve_unmanaged_global(
+    app_data: &mut AppData,
+    global_path: &Path,
+) -> Result<(Option<String>, Option<String>), String> {
+    if !app_data.global_claude_md_entries.is_empty() || !global_path.exists() {
+        return Ok((None, None));
+    }
 
-    // Find target file
-    let target_file = app_data
-        .claude_md_files
-        .iter()
-        .find(|f| f.id == id)
-        .ok_or_else(|| format!("CLAUDE.md file not found: {}", id))?
-        .clone();
+    let existing_content = fs::read_to_string(global_path)
+        .map_err(|e| format!("Failed to read existing global file: {}", e))?;
+    let existing_size = global_path.metadata().map(|m| m.len()).unwrap_or(0);
 
-    // Read content from independent file if managed_path exists, otherwise use content field (old data)
-    let content = if target_file.managed_path.is_some() {
-        read_claude_md_content(&target_file.id)?
-    } else {
-        // Backward compatibility: use content field for old data
-        target_file.content.clone()
+    // Create a new managed file for the existing global CLAUDE.md
+    let import_id = uuid::Uuid::new_v4().to_string();
+    let now = Utc::now().to_rfc3339();
+
+    let imported_file = ClaudeMdFile {
+        id: import_id.clone(),
+        name: "Original Global".to_string(),
+        description: "Original ~/.claude/CLAUDE.md content, kept as a global entry".to_string(),
+        content: String::new(), // Content stored in independent file
+        source_path: global_path.to_string_lossy().to_string(),
+        source_type: ClaudeMdType::Global,
+        category_id: None,
+        tag_ids: vec![],
+        is_global: false, // Set from the stack once written
+        managed_path: Some(get_claude_md_file_path(&import_id).to_string_lossy().to_string()),
+        created_at: now.clone(),
+        updated_at: now,
+        size: existing_size,
+        icon: None,
+        imports: Vec::new(),
     };
 
-    // Global file path
-    let home = dirs::home_dir().ok_or("Cannot get home directory")?;
-    let global_path = home.join(".claude").join("CLAUDE.md");
+    // Create independent file directory and save content
+    let import_dir = get_claude_md_file_dir(&import_id);
+    fs::create_dir_all(&import_dir).map_err(|e| format!("Failed to create import directory: {}", e))?;
+    write_claude_md_content(&import_id, &existing_content)?;
 
-    let mut backup_path: Option<String> = None;
-    let mut auto_imported_id: Option<String> = None;
-    let previous_global_id = app_data.global_claude_md_id.clone();
+    app_data.claude_md_files.push(imported_file);
+    app_data.global_claude_md_entries.push(GlobalClaudeMdEntry {
+        claude_md_id: import_id.clone(),
+        enabled: true,
+    });
 
-    // If global file exists, need to backup and auto-import
-    if global_path.exists() {
-        // Check if it's a file we manage (has is_global=true)
-        let is_managed = app_data.claude_md_files.iter().any(|f| f.is_global);
-
-        if !is_managed {
-            // Not a file we manage - auto-import it first so user doesn't lose it
-            let existing_content = fs::read_to_string(&global_path)
-                .map_err(|e| format!("Failed to read existing global file: {}", e))?;
-
-            let existing_size = global_path.metadata().map(|m| m.len()).unwrap_or(0);
-
-            // Create a new managed file for the existing global CLAUDE.md
-            let import_id = uuid::Uuid::new_v4().to_string();
-            let now = Utc::now().to_rfc3339();
-
-            let imported_file = ClaudeMdFile {
-                id: import_id.clone(),
-                name: "Original Global".to_string(),
-                description: "Auto-imported from ~/.claude/CLAUDE.md before replacement".to_string(),
-                content: String::new(), // Content stored in independent file
-                source_path: global_path.to_string_lossy().to_string(),
-                source_type: ClaudeMdType::Global,
-                category_id: None,
-                tag_ids: vec![],
-                is_global: false, // Not global anymore since we're replacing it
-                managed_path: Some(get_claude_md_file_path(&import_id).to_string_lossy().to_string()),
-                created_at: now.clone(),
-                updated_at: now,
-                size: existing_size,
-                icon: None,
-            };
-
-            // Create independent file directory and save content
-            let import_dir = get_claude_md_file_dir(&import_id);
-            fs::create_dir_all(&import_dir).map_err(|e| format!("Failed to create import directory: {}", e))?;
-            write_claude_md_content(&import_id, &existing_content)?;
-
-            // Add to app data
-            app_data.claude_md_files.push(imported_file);
-            auto_imported_id = Some(import_id);
-
-            // Also create a backup for safety
-            let backup_dir = get_global_backup_dir();
-            fs::create_dir_all(&backup_dir).map_err(|e| e.to_string())?;
+    // Also create a backup for safety
+    let backup_dir = get_global_backup_dir();
+    fs::create_dir_all(&backup_dir).map_err(|e| e.to_string())?;
 
-            let timestamp = Utc::now().format("%Y%m%d_%H%M%S");
-            let backup_file = backup_dir.join(format!("CLAUDE.md.{}.backup", timestamp));
-            fs::copy(&global_path, &backup_file).map_err(|e| e.to_string())?;
+    let timestamp = Utc::now().format("%Y%m%d_%H%M%S");
+    let backup_file = backup_dir.join(format!("CLAUDE.md.{}.backup", timestamp));
+    fs::copy(global_path, &backup_file).map_err(|e| e.to_string())?;
 
-            backup_path = Some(backup_file.to_string_lossy().to_string());
+    log::info!("[set_global_claude_md] Kept existing global file as 'Original Global'");
+    Ok((Some(import_id), Some(backup_file.to_string_lossy().to_string())))
+}
 
-            println!("[set_global_claude_md] Auto-imported existing global file as 'Original Global'");
-        }
-    }
+/// Add a CLAUDE.md to the global stack
+///
+/// Flow:
+/// 1. If ~/.claude/CLAUDE.md exists and is not managed by us, keep it as the
+///    first entry ("Original Global") and back it up
+/// 2. Append the file to the stack, or re-enable it if already there
+/// 3. Compose the enabled entries, in order, into ~/.claude/CLAUDE.md
+///
+/// # Arguments
+/// * `id` - ClaudeMdFile ID to add to the global stack
+#[tauri::command]
+pub fn set_global_claude_md(id: String) -> Result<SetGlobalResult, String> {
+    let _guard = DATA_MUTEX.lock().map_err(|e| e.to_string())?;
+    let mut app_data = read_app_data()?;
+    migrate_global_entries(&mut app_data);
 
-    // Unset previous global file's flag
-    for file in app_data.claude_md_files.iter_mut() {
-        if file.is_global {
-            file.is_global = false;
-            file.updated_at = Utc::now().to_rfc3339();
-        }
+    if !app_data.claude_md_files.iter().any(|f| f.id == id) {
+        return Err(format!("CLAUDE.md file not found: {}", id));
     }
 
-    // Ensure ~/.claude directory exists
-    let claude_dir = global_path.parent().unwrap();
-    fs::create_dir_all(claude_dir).map_err(|e| e.to_string())?;
-
-    // Write global file (using content from independent file or old data)
-    fs::write(&global_path, &content).map_err(|e| e.to_string())?;
+    let global_path = get_global_claude_md_path()?;
+    let previous_global_id = app_data.global_claude_md_id.clone();
+    let (auto_imported_id, backup_path) = preserve_unmanaged_global(&mut app_data, &global_path)?;
 
-    // Set new global flag
-    if let Some(file) = app_data.claude_md_files.iter_mut().find(|f| f.id == id) {
-        file.is_global = true;
-        file.updated_at = Utc::now().to_rfc3339();
+    match app_data.global_claude_md_entries.iter_mut().find(|e| e.claude_md_id == id) {
+        Some(entry) => entry.enabled = true,
+        None => app_data.global_claude_md_entries.push(GlobalClaudeMdEntry {
+            claude_md_id: id,
+            enabled: true,
+        }),
     }
 
-    app_data.global_claude_md_id = Some(id);
+    write_global_stack(&mut app_data)?;
     write_app_data(app_data)?;
 
     Ok(SetGlobalResult {
@@ -767,32 +675,79 @@ pub fn set_global_claude_md(id: String) -> Result<SetGlobalResult, String> {
     })
 }
 
+/// Get the global CLAUDE.md stack, in composition order
+#[tauri::command]
+pub fn get_global_claude_md_entries() -> Result<Vec<GlobalClaudeMdEntry>, String> {
+    let mut app_data = read_app_data()?;
+    migrate_global_entries(&mut app_data);
+    Ok(app_data.global_claude_md_entries)
+}
+
+/// Enable or disable an entry of the global stack and rewrite ~/.claude/CLAUDE.md
+#[tauri::command]
+pub fn toggle_global_claude_md(id: String, enabled: bool) -> Result<Vec<GlobalClaudeMdEntry>, String> {
+    let _guard = DATA_MUTEX.lock().map_err(|e| e.to_string())?;
+    let mut app_data = read_app_data()?;
+    migrate_global_entries(&mut app_data);
+
+    let entry = app_data
+        .global_claude_md_entries
+        .iter_mut()
+        .find(|e| e.claude_md_id == id)
+        .ok_or_else(|| format!("CLAUDE.md file is not in the global stack: {}", id))?;
+    entry.enabled = enabled;
+
+    write_global_stack(&mut app_data)?;
+    let entries = app_data.global_claude_md_entries.clone();
+    write_app_data(app_data)?;
+    Ok(entries)
+}
+
+/// Reorder the global stack and rewrite ~/.claude/CLAUDE.md
+///
+/// Entries missing from `ordered_ids` keep their relative order at the end.
+#[tauri::command]
+pub fn reorder_global_claude_md(ordered_ids: Vec<String>) -> Result<Vec<GlobalClaudeMdEntry>, String> {
+    let _guard = DATA_MUTEX.lock().map_err(|e| e.to_string())?;
+    let mut app_data = read_app_data()?;
+    migrate_global_entries(&mut app_data);
+
+    let entries = std::mem::take(&mut app_data.global_claude_md_entries);
+    app_data.global_claude_md_entries = apply_reorder(entries, &ordered_ids);
+
+    write_global_stack(&mut app_data)?;
+    let entries = app_data.global_claude_md_entries.clone();
+    write_app_data(app_data)?;
+    Ok(entries)
+}
+
+/// Remove an entry from the global stack and rewrite ~/.claude/CLAUDE.md
+#[tauri::command]
+pub fn remove_global_claude_md(id: String) -> Result<Vec<GlobalClaudeMdEntry>, String> {
+    let _guard = DATA_MUTEX.lock().map_err(|e| e.to_string())?;
+    let mut app_data = read_app_data()?;
+    migrate_global_entries(&mut app_data);
+
+    app_data.global_claude_md_entries.retain(|e| e.claude_md_id != id);
+
+    write_global_stack(&mut app_data)?;
+    let entries = app_data.global_claude_md_entries.clone();
+    write_app_data(app_data)?;
+    Ok(entries)
+}
+
 /// Unset global CLAUDE.md setting
 ///
 /// Flow:
-/// 1. Delete ~/.claude/CLAUDE.md
-/// 2. Unset file's isGlobal flag
+/// 1. Clear the global stack and unset isGlobal flags
+/// 2. Delete ~/.claude/CLAUDE.md
 #[tauri::command]
 pub fn unset_global_claude_md() -> Result<(), String> {
     let _guard = DATA_MUTEX.lock().map_err(|e| e.to_string())?;
     let mut app_data = read_app_data()?;
 
-    // Unset all global flags
-    for file in app_data.claude_md_files.iter_mut() {
-        if file.is_global {
-            file.is_global = false;
-            file.updated_at = Utc::now().to_rfc3339();
-        }
-    }
-
-    app_data.global_claude_md_id = None;
-
-    // Delete global file
-    let home = dirs::home_dir().ok_or("Cannot get home directory")?;
-    let global_path = home.join(".claude").join("CLAUDE.md");
-    if global_path.exists() {
-        fs::remove_file(&global_path).map_err(|e| e.to_string())?;
-    }
+    app_data.global_claude_md_entries.clear();
+    write_global_stack(&mut app_data)?;
 
     write_app_data(app_data)?;
     Ok(())
@@ -802,43 +757,76 @@ pub fn unset_global_claude_md() -> Result<(), String> {
 // Distribution commands
 // ============================================================================
 
-/// Distribute CLAUDE.md to project
-///
-/// # Arguments
-/// * `options` - Distribution options
-#[tauri::command]
-pub fn distribute_claude_md(
-    options: ClaudeMdDistributionOptions,
-) -> Result<ClaudeMdDistributionResult, String> {
-    let app_data = read_app_data()?;
-
-    // Find source file
-    let source_file = app_data
-        .claude_md_files
-        .iter()
-        .find(|f| f.id == options.claude_md_id)
-        .ok_or_else(|| format!("CLAUDE.md file not found: {}", options.claude_md_id))?;
+/// Read the stored (unrendered) content of a CLAUDE.md file
+pub(crate) fn read_stored_content(file: &ClaudeMdFile) -> Result<String, String> {
+    // Read content from independent file if managed_path exists, otherwise use content field (old data)
+    if file.managed_path.is_some() {
+        read_claude_md_content(&file.id)
+    } else {
+        // Backward compatibility: use content field for old data
+        Ok(file.content.clone())
+    }
+}
 
+/// Read the content of a distributable (non-global) CLAUDE.md file
+fn read_distributable_content(file: &ClaudeMdFile) -> Result<String, String> {
     // Check if it's a global file
-    if source_file.is_global {
+    if file.is_global {
         return Err(
             "Cannot distribute a global CLAUDE.md. Global files are already available everywhere."
                 .to_string(),
         );
     }
 
-    // Read content from independent file if managed_path exists, otherwise use content field (old data)
-    let content = if source_file.managed_path.is_some() {
-        read_claude_md_content(&source_file.id)?
-    } else {
-        // Backward compatibility: use content field for old data
-        source_file.content.clone()
+    read_stored_content(file)
+}
+
+/// Build template variables for rendering CLAUDE.md content
+///
+/// Built-ins are `os` and `user.home`; project targets add `project.name`,
+/// `project.path` and `scene.name`, then the registered project's own
+/// variables (which may override built-ins).
+fn template_variables(app_data: &AppData, project_path: Option<&Path>) -> HashMap<String, String> {
+    let mut vars = HashMap::new();
+    vars.insert("os".to_string(), std::env::consts::OS.to_string());
+    if let Some(home) = dirs::home_dir() {
+        vars.insert("user.home".to_string(), home.to_string_lossy().to_string());
+    }
+
+    let Some(path) = project_path else {
+        return vars;
     };
+    let project = app_data
+        .projects
+        .iter()
+        .find(|p| expand_path(&p.path) == path);
 
-    // Build target path
-    let project_path = expand_path(&options.project_path);
-    let target_path = project_path.join(options.target_path.as_str());
+    vars.insert("project.path".to_string(), path.to_string_lossy().to_string());
+    let name = match project {
+        Some(p) => p.name.clone(),
+        None => path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default(),
+    };
+    vars.insert("project.name".to_string(), name);
+
+    if let Some(project) = project {
+        if let Some(scene) = app_data.scenes.iter().find(|s| s.id == project.scene_id) {
+            vars.insert("scene.name".to_string(), scene.name.clone());
+        }
+        vars.extend(project.variables.clone());
+    }
 
+    vars
+}
+
+/// Write distributed content to `target_path`, applying the conflict strategy
+fn write_distributed_file(
+    target_path: &Path,
+    content: &str,
+    conflict_resolution: &ClaudeMdConflictResolution,
+) -> Result<ClaudeMdDistributionResult, String> {
     // Ensure parent directory exists
     if let Some(parent) = target_path.parent() {
         fs::create_dir_all(parent).map_err(|e| e.to_string())?;
@@ -849,7 +837,7 @@ pub fn distribute_claude_md(
 
     // Handle conflict
     if target_path.exists() {
-        match options.conflict_resolution {
+        match conflict_resolution {
             ClaudeMdConflictResolution::Skip => {
                 return Ok(ClaudeMdDistributionResult {
                     success: true,
@@ -863,18 +851,32 @@ pub fn distribute_claude_md(
                 // Create backup
                 let timestamp = Utc::now().format("%Y%m%d_%H%M%S");
                 let backup_file = target_path.with_extension(format!("md.{}.backup", timestamp));
-                fs::copy(&target_path, &backup_file).map_err(|e| e.to_string())?;
+                fs::copy(target_path, &backup_file).map_err(|e| e.to_string())?;
                 backup_path = Some(backup_file.to_string_lossy().to_string());
                 action = "backed_up";
             }
             ClaudeMdConflictResolution::Overwrite => {
                 action = "overwritten";
             }
+            ClaudeMdConflictResolution::Managed => {
+                action = "merged";
+            }
         }
     }
 
-    // Write file (using content from independent file or old data)
-    fs::write(&target_path, &content).map_err(|e| e.to_string())?;
+    // Managed mode only replaces the region between the markers
+    let output = if *conflict_resolution == ClaudeMdConflictResolution::Managed {
+        let existing = if target_path.exists() {
+            Some(fs::read_to_string(target_path).map_err(|e| e.to_string())?)
+        } else {
+            None
+        };
+        apply_managed_region(existing.as_deref(), content)
+    } else {
+        content.to_string()
+    };
+
+    fs::write(target_path, output).map_err(|e| e.to_string())?;
 
     Ok(ClaudeMdDistributionResult {
         success: true,
@@ -885,45 +887,308 @@ pub fn distribute_claude_md(
     })
 }
 
-/// Batch distribute CLAUDE.md to project (for Scene)
+/// Apply the import mode to a file's content before it is distributed
+///
+/// In copy mode, `copier` places referenced files in the imports directory
+/// next to the target and references are rewritten to point there.
+fn resolve_distributed_imports(
+    file: &ClaudeMdFile,
+    content: &str,
+    import_mode: &ClaudeMdImportMode,
+    copier: &mut ImportCopier,
+) -> Result<String, String> {
+    let bases = import_bases(&file.id, &file.source_path);
+    match import_mode {
+        ClaudeMdImportMode::Keep => Ok(content.to_string()),
+        ClaudeMdImportMode::Inline => Ok(inline_imports(content, &bases)),
+        ClaudeMdImportMode::Copy => {
+            copier.copy(content, &bases, &format!("{}/", DISTRIBUTED_IMPORTS_DIR))
+        }
+    }
+}
+
+/// Directory that holds imports copied alongside `target_path`
+pub(crate) fn distributed_imports_dir(target_path: &Path) -> PathBuf {
+    let dir = target_path.parent().unwrap_or(Path::new("."));
+    dir.join(DISTRIBUTED_IMPORTS_DIR)
+}
+
+/// Build the content distributed for `files`: imports handled per
+/// `import_mode`, entries composed into sections, then the template rendered
+/// for `project_dir` (None for the global file)
+///
+/// Returns the content and template warnings.
+pub(crate) fn build_distributed_content(
+    app_data: &AppData,
+    files: &[(&ClaudeMdFile, String)],
+    project_dir: Option<&Path>,
+    import_mode: &ClaudeMdImportMode,
+    copier: &mut ImportCopier,
+) -> Result<(String, Vec<String>), String> {
+    let mut contents = Vec::with_capacity(files.len());
+    for (file, content) in files {
+        contents.push(resolve_distributed_imports(file, content, import_mode, copier)?);
+    }
+
+    let composed = compose_sections(
+        &files
+            .iter()
+            .zip(&contents)
+            .map(|((file, _), content)| ClaudeMdSection {
+                id: &file.id,
+                name: &file.name,
+                content,
+            })
+            .collect::<Vec<_>>(),
+    );
+
+    Ok(render_template(&composed, &template_variables(app_data, project_dir)))
+}
+
+/// Imports are left alone when the target would be skipped anyway
+fn effective_import_mode(
+    target_path: &Path,
+    import_mode: ClaudeMdImportMode,
+    conflict_resolution: &ClaudeMdConflictResolution,
+) -> ClaudeMdImportMode {
+    if target_path.exists() && *conflict_resolution == ClaudeMdConflictResolution::Skip {
+        ClaudeMdImportMode::Keep
+    } else {
+        import_mode
+    }
+}
+
+fn failed_distribution(target_path: &str, error: String) -> ClaudeMdDistributionResult {
+    ClaudeMdDistributionResult {
+        success: false,
+        target_path: target_path.to_string(),
+        action: "failed".to_string(),
+        backup_path: None,
+        error: Some(error),
+    }
+}
+
+/// Write a distribution and start tracking the written copy for sync
+///
+/// The caller persists `app_data`.
+fn write_and_track_distribution(
+    app_data: &mut AppData,
+    claude_md_ids: Vec<String>,
+    project_dir: &Path,
+    target_path: &Path,
+    content: &str,
+    conflict_resolution: &ClaudeMdConflictResolution,
+    import_mode: ClaudeMdImportMode,
+) -> Result<ClaudeMdDistributionResult, String> {
+    let result = write_distributed_file(target_path, content, conflict_resolution)?;
+    if result.action != "skipped" {
+        record_distribution(
+            app_data,
+            claude_md_ids,
+            target_path,
+            Some(project_dir),
+            *conflict_resolution == ClaudeMdConflictResolution::Managed,
+            import_mode,
+            content,
+        )?;
+    }
+    Ok(result)
+}
+
+/// Distribute CLAUDE.md to project
+///
+/// The written copy is tracked so later external edits can be detected and
+/// synced (see `get_claude_md_sync_status`).
+///
+/// # Arguments
+/// * `options` - Distribution options
+#[tauri::command]
+pub fn distribute_claude_md(
+    options: ClaudeMdDistributionOptions,
+) -> Result<ClaudeMdDistributionResult, String> {
+    let _guard = DATA_MUTEX.lock().map_err(|e| e.to_string())?;
+    let mut app_data = read_app_data()?;
+
+    // Find source file
+    let source_file = app_data
+        .claude_md_files
+        .iter()
+        .find(|f| f.id == options.claude_md_id)
+        .ok_or_else(|| format!("CLAUDE.md file not found: {}", options.claude_md_id))?;
+
+    let content = read_distributable_content(source_file)?;
+
+    // Build target path
+    let project_path = expand_path(&options.project_path);
+    let subdir = validate_subdirectory(options.subdirectory.as_deref().unwrap_or(""))?;
+    let target_path = project_path.join(subdir).join(options.target_path.as_str());
+
+    // Handle imports, then render template variables and conditionals for this project
+    let import_mode =
+        effective_import_mode(&target_path, options.import_mode, &options.conflict_resolution);
+    let (content, _) = build_distributed_content(
+        &app_data,
+        &[(source_file, content)],
+        Some(&project_path),
+        &import_mode,
+        &mut ImportCopier::new(distributed_imports_dir(&target_path)),
+    )?;
+
+    let result = write_and_track_distribution(
+        &mut app_data,
+        vec![options.claude_md_id],
+        &project_path,
+        &target_path,
+        &content,
+        &options.conflict_resolution,
+        import_mode,
+    )?;
+    write_app_data(app_data)?;
+
+    Ok(result)
+}
+
+/// Distribute a Scene's CLAUDE.md files to project (for Scene)
+///
+/// Files are grouped by the subdirectory they are mapped to, and each group
+/// is composed, in order, into a single target file in that directory with
+/// one delimited section per source. Files that cannot be read are reported
+/// as failed results and left out of the composed file.
 ///
 /// # Arguments
-/// * `claude_md_ids` - File ID list to distribute
+/// * `claude_md_ids` - File ID list to distribute, in section order
 /// * `project_path` - Target project path
 /// * `target_path` - Target file path
 /// * `conflict_resolution` - Conflict resolution strategy
+/// * `import_mode` - How `@path` imports are handled (default: keep)
+/// * `subdirectories` - File ID -> project subdirectory (default: the mapping
+///   saved on the project's Scene)
 #[tauri::command]
 pub fn distribute_scene_claude_md(
     claude_md_ids: Vec<String>,
     project_path: String,
     target_path: ClaudeMdDistributionPath,
     conflict_resolution: ClaudeMdConflictResolution,
+    import_mode: Option<ClaudeMdImportMode>,
+    subdirectories: Option<HashMap<String, String>>,
 ) -> Result<Vec<ClaudeMdDistributionResult>, String> {
+    let _guard = DATA_MUTEX.lock().map_err(|e| e.to_string())?;
+    let mut app_data = read_app_data()?;
+    let project_dir = expand_path(&project_path);
+    let subdirectories = subdirectories.unwrap_or_else(|| {
+        find_project_scene(&app_data, &project_dir)
+            .map(|scene| scene.claude_md_subdirectories.clone())
+            .unwrap_or_default()
+    });
+    let import_mode = import_mode.unwrap_or_default();
+
+    // Group IDs by target directory, keeping the Scene order within each
+    // group; the project root comes first
+    let mut groups: Vec<(String, Vec<&String>)> = vec![(String::new(), Vec::new())];
+    for id in &claude_md_ids {
+        let subdir = subdirectories.get(id).map(|s| s.trim().trim_matches('/')).unwrap_or("");
+        match groups.iter_mut().find(|(dir, _)| dir == subdir) {
+            Some((_, ids)) => ids.push(id),
+            None => groups.push((subdir.to_string(), vec![id])),
+        }
+    }
+
     let mut results: Vec<ClaudeMdDistributionResult> = Vec::new();
+    let mut failures: Vec<ClaudeMdDistributionResult> = Vec::new();
+
+    for (subdir, ids) in groups.into_iter().filter(|(_, ids)| !ids.is_empty()) {
+        let target = project_dir.join(&subdir).join(target_path.as_str());
+        let target_str = target.to_string_lossy().to_string();
+        if let Err(e) = validate_subdirectory(&subdir) {
+            failures.extend(ids.iter().map(|_| failed_distribution(&target_str, e.clone())));
+            continue;
+        }
+        let group_import_mode =
+            effective_import_mode(&target, import_mode.clone(), &conflict_resolution);
+
+        let mut sections: Vec<(&ClaudeMdFile, String)> = Vec::new();
+        for id in ids {
+            let content = app_data
+                .claude_md_files
+                .iter()
+                .find(|f| &f.id == id)
+                .ok_or_else(|| format!("CLAUDE.md file not found: {}", id))
+                .and_then(|file| read_distributable_content(file).map(|c| (file, c)));
+            match content {
+                Ok(section) => sections.push(section),
+                Err(e) => failures.push(failed_distribution(&target_str, e)),
+            }
+        }
 
-    for id in claude_md_ids {
-        let options = ClaudeMdDistributionOptions {
-            claude_md_id: id,
-            project_path: project_path.clone(),
-            target_path: target_path.clone(),
-            conflict_resolution: conflict_resolution.clone(),
-        };
+        if sections.is_empty() {
+            continue;
+        }
+
+        let section_ids: Vec<String> = sections.iter().map(|(file, _)| file.id.clone()).collect();
+        let composed = build_distributed_content(
+            &app_data,
+            &sections,
+            Some(&project_dir),
+            &group_import_mode,
+            &mut ImportCopier::new(distributed_imports_dir(&target)),
+        );
 
-        match distribute_claude_md(options) {
+        let written = composed.and_then(|(composed, _)| {
+            write_and_track_distribution(
+                &mut app_data,
+                section_ids,
+                &project_dir,
+                &target,
+                &composed,
+                &conflict_resolution,
+                group_import_mode,
+            )
+        });
+        match written {
             Ok(result) => results.push(result),
-            Err(e) => results.push(ClaudeMdDistributionResult {
-                success: false,
-                target_path: "".to_string(),
-                action: "failed".to_string(),
-                backup_path: None,
-                error: Some(e),
-            }),
+            Err(e) => results.push(failed_distribution(&target_str, e)),
         }
     }
+    write_app_data(app_data)?;
 
+    results.extend(failures);
     Ok(results)
 }
 
+/// Preview CLAUDE.md content as it would be distributed
+///
+/// Multiple IDs are composed like a Scene distribution. With a project path,
+/// project variables are available; without one, the file renders as it
+/// would for the global ~/.claude/CLAUDE.md.
+#[tauri::command]
+pub fn preview_claude_md(
+    claude_md_ids: Vec<String>,
+    project_path: Option<String>,
+) -> Result<ClaudeMdPreview, String> {
+    let app_data = read_app_data()?;
+
+    let mut files = Vec::new();
+    for id in &claude_md_ids {
+        let file = app_data
+            .claude_md_files
+            .iter()
+            .find(|f| &f.id == id)
+            .ok_or_else(|| format!("CLAUDE.md file not found: {}", id))?;
+        files.push((file, read_stored_content(file)?));
+    }
+
+    let project_dir = project_path.as_deref().map(expand_path);
+    let (content, warnings) = build_distributed_content(
+        &app_data,
+        &files,
+        project_dir.as_deref(),
+        &ClaudeMdImportMode::Keep,
+        &mut ImportCopier::dry_run(PathBuf::new()),
+    )?;
+
+    Ok(ClaudeMdPreview { content, warnings })
+}
+
 // ============================================================================
 // Migration
 // ============================================================================
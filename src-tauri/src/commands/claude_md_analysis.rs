@@ -0,0 +1,395 @@
+use crate::commands::claude_md::{
+    build_distributed_content, import_bases, migrate_global_entries, read_stored_content,
+};
+use crate::commands::claude_md_imports::{inline_imports, ImportCopier};
+use crate::commands::data::read_app_data;
+use crate::types::{
+    ClaudeMdAnalysis, ClaudeMdFile, ClaudeMdFileTokens, ClaudeMdImportMode, ClaudeMdInstructionRef,
+    ClaudeMdIssue, ClaudeMdSectionTokens,
+};
+use crate::utils::expand_path;
+use std::collections::HashSet;
+use std::path::PathBuf;
+
+/// Sections above this many tokens are reported as overly long
+const LONG_SECTION_TOKENS: u32 = 500;
+
+/// Instructions shorter than this (in words) are not compared
+const MIN_INSTRUCTION_WORDS: usize = 4;
+
+/// Word overlap above which two instructions are reported as duplicates
+const DUPLICATE_SIMILARITY: f64 = 0.85;
+
+/// Word overlap above which two instructions of opposite polarity are
+/// reported as contradictory
+const CONTRADICTION_SIMILARITY: f64 = 0.8;
+
+/// Words that turn an instruction into a prohibition ("don't" splits into
+/// "don" and "t")
+const NEGATIONS: &[&str] = &["never", "not", "no", "don", "dont", "avoid", "without"];
+
+/// Words ignored when comparing what two instructions are about
+const POLARITY_NOISE: &[&str] = &["always", "must", "should", "do", "t", "please", "ever"];
+
+// ============================================================================
+// Token estimation
+// ============================================================================
+
+/// Estimate the token count of `text`
+///
+/// Roughly four characters per token for ASCII text; other characters
+/// (CJK in particular) are counted as one token each.
+pub(crate) fn estimate_tokens(text: &str) -> u32 {
+    let (ascii, other) = text
+        .chars()
+        .fold((0u32, 0u32), |(a, o), c| if c.is_ascii() { (a + 1, o) } else { (a, o + 1) });
+    ascii.div_ceil(4) + other
+}
+
+/// Split markdown into `(heading, line, text)` sections at headings outside
+/// code blocks; content before the first heading has an empty heading
+fn split_markdown_sections(content: &str) -> Vec<(String, u32, String)> {
+    let mut sections: Vec<(String, u32, String)> = vec![(String::new(), 1, String::new())];
+    let mut in_fence = false;
+
+    for (index, line) in content.lines().enumerate() {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_fence = !in_fence;
+        }
+        let heading = trimmed.trim_start_matches('#');
+        if !in_fence && trimmed.starts_with('#') && (heading.is_empty() || heading.starts_with(' ')) {
+            sections.push((heading.trim().to_string(), index as u32 + 1, String::new()));
+        }
+        if let Some((_, _, text)) = sections.last_mut() {
+            text.push_str(line);
+            text.push('\n');
+        }
+    }
+
+    if sections[0].2.trim().is_empty() {
+        sections.remove(0);
+    }
+    sections
+}
+
+fn section_tokens(content: &str) -> Vec<ClaudeMdSectionTokens> {
+    split_markdown_sections(content)
+        .into_iter()
+        .map(|(heading, line, text)| ClaudeMdSectionTokens {
+            heading,
+            line,
+            tokens: estimate_tokens(&text),
+        })
+        .collect()
+}
+
+// ============================================================================
+// Instruction analysis
+// ============================================================================
+
+/// A single instruction line of a CLAUDE.md
+struct Instruction {
+    reference: ClaudeMdInstructionRef,
+    words: HashSet<String>,
+    /// Words describing what the instruction is about, without polarity words
+    subject: HashSet<String>,
+    negated: bool,
+}
+
+fn words_of(text: &str) -> Vec<String> {
+    text.to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .map(|w| w.to_string())
+        .collect()
+}
+
+/// Extract instruction lines: list items and paragraph lines outside code
+/// blocks, without headings, comments or list markers
+fn extract_instructions(claude_md_id: &str, content: &str) -> Vec<Instruction> {
+    let mut instructions = Vec::new();
+    let mut in_fence = false;
+
+    for (index, line) in content.lines().enumerate() {
+        let trimmed = line.trim();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_fence = !in_fence;
+            continue;
+        }
+        if in_fence || trimmed.is_empty() || trimmed.starts_with('#') || trimmed.starts_with("<!--") {
+            continue;
+        }
+
+        let text = trimmed.trim_start_matches(['-', '*', '+', '>', ' ']);
+        // Numbered list marker ("1." or "1)")
+        let numbered = text
+            .split_once(['.', ')'])
+            .filter(|(number, _)| !number.is_empty() && number.chars().all(|c| c.is_ascii_digit()));
+        let text = numbered.map(|(_, rest)| rest).unwrap_or(text).trim();
+        let words = words_of(text);
+        if words.len() < MIN_INSTRUCTION_WORDS {
+            continue;
+        }
+
+        let negated = words.iter().any(|w| NEGATIONS.contains(&w.as_str()));
+        let subject = words
+            .iter()
+            .filter(|w| !NEGATIONS.contains(&w.as_str()) && !POLARITY_NOISE.contains(&w.as_str()))
+            .cloned()
+            .collect();
+        instructions.push(Instruction {
+            reference: ClaudeMdInstructionRef {
+                claude_md_id: claude_md_id.to_string(),
+                line: index as u32 + 1,
+                text: text.to_string(),
+            },
+            words: words.into_iter().collect(),
+            subject,
+            negated,
+        });
+    }
+
+    instructions
+}
+
+/// Jaccard similarity of two word sets
+fn overlap(a: &HashSet<String>, b: &HashSet<String>) -> f64 {
+    if a.is_empty() || b.is_empty() {
+        return 0.0;
+    }
+    let intersection = a.intersection(b).count() as f64;
+    intersection / ((a.len() + b.len()) as f64 - intersection)
+}
+
+/// Instructions repeated across files, one issue per group of repeats
+fn find_duplicates(instructions: &[Instruction]) -> Vec<ClaudeMdIssue> {
+    let mut grouped = vec![false; instructions.len()];
+    let mut issues = Vec::new();
+
+    for i in 0..instructions.len() {
+        if grouped[i] {
+            continue;
+        }
+        let mut members = vec![i];
+        for j in (i + 1)..instructions.len() {
+            let other_file = members
+                .iter()
+                .all(|&m| instructions[m].reference.claude_md_id != instructions[j].reference.claude_md_id);
+            if !grouped[j]
+                && other_file
+                && instructions[i].negated == instructions[j].negated
+                && overlap(&instructions[i].words, &instructions[j].words) >= DUPLICATE_SIMILARITY
+            {
+                members.push(j);
+            }
+        }
+        if members.len() < 2 {
+            continue;
+        }
+        for &m in &members {
+            grouped[m] = true;
+        }
+        issues.push(ClaudeMdIssue {
+            kind: "duplicate".to_string(),
+            message: format!(
+                "\"{}\" is repeated in {} files",
+                instructions[i].reference.text,
+                members.len()
+            ),
+            instructions: members.iter().map(|&m| instructions[m].reference.clone()).collect(),
+        });
+    }
+
+    issues
+}
+
+/// Pairs of instructions about the same thing with opposite polarity
+/// (e.g. "Always use tabs" and "Never use tabs")
+fn find_contradictions(instructions: &[Instruction]) -> Vec<ClaudeMdIssue> {
+    let mut issues = Vec::new();
+    for (i, a) in instructions.iter().enumerate() {
+        for b in &instructions[i + 1..] {
+            if a.negated != b.negated
+                && a.subject.len() >= 2
+                && overlap(&a.subject, &b.subject) >= CONTRADICTION_SIMILARITY
+            {
+                issues.push(ClaudeMdIssue {
+                    kind: "contradiction".to_string(),
+                    message: format!("\"{}\" contradicts \"{}\"", a.reference.text, b.reference.text),
+                    instructions: vec![a.reference.clone(), b.reference.clone()],
+                });
+            }
+        }
+    }
+    issues
+}
+
+fn find_long_sections(file: &ClaudeMdFileTokens) -> Vec<ClaudeMdIssue> {
+    file.sections
+        .iter()
+        .filter(|s| s.tokens > LONG_SECTION_TOKENS)
+        .map(|s| ClaudeMdIssue {
+            kind: "long_section".to_string(),
+            message: format!(
+                "Section \"{}\" of {} is ~{} tokens",
+                if s.heading.is_empty() { "(top)" } else { &s.heading },
+                file.name,
+                s.tokens
+            ),
+            instructions: vec![ClaudeMdInstructionRef {
+                claude_md_id: file.id.clone(),
+                line: s.line,
+                text: s.heading.clone(),
+            }],
+        })
+        .collect()
+}
+
+// ============================================================================
+// Commands
+// ============================================================================
+
+/// Estimate tokens and check the quality of CLAUDE.md files loaded together
+///
+/// The selected files are measured as a Scene would distribute them (composed,
+/// imports inlined, template rendered for `project_path`). The global
+/// CLAUDE.md stack is loaded into every session, so it is included unless
+/// `include_global` is false. Duplicated instructions across files,
+/// contradictory directives and overly long sections are reported as issues.
+///
+/// # Arguments
+/// * `claude_md_ids` - Files loaded together, in section order
+/// * `project_path` - Project to render template variables for
+/// * `include_global` - Include the enabled global entries (default: true)
+#[tauri::command]
+pub fn analyze_claude_md(
+    claude_md_ids: Vec<String>,
+    project_path: Option<String>,
+    include_global: Option<bool>,
+) -> Result<ClaudeMdAnalysis, String> {
+    let mut app_data = read_app_data()?;
+    migrate_global_entries(&mut app_data);
+
+    let mut selected: Vec<(&ClaudeMdFile, String)> = Vec::new();
+    for id in &claude_md_ids {
+        let file = app_data
+            .claude_md_files
+            .iter()
+            .find(|f| &f.id == id)
+            .ok_or_else(|| format!("CLAUDE.md file not found: {}", id))?;
+        if !file.is_global {
+            selected.push((file, read_stored_content(file)?));
+        }
+    }
+    // Enabled global entries, in stack order
+    let mut global: Vec<(&ClaudeMdFile, String)> = Vec::new();
+    if include_global.unwrap_or(true) {
+        for entry in app_data.global_claude_md_entries.iter().filter(|e| e.enabled) {
+            if let Some(file) = app_data.claude_md_files.iter().find(|f| f.id == entry.claude_md_id) {
+                global.push((file, read_stored_content(file)?));
+            }
+        }
+    }
+
+    let measure = |files: &[(&ClaudeMdFile, String)], project_dir: Option<&std::path::Path>| {
+        if files.is_empty() {
+            return Ok(0);
+        }
+        build_distributed_content(
+            &app_data,
+            files,
+            project_dir,
+            &ClaudeMdImportMode::Inline,
+            &mut ImportCopier::dry_run(PathBuf::new()),
+        )
+        .map(|(content, _)| estimate_tokens(&content))
+    };
+    let project_dir = project_path.as_deref().map(expand_path);
+    let composed_tokens = measure(&selected, project_dir.as_deref())?;
+    let global_tokens = measure(&global, None)?;
+
+    let mut files = Vec::new();
+    let mut instructions = Vec::new();
+    for (file, content) in global.iter().chain(selected.iter()) {
+        let tokens = estimate_tokens(content);
+        let inlined = inline_imports(content, &import_bases(&file.id, &file.source_path));
+        files.push(ClaudeMdFileTokens {
+            id: file.id.clone(),
+            name: file.name.clone(),
+            tokens,
+            import_tokens: estimate_tokens(&inlined).saturating_sub(tokens),
+            sections: section_tokens(content),
+        });
+        instructions.extend(extract_instructions(&file.id, content));
+    }
+
+    let mut issues = find_duplicates(&instructions);
+    issues.extend(find_contradictions(&instructions));
+    issues.extend(files.iter().flat_map(find_long_sections));
+
+    Ok(ClaudeMdAnalysis {
+        files,
+        composed_tokens,
+        global_tokens,
+        total_tokens: composed_tokens + global_tokens,
+        issues,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_estimate_tokens() {
+        assert_eq!(estimate_tokens(""), 0);
+        assert_eq!(estimate_tokens("abcd"), 1);
+        assert_eq!(estimate_tokens("abcde"), 2);
+        assert_eq!(estimate_tokens("中文"), 2);
+    }
+
+    #[test]
+    fn test_sections_ignore_headings_in_code() {
+        let content = "Intro\n# Style\nUse tabs\n```\n# not a heading\n```\n## Tests\nRun them\n";
+        let sections: Vec<(String, u32)> = split_markdown_sections(content)
+            .into_iter()
+            .map(|(heading, line, _)| (heading, line))
+            .collect();
+        assert_eq!(
+            sections,
+            vec![
+                (String::new(), 1),
+                ("Style".to_string(), 2),
+                ("Tests".to_string(), 7),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_duplicates_across_files_only() {
+        let mut instructions = extract_instructions("a", "- Run cargo fmt before every commit\n");
+        instructions.extend(extract_instructions("b", "# Rules\n* Run `cargo fmt` before every commit.\n"));
+        instructions.extend(extract_instructions("b", "1. Run cargo fmt before every commit\n"));
+
+        let issues = find_duplicates(&instructions);
+        assert_eq!(issues.len(), 1);
+        let lines: Vec<(&str, u32)> = issues[0]
+            .instructions
+            .iter()
+            .map(|r| (r.claude_md_id.as_str(), r.line))
+            .collect();
+        assert_eq!(lines, vec![("a", 1), ("b", 2)]);
+    }
+
+    #[test]
+    fn test_contradictions_need_opposite_polarity() {
+        let mut instructions = extract_instructions("a", "- Always use tabs for indentation\n");
+        instructions.extend(extract_instructions("b", "- Never use tabs for indentation\n"));
+        instructions.extend(extract_instructions("b", "- Use tabs for indentation always\n"));
+
+        let issues = find_contradictions(&instructions);
+        assert_eq!(issues.len(), 2);
+        assert!(issues.iter().all(|i| i.instructions.iter().any(|r| r.text.starts_with("Never"))));
+    }
+}
@@ -0,0 +1,195 @@
+use crate::commands::claude_md::{get_claude_md_storage_dir, read_stored_content, store_claude_md_content};
+use crate::commands::data::{read_app_data, write_app_data, DATA_MUTEX};
+use crate::types::{ClaudeMdFile, ClaudeMdRevision};
+use crate::utils::diff::unified_diff;
+use chrono::Utc;
+use std::fs;
+use std::path::{Path, PathBuf};
+use uuid::Uuid;
+
+// ============================================================================
+// Revision store layout
+// ============================================================================
+
+/// Get the revision directory of a managed file (~/.ensemble/claude-md/{id}/history/)
+fn get_history_dir(file_dir: &Path) -> PathBuf {
+    file_dir.join("history")
+}
+
+/// Get the revision index (history/index.json, oldest first)
+fn get_index_path(file_dir: &Path) -> PathBuf {
+    get_history_dir(file_dir).join("index.json")
+}
+
+fn get_revision_path(file_dir: &Path, revision_id: &str) -> PathBuf {
+    get_history_dir(file_dir).join(format!("{}.md", revision_id))
+}
+
+fn read_revisions(file_dir: &Path) -> Result<Vec<ClaudeMdRevision>, String> {
+    let path = get_index_path(file_dir);
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let content = fs::read_to_string(&path).map_err(|e| e.to_string())?;
+    serde_json::from_str(&content).map_err(|e| format!("Failed to parse {}: {}", path.display(), e))
+}
+
+fn write_revisions(file_dir: &Path, revisions: &[ClaudeMdRevision]) -> Result<(), String> {
+    let json = serde_json::to_string_pretty(revisions).map_err(|e| e.to_string())?;
+    fs::write(get_index_path(file_dir), json).map_err(|e| e.to_string())
+}
+
+/// Drop the oldest revisions beyond `limit` (0 keeps everything)
+fn prune_revisions(file_dir: &Path, revisions: &mut Vec<ClaudeMdRevision>, limit: u32) {
+    let limit = limit as usize;
+    if limit == 0 || revisions.len() <= limit {
+        return;
+    }
+    for old in revisions.drain(..revisions.len() - limit) {
+        let _ = fs::remove_file(get_revision_path(file_dir, &old.id));
+    }
+}
+
+/// Keep the current CLAUDE.md in `file_dir` as a revision before it is
+/// replaced by `new_content`
+///
+/// Nothing is saved when there is no current file or the content is
+/// unchanged. At most `limit` revisions are kept (0 = unlimited).
+pub(crate) fn save_revision(
+    file_dir: &Path,
+    new_content: &str,
+    note: Option<&str>,
+    limit: u32,
+) -> Result<Option<ClaudeMdRevision>, String> {
+    let Ok(current) = fs::read_to_string(file_dir.join("CLAUDE.md")) else {
+        return Ok(None);
+    };
+    if current == new_content {
+        return Ok(None);
+    }
+
+    let revision = ClaudeMdRevision {
+        id: Uuid::new_v4().to_string(),
+        created_at: Utc::now().to_rfc3339(),
+        size: current.len() as u64,
+        note: note.map(|n| n.to_string()),
+    };
+
+    fs::create_dir_all(get_history_dir(file_dir)).map_err(|e| e.to_string())?;
+    fs::write(get_revision_path(file_dir, &revision.id), &current)
+        .map_err(|e| format!("Failed to save revision: {}", e))?;
+
+    let mut revisions = read_revisions(file_dir)?;
+    revisions.push(revision.clone());
+    prune_revisions(file_dir, &mut revisions, limit);
+    write_revisions(file_dir, &revisions)?;
+
+    Ok(Some(revision))
+}
+
+fn read_revision_content(file_dir: &Path, revision_id: &str) -> Result<String, String> {
+    if !read_revisions(file_dir)?.iter().any(|r| r.id == revision_id) {
+        return Err(format!("Revision not found: {}", revision_id));
+    }
+    fs::read_to_string(get_revision_path(file_dir, revision_id))
+        .map_err(|e| format!("Failed to read revision: {}", e))
+}
+
+// ============================================================================
+// Commands
+// ============================================================================
+
+/// List saved revisions of a managed CLAUDE.md, newest first
+#[tauri::command]
+pub fn list_claude_md_revisions(id: String) -> Result<Vec<ClaudeMdRevision>, String> {
+    let mut revisions = read_revisions(&get_claude_md_storage_dir().join(&id))?;
+    revisions.reverse();
+    Ok(revisions)
+}
+
+/// Diff two revisions of a managed CLAUDE.md as a unified diff; when
+/// `to_revision_id` is None the current content is used as the right-hand side
+#[tauri::command]
+pub fn diff_claude_md_revisions(
+    id: String,
+    from_revision_id: String,
+    to_revision_id: Option<String>,
+) -> Result<String, String> {
+    let file_dir = get_claude_md_storage_dir().join(&id);
+    let from = read_revision_content(&file_dir, &from_revision_id)?;
+    let to = match to_revision_id {
+        Some(revision_id) => read_revision_content(&file_dir, &revision_id)?,
+        None => fs::read_to_string(file_dir.join("CLAUDE.md"))
+            .map_err(|e| format!("Failed to read CLAUDE.md content: {}", e))?,
+    };
+    Ok(unified_diff(&from, &to))
+}
+
+/// Restore a revision as the current content
+///
+/// The content being replaced is itself saved as a revision first, so a
+/// restore can be undone.
+#[tauri::command]
+pub fn restore_claude_md_revision(id: String, revision_id: String) -> Result<ClaudeMdFile, String> {
+    let _guard = DATA_MUTEX.lock().map_err(|e| e.to_string())?;
+    let mut app_data = read_app_data()?;
+
+    let content = read_revision_content(&get_claude_md_storage_dir().join(&id), &revision_id)?;
+    let file = app_data
+        .claude_md_files
+        .iter_mut()
+        .find(|f| f.id == id)
+        .ok_or_else(|| format!("CLAUDE.md file not found: {}", id))?;
+
+    store_claude_md_content(file, &content, Some(&format!("Before restoring {}", revision_id)))?;
+    file.updated_at = Utc::now().to_rfc3339();
+
+    let mut restored = file.clone();
+    write_app_data(app_data)?;
+
+    restored.content = read_stored_content(&restored)?;
+    Ok(restored)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn save(dir: &Path, content: &str, limit: u32) -> Option<ClaudeMdRevision> {
+        let revision = save_revision(dir, content, None, limit).unwrap();
+        fs::write(dir.join("CLAUDE.md"), content).unwrap();
+        revision
+    }
+
+    #[test]
+    fn test_save_revision_keeps_previous_content() {
+        let tmp = TempDir::new().unwrap();
+        let dir = tmp.path();
+
+        assert!(save(dir, "v1", 10).is_none(), "first write has nothing to keep");
+        let revision = save(dir, "v2", 10).unwrap();
+        assert!(save(dir, "v2", 10).is_none(), "unchanged content is not saved");
+
+        assert_eq!(read_revision_content(dir, &revision.id).unwrap(), "v1");
+        assert_eq!(read_revisions(dir).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_retention_limit_drops_oldest() {
+        let tmp = TempDir::new().unwrap();
+        let dir = tmp.path();
+        for i in 0..6 {
+            save(dir, &format!("v{}", i), 3);
+        }
+
+        let revisions = read_revisions(dir).unwrap();
+        let contents: Vec<String> = revisions
+            .iter()
+            .map(|r| read_revision_content(dir, &r.id).unwrap())
+            .collect();
+        assert_eq!(contents, vec!["v2", "v3", "v4"]);
+        let files = fs::read_dir(get_history_dir(dir)).unwrap().count();
+        assert_eq!(files, 4, "three revisions plus the index");
+    }
+}
@@ -0,0 +1,282 @@
+use crate::types::ClaudeMdImportRef;
+use crate::utils::claude_md::rewrite_import_references;
+use crate::utils::expand_path;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Maximum depth of nested imports that are followed (same limit as Claude Code)
+const MAX_IMPORT_DEPTH: usize = 5;
+
+/// Directory inside a managed CLAUDE.md's storage that holds imported files
+pub(crate) const MANAGED_IMPORTS_DIR: &str = "imports";
+
+/// Directory next to a distributed CLAUDE.md that holds copied imports
+pub(crate) const DISTRIBUTED_IMPORTS_DIR: &str = ".ensemble-imports";
+
+/// Resolve an import reference to an existing file
+///
+/// `~/` and absolute references are used as-is; relative references are
+/// tried against each base directory in order.
+fn resolve_reference(reference: &str, bases: &[PathBuf]) -> Option<PathBuf> {
+    if reference.starts_with('~') || Path::new(reference).is_absolute() {
+        let path = expand_path(reference);
+        return path.is_file().then_some(path);
+    }
+    bases.iter().map(|base| base.join(reference)).find(|p| p.is_file())
+}
+
+fn parent_dir(path: &Path) -> Vec<PathBuf> {
+    path.parent().map(|p| vec![p.to_path_buf()]).unwrap_or_default()
+}
+
+/// Collect the imports of `content`, following nested imports
+///
+/// `managed_dir` is the file's storage directory; references that resolve
+/// inside it are reported with their managed path.
+pub(crate) fn collect_imports(
+    content: &str,
+    bases: &[PathBuf],
+    managed_dir: &Path,
+) -> Vec<ClaudeMdImportRef> {
+    let mut imports = Vec::new();
+    let mut visited = Vec::new();
+    collect_at_depth(content, bases, managed_dir, 0, &mut visited, &mut imports);
+    imports
+}
+
+fn collect_at_depth(
+    content: &str,
+    bases: &[PathBuf],
+    managed_dir: &Path,
+    depth: usize,
+    visited: &mut Vec<PathBuf>,
+    imports: &mut Vec<ClaudeMdImportRef>,
+) {
+    let mut nested = Vec::new();
+    rewrite_import_references(content, |reference| {
+        let resolved = resolve_reference(reference, bases);
+        // One entry per resolved file, or per unresolved reference
+        match &resolved {
+            Some(path) if visited.contains(path) => return None,
+            Some(path) => {
+                visited.push(path.clone());
+                nested.push(path.clone());
+            }
+            None if imports
+                .iter()
+                .any(|i| i.resolved_path.is_none() && i.reference == reference) =>
+            {
+                return None
+            }
+            None => {}
+        }
+        imports.push(ClaudeMdImportRef {
+            reference: reference.to_string(),
+            resolved_path: resolved.as_ref().map(|p| p.to_string_lossy().to_string()),
+            managed_path: resolved
+                .as_ref()
+                .and_then(|p| p.strip_prefix(managed_dir).ok())
+                .map(|p| p.to_string_lossy().to_string()),
+        });
+        None
+    });
+
+    if depth + 1 >= MAX_IMPORT_DEPTH {
+        return;
+    }
+    for path in nested {
+        if let Ok(text) = fs::read_to_string(&path) {
+            collect_at_depth(&text, &parent_dir(&path), managed_dir, depth + 1, visited, imports);
+        }
+    }
+}
+
+/// Replace imports in `content` with the referenced file content
+///
+/// Nested imports are inlined too; references that cannot be resolved, or
+/// would create a cycle, are left as they are.
+pub(crate) fn inline_imports(content: &str, bases: &[PathBuf]) -> String {
+    inline_at_depth(content, bases, 0, &mut Vec::new())
+}
+
+fn inline_at_depth(content: &str, bases: &[PathBuf], depth: usize, stack: &mut Vec<PathBuf>) -> String {
+    if depth >= MAX_IMPORT_DEPTH {
+        return content.to_string();
+    }
+    rewrite_import_references(content, |reference| {
+        let path = resolve_reference(reference, bases)?;
+        if stack.contains(&path) {
+            return None;
+        }
+        let text = fs::read_to_string(&path).ok()?;
+        stack.push(path.clone());
+        let inlined = inline_at_depth(&text, &parent_dir(&path), depth + 1, stack);
+        stack.pop();
+        Some(inlined.trim_end().to_string())
+    })
+}
+
+/// Copies imported files into one flat directory and rewrites references
+/// to point at the copies
+pub(crate) struct ImportCopier {
+    dir: PathBuf,
+    /// Source file -> file name of its copy
+    copied: HashMap<PathBuf, String>,
+    /// False to only compute the rewritten content without copying anything
+    write: bool,
+}
+
+impl ImportCopier {
+    pub(crate) fn new(dir: PathBuf) -> Self {
+        ImportCopier {
+            dir,
+            copied: HashMap::new(),
+            write: true,
+        }
+    }
+
+    /// A copier that rewrites references as `new` would but writes nothing
+    pub(crate) fn dry_run(dir: PathBuf) -> Self {
+        ImportCopier {
+            write: false,
+            ..ImportCopier::new(dir)
+        }
+    }
+
+    /// Copy the files imported by `content` (and their imports) and return
+    /// the content with references rewritten
+    ///
+    /// `prefix` is the copy directory as seen from the file `content` will be
+    /// written to, e.g. `imports/`.
+    pub(crate) fn copy(&mut self, content: &str, bases: &[PathBuf], prefix: &str) -> Result<String, String> {
+        let mut error = None;
+        let output = self.copy_at_depth(content, bases, prefix, 0, &mut error);
+        match error {
+            Some(e) => Err(e),
+            None => Ok(output),
+        }
+    }
+
+    fn copy_at_depth(
+        &mut self,
+        content: &str,
+        bases: &[PathBuf],
+        prefix: &str,
+        depth: usize,
+        error: &mut Option<String>,
+    ) -> String {
+        rewrite_import_references(content, |reference| {
+            let path = resolve_reference(reference, bases)?;
+            if let Some(name) = self.copied.get(&path) {
+                return Some(format!("@{}{}", prefix, name));
+            }
+
+            let name = self.unique_name(&path);
+            // Register before recursing so import cycles terminate
+            self.copied.insert(path.clone(), name.clone());
+
+            let result = match fs::read_to_string(&path) {
+                // Copies live side by side, so nested references need no prefix
+                Ok(text) if depth + 1 < MAX_IMPORT_DEPTH => {
+                    let nested = self.copy_at_depth(&text, &parent_dir(&path), "", depth + 1, error);
+                    self.write_copy(&name, |dest| fs::write(dest, nested))
+                }
+                _ => self.write_copy(&name, |dest| fs::copy(&path, dest).map(|_| ())),
+            };
+
+            match result {
+                Ok(()) => Some(format!("@{}{}", prefix, name)),
+                Err(e) => {
+                    error.get_or_insert(format!("Failed to copy import {}: {}", path.display(), e));
+                    None
+                }
+            }
+        })
+    }
+
+    fn write_copy(
+        &self,
+        name: &str,
+        write: impl FnOnce(&Path) -> std::io::Result<()>,
+    ) -> Result<(), String> {
+        if !self.write {
+            return Ok(());
+        }
+        fs::create_dir_all(&self.dir).map_err(|e| e.to_string())?;
+        write(&self.dir.join(name)).map_err(|e| e.to_string())
+    }
+
+    /// File name for a copy of `path`, numbered when another file already uses it
+    fn unique_name(&self, path: &Path) -> String {
+        let file_name = path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| "import.md".to_string());
+        let taken = |name: &str| self.copied.values().any(|n| n == name);
+        if !taken(&file_name) {
+            return file_name;
+        }
+
+        let (stem, ext) = match file_name.rsplit_once('.') {
+            Some((stem, ext)) if !stem.is_empty() => (stem.to_string(), format!(".{}", ext)),
+            _ => (file_name.clone(), String::new()),
+        };
+        (2..)
+            .map(|n| format!("{}-{}{}", stem, n, ext))
+            .find(|name| !taken(name))
+            .unwrap_or(file_name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn setup() -> TempDir {
+        let tmp = TempDir::new().unwrap();
+        fs::create_dir_all(tmp.path().join("docs")).unwrap();
+        fs::write(tmp.path().join("docs/style.md"), "Style rules\n@nested.md\n").unwrap();
+        fs::write(tmp.path().join("docs/nested.md"), "Nested\n@style.md\n").unwrap();
+        tmp
+    }
+
+    #[test]
+    fn test_inline_imports_follows_nesting_and_stops_at_cycles() {
+        let tmp = setup();
+        let content = "# Project\n@docs/style.md\n@missing.md\n";
+        let inlined = inline_imports(content, &[tmp.path().to_path_buf()]);
+        assert_eq!(inlined, "# Project\nStyle rules\nNested\n@style.md\n@missing.md\n");
+    }
+
+    #[test]
+    fn test_copy_imports_rewrites_references() {
+        let tmp = setup();
+        let out = tmp.path().join("out");
+        let mut copier = ImportCopier::new(out.join("imports"));
+        let content = copier
+            .copy("See @docs/style.md\n", &[tmp.path().to_path_buf()], "imports/")
+            .unwrap();
+
+        assert_eq!(content, "See @imports/style.md\n");
+        assert_eq!(
+            fs::read_to_string(out.join("imports/style.md")).unwrap(),
+            "Style rules\n@nested.md\n"
+        );
+        assert_eq!(
+            fs::read_to_string(out.join("imports/nested.md")).unwrap(),
+            "Nested\n@style.md\n"
+        );
+
+        let imports = collect_imports(&content, std::slice::from_ref(&out), &out);
+        let managed: Vec<_> = imports.iter().filter_map(|i| i.managed_path.as_deref()).collect();
+        assert_eq!(
+            managed,
+            vec![
+                Path::new("imports/style.md").to_string_lossy(),
+                Path::new("imports/nested.md").to_string_lossy(),
+            ]
+        );
+    }
+}
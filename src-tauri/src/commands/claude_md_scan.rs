@@ -0,0 +1,664 @@
+use crate::commands::claude_md::{infer_claude_md_type, owning_dir};
+use crate::commands::data::{read_app_data, read_settings};
+use crate::types::{
+    ClaudeMdDistribution, ClaudeMdRepoDirectory, ClaudeMdRepoView, ClaudeMdScanItem, ClaudeMdScanProgress,
+    ClaudeMdScanResult,
+};
+use crate::utils::claude_md::find_import_references;
+use crate::utils::{expand_path, find_repo_root, get_app_data_dir};
+use ignore::{DirEntry, WalkBuilder, WalkState};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tauri::Emitter;
+
+// ============================================================================
+// Constants
+// ============================================================================
+
+/// Max scan depth
+const MAX_SCAN_DEPTH: usize = 10;
+
+/// Preview character count
+const PREVIEW_LENGTH: usize = 500;
+
+/// Files looked for in every visited directory
+///
+/// They are checked directly rather than matched while walking, so
+/// `.claude/CLAUDE.md` is found without descending into hidden directories
+/// and a gitignored `CLAUDE.local.md` is still reported.
+const CANDIDATE_FILES: &[&str] = &["CLAUDE.md", "CLAUDE.local.md", ".claude/CLAUDE.md"];
+
+/// Event emitted with `ClaudeMdScanProgress` payloads while scanning
+const SCAN_PROGRESS_EVENT: &str = "claude-md-scan-progress";
+
+/// Minimum time between progress events
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(100);
+
+// ============================================================================
+// Scan cache
+// ============================================================================
+
+/// Scan data of a file, reused while its modified time and size are unchanged
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CachedScanFile {
+    modified_at: String,
+    size: u64,
+    preview: Option<String>,
+    #[serde(default)]
+    imports: Vec<String>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct ScanCache {
+    files: HashMap<String, CachedScanFile>,
+}
+
+/// Get the scan cache path (~/.ensemble/claude-md-scan-cache.json)
+fn get_scan_cache_path() -> PathBuf {
+    get_app_data_dir().join("claude-md-scan-cache.json")
+}
+
+fn read_scan_cache() -> ScanCache {
+    fs::read_to_string(get_scan_cache_path())
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+/// Replace the cache entries under the scanned `roots` with `items`,
+/// keeping entries of roots that were not part of this scan
+fn merge_scan_cache(cache: &mut ScanCache, roots: &[PathBuf], items: &[ClaudeMdScanItem]) {
+    cache
+        .files
+        .retain(|path, _| !roots.iter().any(|root| Path::new(path).starts_with(root)));
+    for item in items {
+        cache.files.insert(
+            item.path.clone(),
+            CachedScanFile {
+                modified_at: item.modified_at.clone(),
+                size: item.size,
+                preview: item.preview.clone(),
+                imports: item.imports.clone(),
+            },
+        );
+    }
+}
+
+fn write_scan_cache(roots: &[PathBuf], items: &[ClaudeMdScanItem]) {
+    let mut cache = read_scan_cache();
+    merge_scan_cache(&mut cache, roots, items);
+    if let Ok(json) = serde_json::to_string(&cache) {
+        if let Err(e) = fs::write(get_scan_cache_path(), json) {
+            log::warn!("Failed to write CLAUDE.md scan cache: {}", e);
+        }
+    }
+}
+
+// ============================================================================
+// Scanning
+// ============================================================================
+
+/// Directory exclusion rules
+///
+/// Hidden directories are always skipped. Bare names match a directory with
+/// that name anywhere; entries with a path separator or `~` match one path.
+struct ScanExcludes {
+    names: Vec<String>,
+    paths: Vec<PathBuf>,
+}
+
+impl ScanExcludes {
+    fn new(patterns: &[String]) -> Self {
+        let (paths, names): (Vec<&String>, Vec<&String>) = patterns
+            .iter()
+            .partition(|p| p.contains('/') || p.contains('\\') || p.starts_with('~'));
+        ScanExcludes {
+            names: names.into_iter().cloned().collect(),
+            paths: paths.into_iter().map(|p| expand_path(p)).collect(),
+        }
+    }
+
+    fn is_excluded(&self, path: &Path) -> bool {
+        let name = path.file_name().map(|n| n.to_string_lossy()).unwrap_or_default();
+        name.starts_with('.') || self.names.iter().any(|n| *n == name) || self.paths.iter().any(|p| p == path)
+    }
+}
+
+/// State shared by the walker threads of one scan
+struct ScanState<'a> {
+    /// Imported source path -> ClaudeMdFile ID
+    imported: HashMap<String, String>,
+    cache: &'a ScanCache,
+    scanned_dirs: AtomicU32,
+    cached_files: AtomicU32,
+    items: Mutex<Vec<ClaudeMdScanItem>>,
+    /// Directories the walker could not read
+    errors: Mutex<Vec<String>>,
+    progress: &'a (dyn Fn(ClaudeMdScanProgress) + Sync),
+    last_progress: Mutex<Instant>,
+}
+
+impl<'a> ScanState<'a> {
+    fn new(
+        imported: HashMap<String, String>,
+        cache: &'a ScanCache,
+        progress: &'a (dyn Fn(ClaudeMdScanProgress) + Sync),
+    ) -> Self {
+        ScanState {
+            imported,
+            cache,
+            scanned_dirs: AtomicU32::new(0),
+            cached_files: AtomicU32::new(0),
+            items: Mutex::new(Vec::new()),
+            errors: Mutex::new(Vec::new()),
+            progress,
+            last_progress: Mutex::new(Instant::now()),
+        }
+    }
+
+    fn report(&self, current_path: &Path, done: bool) {
+        if !done {
+            let Ok(mut last) = self.last_progress.try_lock() else {
+                return;
+            };
+            if last.elapsed() < PROGRESS_INTERVAL {
+                return;
+            }
+            *last = Instant::now();
+        }
+        let found = self.items.lock().map(|items| items.len() as u32).unwrap_or(0);
+        (self.progress)(ClaudeMdScanProgress {
+            scanned_dirs: self.scanned_dirs.load(Ordering::Relaxed),
+            found,
+            current_path: current_path.to_string_lossy().to_string(),
+            done,
+        });
+    }
+
+    /// Check a directory for CLAUDE.md files
+    fn visit_dir(&self, dir: &Path) {
+        self.scanned_dirs.fetch_add(1, Ordering::Relaxed);
+        for candidate in CANDIDATE_FILES {
+            let path = dir.join(candidate);
+            if path.is_file() {
+                if let Some(item) = self.scan_file(&path) {
+                    if let Ok(mut items) = self.items.lock() {
+                        items.push(item);
+                    }
+                }
+            }
+        }
+        self.report(dir, false);
+    }
+
+    /// Build the scan item of a file, reusing cached data when unchanged
+    fn scan_file(&self, path: &Path) -> Option<ClaudeMdScanItem> {
+        let path_str = path.to_string_lossy().to_string();
+
+        let metadata = path.metadata().ok()?;
+        let size = metadata.len();
+        let modified_at = metadata
+            .modified()
+            .ok()
+            .map(|t| chrono::DateTime::<chrono::Utc>::from(t).to_rfc3339())
+            .unwrap_or_default();
+
+        let (preview, imports) = match self.cache.files.get(&path_str) {
+            Some(cached) if cached.modified_at == modified_at && cached.size == size => {
+                self.cached_files.fetch_add(1, Ordering::Relaxed);
+                (cached.preview.clone(), cached.imports.clone())
+            }
+            _ => {
+                let content = fs::read_to_string(path).ok();
+                let imports = content
+                    .as_deref()
+                    .map(find_import_references)
+                    .unwrap_or_default();
+                (content.map(|c| preview_of(&c)), imports)
+            }
+        };
+
+        let imported_id = self.imported.get(&path_str).cloned();
+
+        Some(ClaudeMdScanItem {
+            path: path_str,
+            file_type: infer_claude_md_type(path),
+            size,
+            modified_at,
+            is_imported: imported_id.is_some(),
+            imported_id,
+            preview,
+            project_name: project_name_of(path),
+            imports,
+            repo_root: owning_dir(path)
+                .and_then(find_repo_root)
+                .map(|root| root.to_string_lossy().to_string()),
+        })
+    }
+
+    /// Walk one scan root in parallel, honouring .gitignore files
+    fn walk(&self, root: &Path, excludes: &Arc<ScanExcludes>) {
+        let excludes = Arc::clone(excludes);
+        WalkBuilder::new(root)
+            .max_depth(Some(MAX_SCAN_DEPTH))
+            .follow_links(false)
+            .hidden(false)
+            .git_ignore(true)
+            .git_global(true)
+            .git_exclude(true)
+            // Only directories are walked; candidate files are checked per directory
+            .filter_entry(move |entry: &DirEntry| {
+                entry.file_type().map(|t| t.is_dir()).unwrap_or(false)
+                    && (entry.depth() == 0 || !excludes.is_excluded(entry.path()))
+            })
+            .build_parallel()
+            .run(|| {
+                Box::new(|entry| {
+                    match entry {
+                        Ok(entry) => self.visit_dir(entry.path()),
+                        Err(e) => {
+                            if let Ok(mut errors) = self.errors.lock() {
+                                errors.push(format!("{}: {}", root.display(), e));
+                            }
+                        }
+                    }
+                    WalkState::Continue
+                })
+            });
+    }
+}
+
+/// First `PREVIEW_LENGTH` characters of the content
+fn preview_of(content: &str) -> String {
+    // Use char_indices to safely handle UTF-8 multi-byte characters
+    match content.char_indices().nth(PREVIEW_LENGTH) {
+        Some((byte_index, _)) => format!("{}...", &content[..byte_index]),
+        None => content.to_string(),
+    }
+}
+
+/// Infer project name from the file location
+fn project_name_of(path: &Path) -> Option<String> {
+    path.parent()
+        .and_then(|p| {
+            // If parent directory is .claude, get parent's parent directory name
+            if p.file_name().map(|n| n == ".claude").unwrap_or(false) {
+                p.parent().and_then(|pp| pp.file_name())
+            } else {
+                p.file_name()
+            }
+        })
+        .and_then(|n| n.to_str())
+        .map(|s| s.to_string())
+}
+
+/// Group the CLAUDE.md files of a repository by the directory they apply to
+///
+/// Distributions whose target lies in a directory are listed with it, so
+/// managed and unmanaged files can be told apart.
+fn group_repo_files(
+    repo_root: &Path,
+    items: Vec<ClaudeMdScanItem>,
+    distributions: &[ClaudeMdDistribution],
+) -> Vec<ClaudeMdRepoDirectory> {
+    let relative_dir = |path: &Path| {
+        owning_dir(path)
+            .and_then(|dir| dir.strip_prefix(repo_root).ok())
+            .map(|dir| dir.to_string_lossy().replace('\\', "/"))
+    };
+
+    let mut directories: Vec<ClaudeMdRepoDirectory> = Vec::new();
+    for item in items {
+        let Some(dir) = relative_dir(Path::new(&item.path)) else {
+            continue;
+        };
+        match directories.iter_mut().find(|d| d.relative_dir == dir) {
+            Some(directory) => directory.files.push(item),
+            None => directories.push(ClaudeMdRepoDirectory {
+                relative_dir: dir,
+                files: vec![item],
+                distributed_ids: Vec::new(),
+            }),
+        }
+    }
+
+    for directory in directories.iter_mut() {
+        directory.files.sort_by(|a, b| a.path.cmp(&b.path));
+        for distribution in distributions {
+            if relative_dir(Path::new(&distribution.target_path)).as_deref()
+                == Some(directory.relative_dir.as_str())
+            {
+                for id in &distribution.claude_md_ids {
+                    if !directory.distributed_ids.contains(id) {
+                        directory.distributed_ids.push(id.clone());
+                    }
+                }
+            }
+        }
+    }
+
+    // Root first, then by path
+    directories.sort_by(|a, b| a.relative_dir.cmp(&b.relative_dir));
+    directories
+}
+
+/// Run a scan over `roots` (plus ~/.claude/CLAUDE.md)
+fn run_scan(
+    roots: Vec<PathBuf>,
+    excludes: &[String],
+    full_rescan: bool,
+    progress: &(dyn Fn(ClaudeMdScanProgress) + Sync),
+) -> Result<ClaudeMdScanResult, String> {
+    let start = Instant::now();
+
+    // Read imported file list
+    let app_data = read_app_data().unwrap_or_default();
+    let imported: HashMap<String, String> = app_data
+        .claude_md_files
+        .iter()
+        .map(|f| (f.source_path.clone(), f.id.clone()))
+        .collect();
+
+    let cache = if full_rescan { ScanCache::default() } else { read_scan_cache() };
+    let state = ScanState::new(imported, &cache, progress);
+    let excludes = Arc::new(ScanExcludes::new(excludes));
+
+    // User-level global: ~/.claude/CLAUDE.md
+    let home = dirs::home_dir().ok_or("Cannot get home directory")?;
+    let user_claude_md = home.join(".claude").join("CLAUDE.md");
+    if user_claude_md.is_file() {
+        if let Some(item) = state.scan_file(&user_claude_md) {
+            state.items.lock().map_err(|e| e.to_string())?.push(item);
+        }
+    }
+
+    for root in &roots {
+        state.walk(root, &excludes);
+    }
+    state.report(roots.last().map(|p| p.as_path()).unwrap_or(&home), true);
+
+    let mut items = state.items.into_inner().map_err(|e| e.to_string())?;
+
+    // Deduplicate (based on path); overlapping roots find files twice
+    items.sort_by(|a, b| a.path.cmp(&b.path));
+    items.dedup_by(|a, b| a.path == b.path);
+
+    let mut cached_roots = roots.clone();
+    cached_roots.push(user_claude_md);
+    write_scan_cache(&cached_roots, &items);
+
+    Ok(ClaudeMdScanResult {
+        items,
+        scanned_dirs: state.scanned_dirs.into_inner(),
+        duration: start.elapsed().as_millis() as u64,
+        errors: state.errors.into_inner().map_err(|e| e.to_string())?,
+        cached_files: state.cached_files.into_inner(),
+    })
+}
+
+// ============================================================================
+// Scan commands
+// ============================================================================
+
+/// Scan system for CLAUDE.md files
+///
+/// Directories are walked in parallel, skipping hidden and excluded
+/// directories and anything ignored by .gitignore. Files unchanged since the
+/// previous scan are not re-read unless `full_rescan` is set. Progress is
+/// emitted as `claude-md-scan-progress` events.
+///
+/// # Arguments
+/// * `scan_paths` - Additional paths to scan
+/// * `include_home` - Whether to include the scan roots configured in settings
+/// * `full_rescan` - Ignore the cache from the previous scan
+///
+/// # Returns
+/// * `ClaudeMdScanResult` - Scan result
+#[tauri::command]
+pub async fn scan_claude_md_files(
+    app: tauri::AppHandle,
+    scan_paths: Option<Vec<String>>,
+    include_home: Option<bool>,
+    full_rescan: Option<bool>,
+) -> Result<ClaudeMdScanResult, String> {
+    let settings = read_settings()?;
+
+    let mut roots: Vec<String> = scan_paths.unwrap_or_default();
+    if include_home.unwrap_or(true) {
+        roots.extend(settings.claude_md_scan_roots.clone());
+    }
+    let roots: Vec<PathBuf> = roots
+        .iter()
+        .map(|p| expand_path(p))
+        .filter(|p| p.is_dir())
+        .collect();
+
+    // Walk in a background thread so the UI keeps receiving progress events
+    tauri::async_runtime::spawn_blocking(move || {
+        run_scan(
+            roots,
+            &settings.claude_md_scan_excludes,
+            full_rescan.unwrap_or(false),
+            &|progress| {
+                let _ = app.emit(SCAN_PROGRESS_EVENT, progress);
+            },
+        )
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// List every CLAUDE.md in a repository, grouped by directory
+///
+/// The whole repository is walked with the configured excludes and its
+/// .gitignore rules, e.g. to see all package-level files of a monorepo.
+///
+/// # Arguments
+/// * `repo_path` - A path inside the repository (its git root is used)
+#[tauri::command]
+pub async fn get_repo_claude_md_files(repo_path: String) -> Result<ClaudeMdRepoView, String> {
+    let path = expand_path(&repo_path);
+    if !path.is_dir() {
+        return Err(format!("Directory not found: {}", path.display()));
+    }
+    let repo_root = find_repo_root(&path).unwrap_or(path);
+    let settings = read_settings()?;
+    let app_data = read_app_data()?;
+
+    tauri::async_runtime::spawn_blocking(move || {
+        let imported: HashMap<String, String> = app_data
+            .claude_md_files
+            .iter()
+            .map(|f| (f.source_path.clone(), f.id.clone()))
+            .collect();
+        let cache = read_scan_cache();
+        let progress = |_: ClaudeMdScanProgress| {};
+        let state = ScanState::new(imported, &cache, &progress);
+        state.walk(&repo_root, &Arc::new(ScanExcludes::new(&settings.claude_md_scan_excludes)));
+        let items = state.items.into_inner().map_err(|e| e.to_string())?;
+
+        Ok(ClaudeMdRepoView {
+            repo_path: repo_root.to_string_lossy().to_string(),
+            directories: group_repo_files(&repo_root, items, &app_data.claude_md_distributions),
+        })
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn touch(root: &Path, relative: &str) {
+        let path = root.join(relative);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, "# Rules\n").unwrap();
+    }
+
+    fn scan(root: &Path, cache: &ScanCache) -> (Vec<String>, u32) {
+        let progress = |_: ClaudeMdScanProgress| {};
+        let state = ScanState::new(HashMap::new(), cache, &progress);
+        let excludes = Arc::new(ScanExcludes::new(&["node_modules".to_string()]));
+        state.walk(root, &excludes);
+
+        let mut paths: Vec<String> = state
+            .items
+            .into_inner()
+            .unwrap()
+            .iter()
+            .map(|item| Path::new(&item.path).strip_prefix(root).unwrap().to_string_lossy().to_string())
+            .collect();
+        paths.sort();
+        (paths, state.cached_files.into_inner())
+    }
+
+    #[test]
+    fn test_walk_finds_claude_dir_and_honours_ignores() {
+        let tmp = TempDir::new().unwrap();
+        let repo = tmp.path().join("repo");
+        fs::create_dir_all(repo.join(".git")).unwrap();
+        fs::write(repo.join(".gitignore"), "ignored/\nCLAUDE.local.md\n").unwrap();
+
+        touch(&repo, "CLAUDE.md");
+        touch(&repo, ".claude/CLAUDE.md");
+        touch(&repo, "sub/CLAUDE.local.md");
+        touch(&repo, "ignored/CLAUDE.md");
+        touch(&repo, "node_modules/pkg/CLAUDE.md");
+        touch(&repo, ".hidden/CLAUDE.md");
+
+        let (paths, cached) = scan(&repo, &ScanCache::default());
+        let expected: Vec<String> = [".claude/CLAUDE.md", "CLAUDE.md", "sub/CLAUDE.local.md"]
+            .iter()
+            .map(|p| Path::new(p).to_string_lossy().to_string())
+            .collect();
+        assert_eq!(paths, expected);
+        assert_eq!(cached, 0);
+    }
+
+    #[test]
+    fn test_group_repo_files_by_directory() {
+        let tmp = TempDir::new().unwrap();
+        let repo = tmp.path().join("repo");
+        fs::create_dir_all(repo.join(".git")).unwrap();
+        touch(&repo, "CLAUDE.md");
+        touch(&repo, "packages/api/.claude/CLAUDE.md");
+        touch(&repo, "packages/api/CLAUDE.local.md");
+        touch(&repo, "packages/web/CLAUDE.md");
+
+        let progress = |_: ClaudeMdScanProgress| {};
+        let cache = ScanCache::default();
+        let state = ScanState::new(HashMap::new(), &cache, &progress);
+        state.walk(&repo, &Arc::new(ScanExcludes::new(&[])));
+        let items = state.items.into_inner().unwrap();
+        assert!(items.iter().all(|i| i.repo_root.as_deref() == Some(&*repo.to_string_lossy())));
+
+        let distribution = ClaudeMdDistribution {
+            id: "d1".to_string(),
+            claude_md_ids: vec!["api-rules".to_string()],
+            target_path: repo.join("packages/api/.claude/CLAUDE.md").to_string_lossy().to_string(),
+            project_path: Some(repo.to_string_lossy().to_string()),
+            managed_region: false,
+            import_mode: Default::default(),
+            content_hash: String::new(),
+            synced_at: String::new(),
+        };
+        let directories = group_repo_files(&repo, items, &[distribution]);
+
+        let summary: Vec<(&str, usize, Vec<String>)> = directories
+            .iter()
+            .map(|d| (d.relative_dir.as_str(), d.files.len(), d.distributed_ids.clone()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("", 1, vec![]),
+                ("packages/api", 2, vec!["api-rules".to_string()]),
+                ("packages/web", 1, vec![]),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_unchanged_files_are_reused_from_cache() {
+        let tmp = TempDir::new().unwrap();
+        touch(tmp.path(), "a/CLAUDE.md");
+        touch(tmp.path(), "b/CLAUDE.md");
+
+        let progress = |_: ClaudeMdScanProgress| {};
+        let empty = ScanCache::default();
+        let state = ScanState::new(HashMap::new(), &empty, &progress);
+        state.walk(tmp.path(), &Arc::new(ScanExcludes::new(&[])));
+        let cache = ScanCache {
+            files: state
+                .items
+                .into_inner()
+                .unwrap()
+                .into_iter()
+                .map(|item| {
+                    (
+                        item.path,
+                        CachedScanFile {
+                            modified_at: item.modified_at,
+                            size: item.size,
+                            preview: Some("cached".to_string()),
+                            imports: Vec::new(),
+                        },
+                    )
+                })
+                .collect(),
+        };
+
+        fs::write(tmp.path().join("b/CLAUDE.md"), "# Changed rules\n").unwrap();
+        let (_, cached) = scan(tmp.path(), &cache);
+        assert_eq!(cached, 1);
+    }
+
+    #[test]
+    fn test_scan_cache_merge_keeps_other_roots() {
+        let tmp = TempDir::new().unwrap();
+        touch(tmp.path(), "work/app/CLAUDE.md");
+        let cached = |preview: &str| CachedScanFile {
+            modified_at: String::new(),
+            size: 0,
+            preview: Some(preview.to_string()),
+            imports: Vec::new(),
+        };
+        let mut cache = ScanCache::default();
+        cache.files.insert("/other/CLAUDE.md".to_string(), cached("other"));
+        cache.files.insert(tmp.path().join("work/gone/CLAUDE.md").to_string_lossy().to_string(), cached("gone"));
+
+        let progress = |_: ClaudeMdScanProgress| {};
+        let empty = ScanCache::default();
+        let state = ScanState::new(HashMap::new(), &empty, &progress);
+        let root = tmp.path().join("work");
+        state.walk(&root, &Arc::new(ScanExcludes::new(&[])));
+        merge_scan_cache(&mut cache, std::slice::from_ref(&root), &state.items.into_inner().unwrap());
+
+        let mut paths: Vec<&String> = cache.files.keys().collect();
+        paths.sort();
+        let app = root.join("app/CLAUDE.md").to_string_lossy().to_string();
+        assert_eq!(paths, vec![&"/other/CLAUDE.md".to_string(), &app]);
+    }
+
+    #[test]
+    fn test_walk_reports_unreadable_roots() {
+        let tmp = TempDir::new().unwrap();
+        let progress = |_: ClaudeMdScanProgress| {};
+        let cache = ScanCache::default();
+        let state = ScanState::new(HashMap::new(), &cache, &progress);
+        let missing = tmp.path().join("missing");
+        state.walk(&missing, &Arc::new(ScanExcludes::new(&[])));
+
+        let errors = state.errors.into_inner().unwrap();
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].starts_with(&*missing.to_string_lossy()));
+    }
+}
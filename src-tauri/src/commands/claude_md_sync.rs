@@ -0,0 +1,532 @@
+use crate::commands::claude_md::{
+    build_distributed_content, distributed_imports_dir, get_claude_md_storage_dir,
+    read_stored_content, store_claude_md_content,
+};
+use crate::commands::claude_md_imports::ImportCopier;
+use crate::commands::data::{read_app_data, write_app_data, DATA_MUTEX};
+use crate::types::{
+    AppData, ClaudeMdDistribution, ClaudeMdFile, ClaudeMdImportMode, ClaudeMdSyncDiff,
+    ClaudeMdSyncStatus,
+};
+use crate::utils::claude_md::{
+    apply_managed_region, compose_sections, managed_region_content, split_sections,
+    ClaudeMdSection,
+};
+use crate::utils::diff::unified_diff;
+use chrono::Utc;
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::{Path, PathBuf};
+use uuid::Uuid;
+
+// ============================================================================
+// Distribution tracking
+// ============================================================================
+
+/// Directory holding the last-synced content of each tracked copy
+/// (~/.ensemble/claude-md/distributions/)
+fn get_distribution_base_dir() -> PathBuf {
+    get_claude_md_storage_dir().join("distributions")
+}
+
+fn get_distribution_base_path(id: &str) -> PathBuf {
+    get_distribution_base_dir().join(format!("{}.md", id))
+}
+
+/// Hash used to compare synced content; trailing whitespace is ignored
+fn sync_hash(content: &str) -> String {
+    Sha256::digest(content.trim_end().as_bytes())
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+/// Start (or restart) tracking the copy written to `target_path`
+///
+/// `content` is what Ensemble wrote: the whole file, or only the managed
+/// region when `managed_region` is set. The caller persists `app_data`.
+pub(crate) fn record_distribution(
+    app_data: &mut AppData,
+    claude_md_ids: Vec<String>,
+    target_path: &Path,
+    project_path: Option<&Path>,
+    managed_region: bool,
+    import_mode: ClaudeMdImportMode,
+    content: &str,
+) -> Result<(), String> {
+    let target = target_path.to_string_lossy().to_string();
+    let id = app_data
+        .claude_md_distributions
+        .iter()
+        .find(|d| d.target_path == target)
+        .map(|d| d.id.clone())
+        .unwrap_or_else(|| Uuid::new_v4().to_string());
+
+    fs::create_dir_all(get_distribution_base_dir()).map_err(|e| e.to_string())?;
+    fs::write(get_distribution_base_path(&id), content.trim_end())
+        .map_err(|e| format!("Failed to save synced content: {}", e))?;
+
+    app_data.claude_md_distributions.retain(|d| d.target_path != target);
+    app_data.claude_md_distributions.push(ClaudeMdDistribution {
+        id,
+        claude_md_ids,
+        target_path: target,
+        project_path: project_path.map(|p| p.to_string_lossy().to_string()),
+        managed_region,
+        import_mode,
+        content_hash: sync_hash(content),
+        synced_at: Utc::now().to_rfc3339(),
+    });
+    Ok(())
+}
+
+/// Stop tracking the distributions matching `predicate`
+pub(crate) fn forget_distributions(
+    app_data: &mut AppData,
+    predicate: impl Fn(&ClaudeMdDistribution) -> bool,
+) {
+    app_data.claude_md_distributions.retain(|d| {
+        if predicate(d) {
+            let _ = fs::remove_file(get_distribution_base_path(&d.id));
+            false
+        } else {
+            true
+        }
+    });
+}
+
+// ============================================================================
+// Sync state
+// ============================================================================
+
+/// Ensemble's part of the distributed copy: the whole file, or the managed
+/// region. None when the file (or its region) no longer exists.
+fn read_local(distribution: &ClaudeMdDistribution) -> Option<String> {
+    let text = fs::read_to_string(&distribution.target_path).ok()?;
+    if distribution.managed_region {
+        managed_region_content(&text).map(|c| c.to_string())
+    } else {
+        Some(text)
+    }
+}
+
+/// Managed files of a distribution with their stored content
+fn source_files<'a>(
+    app_data: &'a AppData,
+    distribution: &ClaudeMdDistribution,
+) -> Result<Vec<(&'a ClaudeMdFile, String)>, String> {
+    distribution
+        .claude_md_ids
+        .iter()
+        .map(|id| {
+            let file = app_data
+                .claude_md_files
+                .iter()
+                .find(|f| &f.id == id)
+                .ok_or_else(|| format!("CLAUDE.md file not found: {}", id))?;
+            Ok((file, read_stored_content(file)?))
+        })
+        .collect()
+}
+
+/// Content the managed source would be distributed as now
+///
+/// With `copy_imports` unset, copy-mode imports are rewritten but not copied.
+fn render_source(
+    app_data: &AppData,
+    distribution: &ClaudeMdDistribution,
+    copy_imports: bool,
+) -> Result<String, String> {
+    let files = source_files(app_data, distribution)?;
+    let imports_dir = distributed_imports_dir(Path::new(&distribution.target_path));
+    let mut copier = if copy_imports {
+        ImportCopier::new(imports_dir)
+    } else {
+        ImportCopier::dry_run(imports_dir)
+    };
+    let (content, _) = build_distributed_content(
+        app_data,
+        &files,
+        distribution.project_path.as_deref().map(Path::new),
+        &distribution.import_mode,
+        &mut copier,
+    )?;
+    Ok(content)
+}
+
+/// Compare a copy's current content and the source's rendering against the
+/// last-synced hash
+fn sync_state(distribution: &ClaudeMdDistribution, local: Option<&str>, source: &str) -> &'static str {
+    let Some(local) = local else {
+        return "missing";
+    };
+    let local_changed = sync_hash(local) != distribution.content_hash;
+    let source_changed = sync_hash(source) != distribution.content_hash;
+    match (local_changed, source_changed) {
+        _ if sync_hash(local) == sync_hash(source) => "in_sync",
+        (true, true) => "conflict",
+        (true, false) => "local_modified",
+        (false, true) => "source_modified",
+        (false, false) => "in_sync",
+    }
+}
+
+fn compute_status(app_data: &AppData, distribution: &ClaudeMdDistribution) -> ClaudeMdSyncStatus {
+    let local = read_local(distribution);
+    let (state, error) = match render_source(app_data, distribution, false) {
+        Ok(source) => (sync_state(distribution, local.as_deref(), &source), None),
+        Err(e) => ("error", Some(e)),
+    };
+    ClaudeMdSyncStatus {
+        distribution: distribution.clone(),
+        state: state.to_string(),
+        error,
+    }
+}
+
+fn find_distribution<'a>(app_data: &'a AppData, id: &str) -> Result<&'a ClaudeMdDistribution, String> {
+    app_data
+        .claude_md_distributions
+        .iter()
+        .find(|d| d.id == id)
+        .ok_or_else(|| format!("Distribution not found: {}", id))
+}
+
+/// Update the tracked hash and base content after a pull or push
+fn mark_synced(app_data: &mut AppData, id: &str, content: &str) -> Result<(), String> {
+    fs::write(get_distribution_base_path(id), content.trim_end())
+        .map_err(|e| format!("Failed to save synced content: {}", e))?;
+    if let Some(d) = app_data.claude_md_distributions.iter_mut().find(|d| d.id == id) {
+        d.content_hash = sync_hash(content);
+        d.synced_at = Utc::now().to_rfc3339();
+    }
+    Ok(())
+}
+
+// ============================================================================
+// Sync commands
+// ============================================================================
+
+/// Get the sync state of every tracked CLAUDE.md copy
+///
+/// States: `in_sync`, `local_modified` (edited outside Ensemble),
+/// `source_modified` (managed file changed since distribution), `conflict`
+/// (both), `missing` (copy deleted) and `error` (source unavailable).
+#[tauri::command]
+pub fn get_claude_md_sync_status() -> Result<Vec<ClaudeMdSyncStatus>, String> {
+    let app_data = read_app_data()?;
+    Ok(app_data
+        .claude_md_distributions
+        .iter()
+        .map(|d| compute_status(&app_data, d))
+        .collect())
+}
+
+/// Three-way diff of a distributed copy: last-synced base vs. the copy
+/// and vs. the managed source
+#[tauri::command]
+pub fn diff_claude_md_distribution(distribution_id: String) -> Result<ClaudeMdSyncDiff, String> {
+    let app_data = read_app_data()?;
+    let distribution = find_distribution(&app_data, &distribution_id)?;
+
+    let base = fs::read_to_string(get_distribution_base_path(&distribution.id)).unwrap_or_default();
+    let local = read_local(distribution);
+    let source = render_source(&app_data, distribution, false)?;
+
+    Ok(ClaudeMdSyncDiff {
+        status: compute_status(&app_data, distribution),
+        local_diff: unified_diff(&base, local.as_deref().unwrap_or("")),
+        source_diff: unified_diff(&base, &source),
+        base,
+        local,
+        source,
+    })
+}
+
+/// Pull external edits of a distributed copy back into the managed file(s)
+///
+/// Composed copies are split on their section markers. Sources whose
+/// content changes when rendered (template variables, inlined or copied
+/// imports) are refused unless `force` is set, since the rendered text
+/// would replace the template.
+#[tauri::command]
+pub fn pull_claude_md_distribution(
+    distribution_id: String,
+    force: Option<bool>,
+) -> Result<ClaudeMdSyncStatus, String> {
+    let _guard = DATA_MUTEX.lock().map_err(|e| e.to_string())?;
+    let mut app_data = read_app_data()?;
+    let distribution = find_distribution(&app_data, &distribution_id)?.clone();
+
+    let local = read_local(&distribution)
+        .ok_or_else(|| format!("Distributed copy not found: {}", distribution.target_path))?;
+
+    if !force.unwrap_or(false) {
+        let files = source_files(&app_data, &distribution)?;
+        let raw = compose_sections(
+            &files
+                .iter()
+                .map(|(file, content)| ClaudeMdSection {
+                    id: &file.id,
+                    name: &file.name,
+                    content,
+                })
+                .collect::<Vec<_>>(),
+        );
+        let rendered = render_source(&app_data, &distribution, false)?;
+        if raw.trim_end() != rendered.trim_end() {
+            return Err(
+                "The managed source uses template variables or imports that are rendered on \
+                 distribution; pulling would replace them with rendered text"
+                    .to_string(),
+            );
+        }
+    }
+
+    let sections: Vec<(String, String)> = match distribution.claude_md_ids.as_slice() {
+        [single] => vec![(single.clone(), local.clone())],
+        ids => {
+            let sections = split_sections(&local)
+                .ok_or("Section markers were removed from the distributed copy")?;
+            if let Some(missing) = ids.iter().find(|id| !sections.iter().any(|(s, _)| s == *id)) {
+                return Err(format!("Section for {} was removed from the distributed copy", missing));
+            }
+            sections
+                .into_iter()
+                .filter(|(id, _)| ids.contains(id))
+                .collect()
+        }
+    };
+
+    let now = Utc::now().to_rfc3339();
+    for (id, content) in sections {
+        if let Some(file) = app_data.claude_md_files.iter_mut().find(|f| f.id == id) {
+            store_claude_md_content(
+                file,
+                &format!("{}\n", content.trim_end()),
+                Some(&format!("Before pull from {}", distribution.target_path)),
+            )?;
+            file.updated_at = now.clone();
+        }
+    }
+
+    mark_synced(&mut app_data, &distribution.id, &local)?;
+    let status = compute_status(&app_data, find_distribution(&app_data, &distribution.id)?);
+    write_app_data(app_data)?;
+    Ok(status)
+}
+
+/// Push the managed source to a distributed copy
+///
+/// Refuses to overwrite a copy edited outside Ensemble unless `force` is set.
+/// Copies tracked with a managed region only replace that region.
+#[tauri::command]
+pub fn push_claude_md_distribution(
+    distribution_id: String,
+    force: Option<bool>,
+) -> Result<ClaudeMdSyncStatus, String> {
+    let _guard = DATA_MUTEX.lock().map_err(|e| e.to_string())?;
+    let mut app_data = read_app_data()?;
+    let distribution = find_distribution(&app_data, &distribution_id)?.clone();
+
+    if let Some(local) = read_local(&distribution) {
+        if sync_hash(&local) != distribution.content_hash && !force.unwrap_or(false) {
+            return Err(format!(
+                "{} was modified outside Ensemble; pull or diff the changes first",
+                distribution.target_path
+            ));
+        }
+    }
+
+    let content = render_source(&app_data, &distribution, true)?;
+    let target = Path::new(&distribution.target_path);
+    if let Some(parent) = target.parent() {
+        fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    let output = if distribution.managed_region {
+        let existing = fs::read_to_string(target).ok();
+        apply_managed_region(existing.as_deref(), &content)
+    } else {
+        content.clone()
+    };
+    fs::write(target, output).map_err(|e| e.to_string())?;
+
+    mark_synced(&mut app_data, &distribution.id, &content)?;
+    let status = compute_status(&app_data, find_distribution(&app_data, &distribution.id)?);
+    write_app_data(app_data)?;
+    Ok(status)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::path::ENV_TEST_LOCK;
+    use tempfile::TempDir;
+
+    /// Scope `ENSEMBLE_DATA_DIR` to a TempDir, holding [`ENV_TEST_LOCK`]
+    struct ScopedDataDir {
+        tempdir: TempDir,
+        prior: Option<String>,
+        _guard: std::sync::MutexGuard<'static, ()>,
+    }
+
+    impl ScopedDataDir {
+        fn new() -> Self {
+            let guard = ENV_TEST_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+            let prior = std::env::var("ENSEMBLE_DATA_DIR").ok();
+            let tempdir = TempDir::new().expect("create tempdir");
+            std::env::set_var("ENSEMBLE_DATA_DIR", tempdir.path().join("data"));
+            Self {
+                tempdir,
+                prior,
+                _guard: guard,
+            }
+        }
+    }
+
+    impl Drop for ScopedDataDir {
+        fn drop(&mut self) {
+            match &self.prior {
+                Some(v) => std::env::set_var("ENSEMBLE_DATA_DIR", v),
+                None => std::env::remove_var("ENSEMBLE_DATA_DIR"),
+            }
+        }
+    }
+
+    fn managed_file(id: &str, content: &str) -> ClaudeMdFile {
+        let mut file: ClaudeMdFile = serde_json::from_value(serde_json::json!({
+            "id": id, "name": id.to_uppercase(), "description": "", "sourcePath": "",
+            "sourceType": "project", "isGlobal": false, "createdAt": "", "updatedAt": "", "size": 0
+        }))
+        .unwrap();
+        store_claude_md_content(&mut file, content, None).unwrap();
+        file
+    }
+
+    /// Distribute `files` to `target` (into a managed region after the
+    /// user's notes when `managed_region` is set) and track the copy
+    fn distribute(files: Vec<ClaudeMdFile>, target: &Path, managed_region: bool) -> String {
+        let mut app_data = AppData {
+            claude_md_files: files,
+            ..AppData::default()
+        };
+        let ids = app_data.claude_md_files.iter().map(|f| f.id.clone()).collect();
+        record_distribution(&mut app_data, ids, target, None, managed_region, ClaudeMdImportMode::Keep, "")
+            .unwrap();
+        let distribution = app_data.claude_md_distributions[0].clone();
+
+        let content = render_source(&app_data, &distribution, false).unwrap();
+        let output = if managed_region {
+            apply_managed_region(Some("# My notes\n"), &content)
+        } else {
+            content.clone()
+        };
+        fs::write(target, output).unwrap();
+        mark_synced(&mut app_data, &distribution.id, &content).unwrap();
+        write_app_data(app_data).unwrap();
+        distribution.id
+    }
+
+    fn stored(id: &str) -> String {
+        let app_data = read_app_data().unwrap();
+        read_stored_content(app_data.claude_md_files.iter().find(|f| f.id == id).unwrap()).unwrap()
+    }
+
+    fn state(id: &str) -> String {
+        let app_data = read_app_data().unwrap();
+        compute_status(&app_data, find_distribution(&app_data, id).unwrap()).state
+    }
+
+    #[test]
+    fn test_pull_picks_up_external_edits() {
+        let scope = ScopedDataDir::new();
+        let target = scope.tempdir.path().join("CLAUDE.md");
+        let id = distribute(
+            vec![managed_file("a", "First\n"), managed_file("b", "Second\n")],
+            &target,
+            false,
+        );
+        assert_eq!(state(&id), "in_sync");
+
+        let edited = fs::read_to_string(&target).unwrap().replace("Second", "Second, edited");
+        fs::write(&target, edited).unwrap();
+        assert_eq!(state(&id), "local_modified");
+
+        let status = pull_claude_md_distribution(id.clone(), None).unwrap();
+        assert_eq!(status.state, "in_sync");
+        assert_eq!(stored("a"), "First\n");
+        assert_eq!(stored("b"), "Second, edited\n");
+    }
+
+    #[test]
+    fn test_push_writes_managed_region() {
+        let scope = ScopedDataDir::new();
+        let target = scope.tempdir.path().join("CLAUDE.md");
+        let id = distribute(vec![managed_file("a", "First\n")], &target, true);
+
+        let mut app_data = read_app_data().unwrap();
+        store_claude_md_content(&mut app_data.claude_md_files[0], "Updated\n", None).unwrap();
+        write_app_data(app_data).unwrap();
+        assert_eq!(state(&id), "source_modified");
+
+        let status = push_claude_md_distribution(id, None).unwrap();
+        assert_eq!(status.state, "in_sync");
+        let text = fs::read_to_string(&target).unwrap();
+        assert!(text.starts_with("# My notes\n"));
+        assert_eq!(managed_region_content(&text), Some("Updated"));
+    }
+
+    #[test]
+    fn test_push_refuses_local_edits_without_force() {
+        let scope = ScopedDataDir::new();
+        let target = scope.tempdir.path().join("CLAUDE.md");
+        let id = distribute(vec![managed_file("a", "First\n")], &target, false);
+        fs::write(&target, "Edited\n").unwrap();
+
+        assert!(push_claude_md_distribution(id.clone(), None).is_err());
+        assert_eq!(fs::read_to_string(&target).unwrap(), "Edited\n");
+
+        let status = push_claude_md_distribution(id, Some(true)).unwrap();
+        assert_eq!(status.state, "in_sync");
+        assert_eq!(fs::read_to_string(&target).unwrap(), "First\n");
+    }
+
+    #[test]
+    fn test_pull_refuses_templated_entries_without_force() {
+        let scope = ScopedDataDir::new();
+        let target = scope.tempdir.path().join("CLAUDE.md");
+        let id = distribute(vec![managed_file("a", "Runs on {{os}}\n")], &target, false);
+        let edited = format!("{}More\n", fs::read_to_string(&target).unwrap());
+        fs::write(&target, &edited).unwrap();
+
+        assert!(pull_claude_md_distribution(id.clone(), None).is_err());
+        assert_eq!(stored("a"), "Runs on {{os}}\n");
+
+        pull_claude_md_distribution(id, Some(true)).unwrap();
+        assert_eq!(stored("a"), edited);
+    }
+
+    fn distribution(content: &str) -> ClaudeMdDistribution {
+        ClaudeMdDistribution {
+            id: "d".to_string(),
+            claude_md_ids: vec!["a".to_string()],
+            target_path: "/tmp/CLAUDE.md".to_string(),
+            project_path: None,
+            managed_region: false,
+            import_mode: ClaudeMdImportMode::Keep,
+            content_hash: sync_hash(content),
+            synced_at: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_sync_state() {
+        let d = distribution("base\n");
+        assert_eq!(sync_state(&d, Some("base"), "base\n"), "in_sync");
+        assert_eq!(sync_state(&d, Some("edited"), "base"), "local_modified");
+        assert_eq!(sync_state(&d, Some("base"), "updated"), "source_modified");
+        assert_eq!(sync_state(&d, Some("edited"), "updated"), "conflict");
+        assert_eq!(sync_state(&d, Some("same"), "same"), "in_sync");
+        assert_eq!(sync_state(&d, None, "base"), "missing");
+    }
+}
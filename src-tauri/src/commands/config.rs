@@ -1,8 +1,21 @@
-use crate::types::{McpServer, ProjectConfigStatus};
+use crate::commands::agents::{count_deployed_agents, deploy_agents, remove_deployed_agents};
+use crate::commands::data::{find_project_scene, read_app_data};
+use crate::commands::deploy::{
+    deploy_skills, is_managed_skill_entry, project_deploy_settings, remove_deployed_skills,
+    SkillDeployment,
+};
+use crate::commands::library::get_library_dir;
+use crate::commands::settings_fragments::{
+    add_allow_rules, apply_settings_patches, fragment_patches, remove_managed_settings,
+};
+use crate::commands::slash_commands::{count_deployed_commands, deploy_commands, remove_deployed_commands};
+use crate::types::{McpServer, ProjectConfigStatus, SkillDeployResult};
+use crate::utils::claude_md::remove_managed_region;
 use crate::utils::expand_path;
 use serde_json::{json, Value};
 use std::collections::HashMap;
 use std::fs;
+use std::path::Path;
 
 /// Write MCP configuration to project's .mcp.json (project root)
 /// Note: Claude Code reads project-level MCP config from .mcp.json, not settings.local.json
@@ -59,50 +72,79 @@ pub fn write_mcp_config(project_path: String, mcp_servers: Vec<McpServer>) -> Re
     Ok(())
 }
 
-/// Sync project configuration - creates symlinks for skills and writes MCP config
+/// Sync project configuration - deploys skills, agents and slash commands and writes MCP config
+/// and settings
+///
+/// Agents are symlinked into .claude/agents; without `agentPaths`, the
+/// agents of the project's Scene are used. Skills are symlinked, or copied when the project (or its scene) uses copy
+/// mode or pins a skill version; slash commands (the Scene's, without
+/// `commandPaths`) follow the same mode into .claude/commands, keeping their
+/// namespace subfolders. Copies whose source
+/// changed are refreshed; locally modified copies are left untouched and reported.
+/// The settings fragments of the project's Scene are merged into
+/// .claude/settings.json / settings.local.json, replacing what the previous
+/// sync merged and preserving everything else; the permission rules saved on
+/// the Scene go to settings.local.json.
 #[tauri::command]
 #[allow(non_snake_case)]
 pub fn sync_project_config(
     projectPath: String,
     skillPaths: Vec<String>,
     mcpServers: Vec<McpServer>,
-) -> Result<(), String> {
+    agentPaths: Option<Vec<String>>,
+    commandPaths: Option<Vec<String>>,
+) -> Result<SkillDeployResult, String> {
     let project_dir = expand_path(&projectPath);
     let claude_dir = project_dir.join(".claude");
     let skills_dir = claude_dir.join("skills");
 
-    // Ensure directories exist
-    fs::create_dir_all(&skills_dir).map_err(|e| e.to_string())?;
+    let app_data = read_app_data()?;
+    let scene = find_project_scene(&app_data, &project_dir);
 
-    // Remove existing skill symlinks
-    if skills_dir.exists() {
-        for entry in fs::read_dir(&skills_dir).map_err(|e| e.to_string())? {
-            let entry = entry.map_err(|e| e.to_string())?;
-            let path = entry.path();
-            if path.symlink_metadata().map(|m| m.file_type().is_symlink()).unwrap_or(false) {
-                fs::remove_file(&path).map_err(|e| e.to_string())?;
-            }
-        }
-    }
+    let (mode, pinned) = project_deploy_settings(&project_dir);
+    let deployments: Vec<SkillDeployment> = skillPaths
+        .iter()
+        .map(|skill_path| {
+            let source = expand_path(skill_path);
+            let version_id = source
+                .file_name()
+                .and_then(|name| pinned.get(name.to_string_lossy().as_ref()))
+                .cloned();
+            SkillDeployment { source, version_id }
+        })
+        .collect();
 
-    // Create symlinks for skills
-    for skill_path in skillPaths {
-        let source = expand_path(&skill_path);
-        if let Some(skill_name) = source.file_name() {
-            let target = skills_dir.join(skill_name);
-            
-            #[cfg(unix)]
-            std::os::unix::fs::symlink(&source, &target).map_err(|e| e.to_string())?;
-            
-            #[cfg(windows)]
-            std::os::windows::fs::symlink_dir(&source, &target).map_err(|e| e.to_string())?;
-        }
-    }
+    let mut result = deploy_skills(&skills_dir, &deployments, &mode)?;
+
+    let agent_paths: Vec<_> = agentPaths
+        .unwrap_or_else(|| scene.map(|s| s.agent_ids.clone()).unwrap_or_default())
+        .iter()
+        .map(|p| expand_path(p))
+        .collect();
+    result.agents = deploy_agents(&claude_dir.join("agents"), &agent_paths, &get_library_dir().join("agents"))?;
+
+    let command_paths: Vec<_> = commandPaths
+        .unwrap_or_else(|| scene.map(|s| s.command_ids.clone()).unwrap_or_default())
+        .iter()
+        .map(|p| expand_path(p))
+        .collect();
+    let (commands, commands_skipped_modified) = deploy_commands(
+        &claude_dir.join("commands"),
+        &command_paths,
+        &get_library_dir().join("commands"),
+        &mode,
+    )?;
+    result.commands = commands;
+    result.commands_skipped_modified = commands_skipped_modified;
+
+    let mut patches = fragment_patches(scene.map(|s| s.settings_fragment_ids.as_slice()).unwrap_or_default())?;
+    add_allow_rules(&mut patches, scene.and_then(|s| s.permission_rules.clone()).unwrap_or_default());
+    result.settings_conflicts = apply_settings_patches(&claude_dir, &patches)?;
 
     // Write MCP configuration
     write_mcp_config(projectPath, mcpServers)?;
 
-    Ok(())
+    Ok(result)
 }
 
 /// Clear project configuration
@@ -114,16 +156,17 @@ pub fn clear_project_config(projectPath: String) -> Result<(), String> {
     let skills_dir = claude_dir.join("skills");
     let settings_path = claude_dir.join("settings.local.json");
 
-    // Remove skill symlinks
-    if skills_dir.exists() {
-        for entry in fs::read_dir(&skills_dir).map_err(|e| e.to_string())? {
-            let entry = entry.map_err(|e| e.to_string())?;
-            let path = entry.path();
-            if path.symlink_metadata().map(|m| m.file_type().is_symlink()).unwrap_or(false) {
-                fs::remove_file(&path).map_err(|e| e.to_string())?;
-            }
-        }
-    }
+    // Remove skill symlinks and unmodified copies
+    remove_deployed_skills(&skills_dir)?;
+
+    // Remove agent symlinks
+    remove_deployed_agents(&claude_dir.join("agents"), &get_library_dir().join("agents"))?;
+
+    // Remove command symlinks and unmodified copies
+    remove_deployed_commands(&claude_dir.join("commands"), &get_library_dir().join("commands"))?;
+
+    // Remove merged settings fragment entries
+    remove_managed_settings(&claude_dir)?;
 
     // Clear MCP config (.mcp.json in project root)
     let mcp_path = project_dir.join(".mcp.json");
@@ -149,26 +192,38 @@ pub fn clear_project_config(projectPath: String) -> Result<(), String> {
 
     // Clear CLAUDE.md files (all possible distribution paths)
     // 1. Project root: CLAUDE.md
-    let claude_md_root = project_dir.join("CLAUDE.md");
-    if claude_md_root.exists() {
-        let _ = fs::remove_file(&claude_md_root);
-    }
+    clear_claude_md_file(&project_dir.join("CLAUDE.md"));
 
     // 2. Claude directory: .claude/CLAUDE.md
-    let claude_md_dir = claude_dir.join("CLAUDE.md");
-    if claude_md_dir.exists() {
-        let _ = fs::remove_file(&claude_md_dir);
-    }
+    clear_claude_md_file(&claude_dir.join("CLAUDE.md"));
 
     // 3. Local: CLAUDE.local.md
-    let claude_md_local = project_dir.join("CLAUDE.local.md");
-    if claude_md_local.exists() {
-        let _ = fs::remove_file(&claude_md_local);
-    }
+    clear_claude_md_file(&project_dir.join("CLAUDE.local.md"));
 
     Ok(())
 }
 
+/// Remove a distributed CLAUDE.md file
+///
+/// Files with a managed region only lose that region, so user text outside
+/// the markers survives; the file is removed if nothing else is left.
+fn clear_claude_md_file(path: &Path) {
+    if !path.exists() {
+        return;
+    }
+    let remaining = fs::read_to_string(path)
+        .ok()
+        .and_then(|content| remove_managed_region(&content));
+    match remaining {
+        Some(text) if !text.trim().is_empty() => {
+            let _ = fs::write(path, text);
+        }
+        _ => {
+            let _ = fs::remove_file(path);
+        }
+    }
+}
+
 /// Get project configuration status
 #[tauri::command]
 pub fn get_project_config_status(project_path: String) -> Result<ProjectConfigStatus, String> {
@@ -182,18 +237,13 @@ pub fn get_project_config_status(project_path: String) -> Result<ProjectConfigSt
     let has_settings_local = settings_path.exists();
     let has_commands_md = commands_path.exists();
 
-    // Count skills (symlinks in skills dir)
+    // Count skills (symlinks and managed copies in skills dir)
     let skill_count = if skills_dir.exists() {
         fs::read_dir(&skills_dir)
             .map(|entries| {
                 entries
                     .filter_map(|e| e.ok())
-                    .filter(|e| {
-                        e.path()
-                            .symlink_metadata()
-                            .map(|m| m.file_type().is_symlink())
-                            .unwrap_or(false)
-                    })
+                    .filter(|e| is_managed_skill_entry(&e.path()))
                     .count() as u32
             })
             .unwrap_or(0)
@@ -213,11 +263,19 @@ pub fn get_project_config_status(project_path: String) -> Result<ProjectConfigSt
         0
     };
 
+    // Count agents (symlinks into the library agents dir)
+    let agent_count = count_deployed_agents(&claude_dir.join("agents"), &get_library_dir().join("agents"));
+
+    // Count slash commands (symlinks and managed copies in commands dir)
+    let command_count = count_deployed_commands(&claude_dir.join("commands"), &get_library_dir().join("commands"));
+
     Ok(ProjectConfigStatus {
         has_claude_dir,
         has_settings_local,
         has_commands_md,
         skill_count,
         mcp_count,
+        agent_count,
+        command_count,
     })
 }
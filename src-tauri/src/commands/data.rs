@@ -1,6 +1,12 @@
-use crate::types::{AppData, AppSettings, Category, Project, Scene, Tag, TrashedProject, TrashedScene};
-use crate::utils::{ensure_dir, get_app_data_dir, get_data_file_path, get_settings_file_path};
+use crate::commands::deploy::validate_deploy_mode;
+use crate::types::{
+    AppData, AppSettings, Category, GlobalClaudeMdEntry, Project, Scene, Tag, TrashedProject, TrashedScene,
+};
+use crate::utils::{ensure_dir, expand_path, get_app_data_dir, get_data_file_path, get_settings_file_path, validate_subdirectory};
+use serde_json::{Map, Value};
+use std::collections::HashMap;
 use std::fs;
+use std::path::Path;
 use std::sync::Mutex;
 use uuid::Uuid;
 
@@ -34,6 +40,12 @@ impl HasId for Tag {
     }
 }
 
+impl HasId for GlobalClaudeMdEntry {
+    fn id(&self) -> &str {
+        &self.claude_md_id
+    }
+}
+
 /// Pure function: reorder a `Vec<T>` so that ids in `ordered_ids` come first
 /// in the given order, with any remaining items appended in their original
 /// relative order.
@@ -129,9 +141,27 @@ pub fn read_settings() -> Result<AppSettings, String> {
     }
 }
 
+/// Overlay the fields present in `patch` on `stored`
+fn merge_settings(stored: &AppSettings, patch: Map<String, Value>) -> Result<AppSettings, String> {
+    let mut merged = match serde_json::to_value(stored).map_err(|e| e.to_string())? {
+        Value::Object(fields) => fields,
+        _ => Map::new(),
+    };
+    merged.extend(patch);
+    serde_json::from_value(Value::Object(merged)).map_err(|e| e.to_string())
+}
+
 /// Write application settings
+///
+/// Fields missing from `settings` keep their stored value, so settings the
+/// UI doesn't manage (such as the library location) survive a save.
 #[tauri::command]
-pub fn write_settings(settings: AppSettings) -> Result<(), String> {
+pub fn write_settings(settings: Map<String, Value>) -> Result<(), String> {
+    save_settings(&merge_settings(&read_settings()?, settings)?)
+}
+
+/// Replace the stored application settings
+pub(crate) fn save_settings(settings: &AppSettings) -> Result<(), String> {
     let settings_path = get_settings_file_path();
 
     // Ensure directory exists
@@ -139,7 +169,7 @@ pub fn write_settings(settings: AppSettings) -> Result<(), String> {
         ensure_dir(parent).map_err(|e| e.to_string())?;
     }
 
-    let json = serde_json::to_string_pretty(&settings).map_err(|e| e.to_string())?;
+    let json = serde_json::to_string_pretty(settings).map_err(|e| e.to_string())?;
     fs::write(&settings_path, json).map_err(|e| e.to_string())?;
 
     Ok(())
@@ -159,6 +189,14 @@ pub fn init_app_data() -> Result<(), String> {
     let mcps_dir = app_dir.join("mcps");
     ensure_dir(&mcps_dir).map_err(|e| e.to_string())?;
 
+    // Create agents directory
+    let agents_dir = app_dir.join("agents");
+    ensure_dir(&agents_dir).map_err(|e| e.to_string())?;
+
+    // Create slash commands directory
+    let commands_dir = app_dir.join("commands");
+    ensure_dir(&commands_dir).map_err(|e| e.to_string())?;
+
     // Initialize data.json if not exists
     let data_path = get_data_file_path();
     if !data_path.exists() {
@@ -188,12 +226,17 @@ pub fn init_app_data() -> Result<(), String> {
             projects: vec![],
             skill_metadata: std::collections::HashMap::new(),
             mcp_metadata: std::collections::HashMap::new(),
+            agent_metadata: std::collections::HashMap::new(),
+            command_metadata: std::collections::HashMap::new(),
+            settings_fragments: vec![],
             trashed_scenes: vec![],
             trashed_projects: vec![],
             imported_plugin_skills: vec![],
             imported_plugin_mcps: vec![],
             claude_md_files: vec![],
             global_claude_md_id: None,
+            global_claude_md_entries: vec![],
+            claude_md_distributions: vec![],
         };
         write_app_data(default_data)?;
     }
@@ -201,7 +244,7 @@ pub fn init_app_data() -> Result<(), String> {
     // Initialize settings.json if not exists
     let settings_path = get_settings_file_path();
     if !settings_path.exists() {
-        write_settings(AppSettings::default())?;
+        save_settings(&AppSettings::default())?;
     }
 
     Ok(())
@@ -354,7 +397,7 @@ pub fn get_scenes() -> Result<Vec<Scene>, String> {
 
 /// Add a new scene
 #[tauri::command]
-#[allow(non_snake_case)]
+#[allow(non_snake_case, clippy::too_many_arguments)]
 pub fn add_scene(
     name: String,
     description: String,
@@ -362,6 +405,9 @@ pub fn add_scene(
     skillIds: Vec<String>,
     mcpIds: Vec<String>,
     claudeMdIds: Option<Vec<String>>,
+    agentIds: Option<Vec<String>>,
+    commandIds: Option<Vec<String>>,
+    settingsFragmentIds: Option<Vec<String>>,
 ) -> Result<Scene, String> {
     println!("add_scene called: name={}, skillIds={:?}, mcpIds={:?}, claudeMdIds={:?}", name, skillIds, mcpIds, claudeMdIds);
     let _guard = DATA_MUTEX.lock().map_err(|e| e.to_string())?;
@@ -375,9 +421,15 @@ pub fn add_scene(
         icon,
         skill_ids: skillIds,
         mcp_ids: mcpIds,
+        agent_ids: agentIds.unwrap_or_default(),
+        command_ids: commandIds.unwrap_or_default(),
+        settings_fragment_ids: settingsFragmentIds.unwrap_or_default(),
+        permission_rules: None,
         claude_md_ids: claudeMdIds.unwrap_or_default(),
+        claude_md_subdirectories: HashMap::new(),
         created_at: chrono::Utc::now().to_rfc3339(),
         last_used: None,
+        deploy_mode: None,
     };
 
     data.scenes.push(scene.clone());
@@ -388,6 +440,7 @@ pub fn add_scene(
 
 /// Update a scene
 #[tauri::command]
+#[allow(clippy::too_many_arguments)]
 pub fn update_scene(
     id: String,
     name: Option<String>,
@@ -396,9 +449,14 @@ pub fn update_scene(
     skill_ids: Option<Vec<String>>,
     mcp_ids: Option<Vec<String>>,
     claude_md_ids: Option<Vec<String>>,
+    deploy_mode: Option<String>,
+    agent_ids: Option<Vec<String>>,
+    command_ids: Option<Vec<String>>,
+    settings_fragment_ids: Option<Vec<String>>,
 ) -> Result<(), String> {
     let _guard = DATA_MUTEX.lock().map_err(|e| e.to_string())?;
     let mut data = read_app_data()?;
+    let deploy_mode = deploy_mode.map(|m| validate_deploy_mode(&m)).transpose()?;
 
     if let Some(scene) = data.scenes.iter_mut().find(|s| s.id == id) {
         if let Some(n) = name {
@@ -416,9 +474,22 @@ pub fn update_scene(
         if let Some(m) = mcp_ids {
             scene.mcp_ids = m;
         }
+        if let Some(a) = agent_ids {
+            scene.agent_ids = a;
+        }
+        if let Some(c) = command_ids {
+            scene.command_ids = c;
+        }
+        if let Some(f) = settings_fragment_ids {
+            scene.settings_fragment_ids = f;
+        }
         if let Some(c) = claude_md_ids {
+            scene.claude_md_subdirectories.retain(|id, _| c.contains(id));
             scene.claude_md_ids = c;
         }
+        if let Some(m) = deploy_mode {
+            scene.deploy_mode = m;
+        }
         write_app_data(data)?;
         Ok(())
     } else {
@@ -426,6 +497,41 @@ pub fn update_scene(
     }
 }
 
+/// Map one of a scene's CLAUDE.md files to a project subdirectory
+///
+/// `subdirectory` is relative to the project root; None or "" distributes
+/// the file to the project root again.
+#[tauri::command]
+pub fn set_scene_claude_md_subdirectory(
+    scene_id: String,
+    claude_md_id: String,
+    subdirectory: Option<String>,
+) -> Result<Scene, String> {
+    let _guard = DATA_MUTEX.lock().map_err(|e| e.to_string())?;
+    let mut data = read_app_data()?;
+
+    let scene = data
+        .scenes
+        .iter_mut()
+        .find(|s| s.id == scene_id)
+        .ok_or("Scene not found")?;
+    if !scene.claude_md_ids.contains(&claude_md_id) {
+        return Err(format!("CLAUDE.md file is not part of the scene: {}", claude_md_id));
+    }
+
+    let subdir = validate_subdirectory(subdirectory.as_deref().unwrap_or(""))?;
+    if subdir.as_os_str().is_empty() {
+        scene.claude_md_subdirectories.remove(&claude_md_id);
+    } else {
+        let subdir = subdir.to_string_lossy().replace('\\', "/");
+        scene.claude_md_subdirectories.insert(claude_md_id, subdir);
+    }
+
+    let scene = scene.clone();
+    write_app_data(data)?;
+    Ok(scene)
+}
+
 /// Delete a scene (soft delete - moves to trashed_scenes)
 #[tauri::command]
 pub fn delete_scene(id: String) -> Result<(), String> {
@@ -444,10 +550,16 @@ pub fn delete_scene(id: String) -> Result<(), String> {
             icon: scene.icon,
             skill_ids: scene.skill_ids,
             mcp_ids: scene.mcp_ids,
+            agent_ids: scene.agent_ids,
+            command_ids: scene.command_ids,
+            settings_fragment_ids: scene.settings_fragment_ids,
+            permission_rules: scene.permission_rules,
             claude_md_ids: scene.claude_md_ids,
+            claude_md_subdirectories: scene.claude_md_subdirectories,
             created_at: scene.created_at,
             last_used: scene.last_used,
             deleted_at: chrono::Utc::now().to_rfc3339(),
+            deploy_mode: scene.deploy_mode,
         };
 
         data.trashed_scenes.push(trashed_scene);
@@ -459,6 +571,14 @@ pub fn delete_scene(id: String) -> Result<(), String> {
 
 // ============ Projects ============
 
+/// Scene used by the registered project at `project_dir`, if any
+pub(crate) fn find_project_scene<'a>(data: &'a AppData, project_dir: &Path) -> Option<&'a Scene> {
+    data.projects
+        .iter()
+        .find(|p| expand_path(&p.path) == project_dir)
+        .and_then(|project| data.scenes.iter().find(|s| s.id == project.scene_id))
+}
+
 /// Get all projects
 #[tauri::command]
 pub fn get_projects() -> Result<Vec<Project>, String> {
@@ -479,6 +599,9 @@ pub fn add_project(name: String, path: String, sceneId: Option<String>) -> Resul
         path,
         scene_id: sceneId.unwrap_or_default(),
         last_synced: None,
+        pinned_skill_versions: HashMap::new(),
+        deploy_mode: None,
+        variables: HashMap::new(),
     };
 
     data.projects.push(project.clone());
@@ -496,9 +619,12 @@ pub fn update_project(
     path: Option<String>,
     sceneId: Option<String>,
     lastSynced: Option<String>,
+    deployMode: Option<String>,
+    variables: Option<HashMap<String, String>>,
 ) -> Result<(), String> {
     let _guard = DATA_MUTEX.lock().map_err(|e| e.to_string())?;
     let mut data = read_app_data()?;
+    let deploy_mode = deployMode.map(|m| validate_deploy_mode(&m)).transpose()?;
 
     if let Some(project) = data.projects.iter_mut().find(|p| p.id == id) {
         if let Some(n) = name {
@@ -513,6 +639,12 @@ pub fn update_project(
         if let Some(l) = lastSynced {
             project.last_synced = Some(l);
         }
+        if let Some(m) = deploy_mode {
+            project.deploy_mode = m;
+        }
+        if let Some(v) = variables {
+            project.variables = v;
+        }
         write_app_data(data)?;
         Ok(())
     } else {
@@ -538,6 +670,9 @@ pub fn delete_project(id: String) -> Result<(), String> {
             scene_id: project.scene_id,
             last_synced: project.last_synced,
             deleted_at: chrono::Utc::now().to_rfc3339(),
+            pinned_skill_versions: project.pinned_skill_versions,
+            deploy_mode: project.deploy_mode,
+            variables: project.variables,
         };
 
         data.trashed_projects.push(trashed_project);
@@ -630,6 +765,15 @@ mod apply_reorder_tests {
         let out_ids: Vec<&str> = out.iter().map(|t| t.id.as_str()).collect();
         assert_eq!(out_ids, vec!["t3", "t1", "t2"]);
     }
+
+    #[test]
+    fn works_for_global_claude_md_entries() {
+        // Global stack entries are keyed by their CLAUDE.md id
+        let entry = |id: &str, enabled| GlobalClaudeMdEntry { claude_md_id: id.into(), enabled };
+        let items = vec![entry("a", true), entry("b", false), entry("c", true)];
+        let out = apply_reorder(items, &s(&["c", "b"]));
+        assert_eq!(out, vec![entry("c", true), entry("b", false), entry("a", true)]);
+    }
 }
 
 #[cfg(test)]
@@ -814,3 +958,55 @@ mod reorder_integration_tests {
         }
     }
 }
+
+#[cfg(test)]
+mod project_scene_tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn find_project_scene_by_path() {
+        let scene: Scene = serde_json::from_value(json!({
+            "id": "s1", "name": "Web", "description": "", "icon": "", "skillIds": [], "mcpIds": [],
+            "createdAt": "", "lastUsed": null,
+            "claudeMdSubdirectories": { "md1": "frontend" }
+        }))
+        .unwrap();
+        let project: Project = serde_json::from_value(json!({
+            "id": "p1", "name": "app", "path": "/work/app", "sceneId": "s1", "lastSynced": null
+        }))
+        .unwrap();
+        let data = AppData {
+            scenes: vec![scene],
+            projects: vec![project],
+            ..AppData::default()
+        };
+
+        let found = find_project_scene(&data, Path::new("/work/app")).expect("scene");
+        assert_eq!(found.claude_md_subdirectories.get("md1").map(String::as_str), Some("frontend"));
+        assert!(find_project_scene(&data, Path::new("/work/other")).is_none());
+    }
+}
+
+#[cfg(test)]
+mod settings_tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn merge_settings_keeps_fields_missing_from_the_patch() {
+        let stored = AppSettings {
+            library_dir: Some("/data/ensemble".to_string()),
+            claude_md_history_limit: 10,
+            ..AppSettings::default()
+        };
+        let patch = json!({ "terminalApp": "Warp", "hasCompletedImport": true });
+        let Value::Object(patch) = patch else { unreachable!() };
+
+        let merged = merge_settings(&stored, patch).unwrap();
+        assert_eq!(merged.terminal_app, "Warp");
+        assert!(merged.has_completed_import);
+        assert_eq!(merged.library_dir.as_deref(), Some("/data/ensemble"));
+        assert_eq!(merged.claude_md_history_limit, 10);
+    }
+}
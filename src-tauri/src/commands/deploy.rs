@@ -0,0 +1,381 @@
+use crate::commands::data::read_app_data;
+use crate::commands::skill_versions::{hash_working_tree, materialize_skill_version, tree_hash};
+use crate::types::{SkillDeployResult, SkillDeployStamp};
+use crate::utils::expand_path;
+use chrono::Utc;
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Stamp file marking a skill directory as a copy deployed by Ensemble
+pub const DEPLOY_STAMP_FILE: &str = ".ensemble-deploy.json";
+
+/// Skills are symlinked to the library (default)
+pub const DEPLOY_MODE_SYMLINK: &str = "symlink";
+/// Skills are copied into the project with a deploy stamp
+pub const DEPLOY_MODE_COPY: &str = "copy";
+
+/// A skill to deploy into a skills directory
+pub(crate) struct SkillDeployment {
+    /// Library skill directory
+    pub source: PathBuf,
+    /// Pinned version; pinned skills are always deployed as copies
+    pub version_id: Option<String>,
+}
+
+/// Validate a deployment mode parameter
+///
+/// Returns None for an empty string, which clears an override.
+pub(crate) fn validate_deploy_mode(mode: &str) -> Result<Option<String>, String> {
+    match mode {
+        "" => Ok(None),
+        DEPLOY_MODE_SYMLINK | DEPLOY_MODE_COPY => Ok(Some(mode.to_string())),
+        _ => Err(format!("Invalid deploy mode: {}", mode)),
+    }
+}
+
+/// Resolve the deployment mode and pinned versions for the project at `project_dir`
+///
+/// The project's own mode wins over its scene's; unregistered projects use symlinks.
+pub(crate) fn project_deploy_settings(project_dir: &Path) -> (String, HashMap<String, String>) {
+    let Ok(data) = read_app_data() else {
+        return (DEPLOY_MODE_SYMLINK.to_string(), HashMap::new());
+    };
+    let Some(project) = data
+        .projects
+        .iter()
+        .find(|p| expand_path(&p.path) == project_dir)
+    else {
+        return (DEPLOY_MODE_SYMLINK.to_string(), HashMap::new());
+    };
+
+    let mode = project
+        .deploy_mode
+        .clone()
+        .or_else(|| {
+            data.scenes
+                .iter()
+                .find(|s| s.id == project.scene_id)
+                .and_then(|s| s.deploy_mode.clone())
+        })
+        .unwrap_or_else(|| DEPLOY_MODE_SYMLINK.to_string());
+
+    (mode, project.pinned_skill_versions.clone())
+}
+
+fn is_symlink(path: &Path) -> bool {
+    path.symlink_metadata()
+        .map(|m| m.file_type().is_symlink())
+        .unwrap_or(false)
+}
+
+/// Read the deploy stamp of a copied skill directory
+pub(crate) fn read_stamp(dir: &Path) -> Option<SkillDeployStamp> {
+    if is_symlink(dir) {
+        return None;
+    }
+    let content = fs::read_to_string(dir.join(DEPLOY_STAMP_FILE)).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+/// Whether a .claude/skills entry is managed by Ensemble (symlink or stamped copy)
+pub(crate) fn is_managed_skill_entry(path: &Path) -> bool {
+    is_symlink(path) || (path.is_dir() && path.join(DEPLOY_STAMP_FILE).exists())
+}
+
+/// Whether a deployed copy was edited after deployment
+fn is_locally_modified(dir: &Path, stamp: &SkillDeployStamp) -> bool {
+    hash_working_tree(dir)
+        .map(|files| files != stamp.files)
+        .unwrap_or(true)
+}
+
+/// Whether a deployed copy still matches what `deployment` would deploy
+fn copy_is_current(deployment: &SkillDeployment, stamp: &SkillDeployStamp) -> bool {
+    match &deployment.version_id {
+        Some(version_id) => stamp.version_id.as_ref() == Some(version_id),
+        None => {
+            stamp.version_id.is_none()
+                && hash_working_tree(&deployment.source)
+                    .map(|files| tree_hash(&files) == stamp.source_hash)
+                    .unwrap_or(false)
+        }
+    }
+}
+
+/// Write a stamped copy of a skill to `target`, replacing any previous copy
+///
+/// The copy is assembled in a hidden sibling directory and renamed into
+/// place, so an interrupted deploy never leaves a half-written skill.
+fn write_copy(deployment: &SkillDeployment, target: &Path) -> Result<(), String> {
+    let name = target
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .ok_or("Invalid skill target")?;
+    let partial = target.with_file_name(format!(".{}.partial", name));
+    if partial.exists() {
+        fs::remove_dir_all(&partial).map_err(|e| e.to_string())?;
+    }
+
+    let (files, source_hash) = match &deployment.version_id {
+        Some(version_id) => {
+            let version = materialize_skill_version(&deployment.source, version_id, &partial)?;
+            let files: BTreeMap<String, String> = version
+                .files
+                .into_iter()
+                .map(|f| (f.path, f.hash))
+                .collect();
+            (files, version.content_hash)
+        }
+        None => {
+            let files = hash_working_tree(&deployment.source)?;
+            for rel_path in files.keys() {
+                let dest = partial.join(rel_path);
+                if let Some(parent) = dest.parent() {
+                    fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+                }
+                fs::copy(deployment.source.join(rel_path), &dest)
+                    .map_err(|e| format!("Failed to copy {}: {}", rel_path, e))?;
+            }
+            let hash = tree_hash(&files);
+            (files, hash)
+        }
+    };
+    fs::create_dir_all(&partial).map_err(|e| e.to_string())?;
+
+    let stamp = SkillDeployStamp {
+        source_path: deployment.source.to_string_lossy().to_string(),
+        version_id: deployment.version_id.clone(),
+        source_hash,
+        files,
+        deployed_at: Utc::now().to_rfc3339(),
+    };
+    let json = serde_json::to_string_pretty(&stamp).map_err(|e| e.to_string())?;
+    fs::write(partial.join(DEPLOY_STAMP_FILE), json).map_err(|e| e.to_string())?;
+
+    if target.exists() {
+        fs::remove_dir_all(target).map_err(|e| e.to_string())?;
+    }
+    fs::rename(&partial, target).map_err(|e| e.to_string())
+}
+
+fn create_symlink(source: &Path, target: &Path) -> Result<(), String> {
+    #[cfg(unix)]
+    std::os::unix::fs::symlink(source, target).map_err(|e| e.to_string())?;
+
+    #[cfg(windows)]
+    std::os::windows::fs::symlink_dir(source, target).map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+/// Remove a deployed skill entry
+///
+/// Symlinks and unmodified copies are removed. Returns false when the entry
+/// was kept: a locally modified copy, or a directory Ensemble does not manage.
+pub(crate) fn remove_deployed_skill(path: &Path) -> Result<bool, String> {
+    if is_symlink(path) {
+        fs::remove_file(path).map_err(|e| e.to_string())?;
+        return Ok(true);
+    }
+    match read_stamp(path) {
+        Some(stamp) if !is_locally_modified(path, &stamp) => {
+            fs::remove_dir_all(path).map_err(|e| e.to_string())?;
+            Ok(true)
+        }
+        _ => Ok(false),
+    }
+}
+
+/// Remove all deployed skills from a skills directory, returning the names
+/// of locally modified copies that were left in place
+pub(crate) fn remove_deployed_skills(skills_dir: &Path) -> Result<Vec<String>, String> {
+    let mut kept = Vec::new();
+    if !skills_dir.exists() {
+        return Ok(kept);
+    }
+
+    for entry in fs::read_dir(skills_dir).map_err(|e| e.to_string())? {
+        let entry = entry.map_err(|e| e.to_string())?;
+        let path = entry.path();
+        if is_managed_skill_entry(&path) && !remove_deployed_skill(&path)? {
+            kept.push(entry.file_name().to_string_lossy().to_string());
+        }
+    }
+
+    Ok(kept)
+}
+
+/// Deploy one skill into `skills_dir`, recording the outcome in `result`
+pub(crate) fn deploy_skill(
+    skills_dir: &Path,
+    deployment: &SkillDeployment,
+    mode: &str,
+    result: &mut SkillDeployResult,
+) -> Result<(), String> {
+    let name = deployment
+        .source
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .ok_or("Invalid skill path")?;
+    let target = skills_dir.join(&name);
+    let as_copy = mode == DEPLOY_MODE_COPY || deployment.version_id.is_some();
+
+    if !as_copy {
+        if is_symlink(&target) {
+            fs::remove_file(&target).map_err(|e| e.to_string())?;
+        } else if target.exists() && !remove_deployed_skill(&target)? {
+            if read_stamp(&target).is_some() {
+                result.skipped_modified.push(name);
+                return Ok(());
+            }
+            return Err(format!(
+                "Target path {} exists and is not managed by Ensemble",
+                target.display()
+            ));
+        }
+        create_symlink(&deployment.source, &target)?;
+        result.deployed.push(name);
+        return Ok(());
+    }
+
+    if is_symlink(&target) {
+        fs::remove_file(&target).map_err(|e| e.to_string())?;
+    }
+
+    match read_stamp(&target) {
+        Some(stamp) if copy_is_current(deployment, &stamp) => result.unchanged.push(name),
+        Some(stamp) if is_locally_modified(&target, &stamp) => result.skipped_modified.push(name),
+        Some(_) => {
+            write_copy(deployment, &target)?;
+            result.updated.push(name);
+        }
+        None if target.exists() => {
+            return Err(format!(
+                "Target path {} exists and is not managed by Ensemble",
+                target.display()
+            ));
+        }
+        None => {
+            write_copy(deployment, &target)?;
+            result.deployed.push(name);
+        }
+    }
+
+    Ok(())
+}
+
+/// Bring a skills directory in line with `deployments`
+///
+/// Entries that are no longer wanted are removed; copies are refreshed when
+/// their source changed, but locally modified copies are never overwritten
+/// or removed.
+pub(crate) fn deploy_skills(
+    skills_dir: &Path,
+    deployments: &[SkillDeployment],
+    mode: &str,
+) -> Result<SkillDeployResult, String> {
+    fs::create_dir_all(skills_dir).map_err(|e| e.to_string())?;
+    let mut result = SkillDeployResult {
+        mode: mode.to_string(),
+        ..Default::default()
+    };
+
+    let wanted: Vec<String> = deployments
+        .iter()
+        .filter_map(|d| d.source.file_name().map(|n| n.to_string_lossy().to_string()))
+        .collect();
+
+    for entry in fs::read_dir(skills_dir).map_err(|e| e.to_string())? {
+        let entry = entry.map_err(|e| e.to_string())?;
+        let name = entry.file_name().to_string_lossy().to_string();
+        let path = entry.path();
+        if wanted.contains(&name) || !is_managed_skill_entry(&path) {
+            continue;
+        }
+        if !remove_deployed_skill(&path)? {
+            result.skipped_modified.push(name);
+        }
+    }
+
+    for deployment in deployments {
+        deploy_skill(skills_dir, deployment, mode, &mut result)?;
+    }
+
+    Ok(result)
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn setup() -> (TempDir, PathBuf, PathBuf) {
+        let tmp = TempDir::new().unwrap();
+        let source = tmp.path().join("library").join("commit");
+        fs::create_dir_all(&source).unwrap();
+        fs::write(source.join("SKILL.md"), "# Commit\n").unwrap();
+        let skills_dir = tmp.path().join("project").join(".claude").join("skills");
+        (tmp, source, skills_dir)
+    }
+
+    fn deployment(source: &Path) -> SkillDeployment {
+        SkillDeployment {
+            source: source.to_path_buf(),
+            version_id: None,
+        }
+    }
+
+    #[test]
+    fn test_copy_mode_deploys_stamped_copy_and_refreshes_on_change() {
+        let (_tmp, source, skills_dir) = setup();
+        let target = skills_dir.join("commit");
+
+        let result = deploy_skills(&skills_dir, &[deployment(&source)], DEPLOY_MODE_COPY).unwrap();
+        assert_eq!(result.deployed, vec!["commit"]);
+        assert!(!is_symlink(&target));
+        assert!(read_stamp(&target).is_some());
+
+        let result = deploy_skills(&skills_dir, &[deployment(&source)], DEPLOY_MODE_COPY).unwrap();
+        assert_eq!(result.unchanged, vec!["commit"]);
+
+        fs::write(source.join("SKILL.md"), "# Commit v2\n").unwrap();
+        let result = deploy_skills(&skills_dir, &[deployment(&source)], DEPLOY_MODE_COPY).unwrap();
+        assert_eq!(result.updated, vec!["commit"]);
+        assert_eq!(fs::read_to_string(target.join("SKILL.md")).unwrap(), "# Commit v2\n");
+    }
+
+    #[test]
+    fn test_locally_modified_copy_is_not_overwritten_or_removed() {
+        let (_tmp, source, skills_dir) = setup();
+        let target = skills_dir.join("commit");
+        deploy_skills(&skills_dir, &[deployment(&source)], DEPLOY_MODE_COPY).unwrap();
+
+        fs::write(target.join("SKILL.md"), "# Local edit\n").unwrap();
+        fs::write(source.join("SKILL.md"), "# Commit v2\n").unwrap();
+        let result = deploy_skills(&skills_dir, &[deployment(&source)], DEPLOY_MODE_COPY).unwrap();
+        assert_eq!(result.skipped_modified, vec!["commit"]);
+        assert_eq!(fs::read_to_string(target.join("SKILL.md")).unwrap(), "# Local edit\n");
+
+        // Dropping the skill from the scene keeps the edited copy as well
+        let result = deploy_skills(&skills_dir, &[], DEPLOY_MODE_COPY).unwrap();
+        assert_eq!(result.skipped_modified, vec!["commit"]);
+        assert!(target.exists());
+    }
+
+    #[test]
+    fn test_switching_to_symlink_mode_replaces_unmodified_copy() {
+        let (_tmp, source, skills_dir) = setup();
+        deploy_skills(&skills_dir, &[deployment(&source)], DEPLOY_MODE_COPY).unwrap();
+
+        let result = deploy_skills(&skills_dir, &[deployment(&source)], DEPLOY_MODE_SYMLINK).unwrap();
+        assert_eq!(result.deployed, vec!["commit"]);
+        assert!(is_symlink(&skills_dir.join("commit")));
+
+        // Unmanaged directories are never touched
+        fs::create_dir_all(skills_dir.join("handmade")).unwrap();
+        assert!(remove_deployed_skills(&skills_dir).unwrap().is_empty());
+        assert!(skills_dir.join("handmade").exists());
+        assert!(!skills_dir.join("commit").exists());
+    }
+}
@@ -0,0 +1,82 @@
+use crate::commands::data::{read_app_data, write_app_data, DATA_MUTEX};
+use crate::types::{AppData, FileItemMetadata};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Whether `path` is a markdown definition file (visible `.md`), as used for
+/// subagents and slash commands
+pub(crate) fn is_markdown_file(path: &Path) -> bool {
+    let visible = path
+        .file_name()
+        .map(|n| !n.to_string_lossy().starts_with('.'))
+        .unwrap_or(false);
+    visible && path.extension().and_then(|e| e.to_str()) == Some("md") && path.is_file()
+}
+
+/// Creation time of a file as RFC 3339, shown as its install date
+pub(crate) fn installed_at(path: &Path) -> Option<String> {
+    fs::metadata(path)
+        .ok()
+        .and_then(|m| m.created().ok())
+        .map(|t| {
+            let datetime: chrono::DateTime<chrono::Utc> = t.into();
+            datetime.to_rfc3339()
+        })
+}
+
+/// Move a markdown file into `trash_dir`, returning its new path
+///
+/// A timestamp is appended to the name when the trash already holds a
+/// file with the same name.
+pub(crate) fn move_file_to_trash(path: &Path, trash_dir: &Path) -> Result<PathBuf, String> {
+    let file_stem = path
+        .file_stem()
+        .and_then(|n| n.to_str())
+        .ok_or_else(|| format!("Invalid path: {}", path.display()))?;
+
+    fs::create_dir_all(trash_dir).map_err(|e| format!("Failed to create trash directory: {}", e))?;
+
+    // Generate unique destination path (add timestamp if exists)
+    let mut dest_path = trash_dir.join(format!("{}.md", file_stem));
+    if dest_path.exists() {
+        let timestamp = chrono::Utc::now().format("%Y%m%d_%H%M%S");
+        dest_path = trash_dir.join(format!("{}_{}.md", file_stem, timestamp));
+    }
+
+    fs::rename(path, &dest_path).map_err(|e| format!("Failed to move {} to trash: {}", path.display(), e))?;
+    Ok(dest_path)
+}
+
+/// Update the metadata of a file item (category, tags, enabled status, icon)
+/// in the map `select` picks from the app data
+pub(crate) fn update_file_item_metadata(
+    select: impl FnOnce(&mut AppData) -> &mut HashMap<String, FileItemMetadata>,
+    id: String,
+    category: Option<String>,
+    tags: Option<Vec<String>>,
+    enabled: Option<bool>,
+    icon: Option<String>,
+) -> Result<(), String> {
+    let _guard = DATA_MUTEX.lock().map_err(|e| e.to_string())?;
+    let mut app_data = read_app_data()?;
+
+    let metadata = select(&mut app_data).entry(id).or_insert_with(|| FileItemMetadata {
+        enabled: true,
+        ..Default::default()
+    });
+    if let Some(cat) = category {
+        metadata.category = cat;
+    }
+    if let Some(t) = tags {
+        metadata.tags = t;
+    }
+    if let Some(e) = enabled {
+        metadata.enabled = e;
+    }
+    if let Some(i) = icon {
+        metadata.icon = Some(i);
+    }
+
+    write_app_data(app_data)
+}
@@ -1,10 +1,14 @@
 #![allow(unused_imports)]
 #![allow(unused_variables)]
 
+use crate::commands::deploy::{
+    deploy_skill, is_managed_skill_entry, remove_deployed_skill, validate_deploy_mode,
+    SkillDeployment, DEPLOY_MODE_SYMLINK,
+};
 use crate::types::{
     AppData, BackupInfo, ClaudeJson, ClaudeMcpConfig, ClaudeSettings, DetectedMcp, DetectedSkill,
     ExistingConfig, ImportItem, ImportResult, ImportedCounts, McpConfigFile, McpMetadata,
-    SkillMetadata,
+    SkillDeployResult, SkillMetadata,
 };
 use crate::utils::path::{expand_tilde, get_data_file_path};
 use chrono::Utc;
@@ -766,13 +770,22 @@ fn extract_mcp_config(
 }
 
 /// Update Skill scope and sync to corresponding location
+///
+/// `deploy_mode` selects how a global skill is placed in ~/.claude/skills/:
+/// "symlink" (default) or "copy".
 #[tauri::command]
 pub fn update_skill_scope(
     skill_id: String,
     scope: String,
     ensemble_dir: String,
     claude_config_dir: String,
+    deploy_mode: Option<String>,
 ) -> Result<(), String> {
+    let deploy_mode = deploy_mode
+        .map(|m| validate_deploy_mode(&m))
+        .transpose()?
+        .flatten()
+        .unwrap_or_else(|| DEPLOY_MODE_SYMLINK.to_string());
     let ensemble_path = expand_tilde(&ensemble_dir);
     let claude_path = expand_tilde(&claude_config_dir);
 
@@ -795,47 +808,34 @@ pub fn update_skill_scope(
 
     match scope.as_str() {
         "global" => {
-            // Create symlink in ~/.claude/skills/ pointing to ~/.ensemble/skills/<name>
+            // Link or copy ~/.ensemble/skills/<name> into ~/.claude/skills/
             fs::create_dir_all(&claude_skills_dir).map_err(|e| e.to_string())?;
 
-            // If target exists (could be old symlink or directory), handle accordingly
-            if target_skill_path.exists() || target_skill_path.symlink_metadata().is_ok() {
-                if target_skill_path
-                    .symlink_metadata()
-                    .map(|m| m.file_type().is_symlink())
-                    .unwrap_or(false)
-                {
-                    // It's a symlink, remove it
-                    fs::remove_file(&target_skill_path).map_err(|e| e.to_string())?;
-                } else if target_skill_path.is_dir() {
-                    // It's a real directory, don't delete it - report warning
-                    return Err(format!(
-                        "Target path {} exists and is not a symlink",
-                        target_skill_path.display()
-                    ));
-                }
+            let deployment = SkillDeployment {
+                source: source_skill_path,
+                version_id: None,
+            };
+            let mut result = SkillDeployResult::default();
+            deploy_skill(&claude_skills_dir, &deployment, &deploy_mode, &mut result)?;
+            if !result.skipped_modified.is_empty() {
+                return Err(format!(
+                    "{} has local modifications and was not replaced",
+                    target_skill_path.display()
+                ));
             }
-
-            // Create symlink
-            #[cfg(unix)]
-            std::os::unix::fs::symlink(&source_skill_path, &target_skill_path)
-                .map_err(|e| e.to_string())?;
-
-            #[cfg(windows)]
-            std::os::windows::fs::symlink_dir(&source_skill_path, &target_skill_path)
-                .map_err(|e| e.to_string())?;
         }
         "project" => {
-            // If ~/.claude/skills/<name> is a symlink, remove it
+            // Remove ~/.claude/skills/<name> if it is a symlink or an unmodified copy
+            // (other directories might be user manually placed)
             if target_skill_path.symlink_metadata().is_ok()
-                && target_skill_path
-                    .symlink_metadata()
-                    .map(|m| m.file_type().is_symlink())
-                    .unwrap_or(false)
+                && is_managed_skill_entry(&target_skill_path)
+                && !remove_deployed_skill(&target_skill_path)?
             {
-                fs::remove_file(&target_skill_path).map_err(|e| e.to_string())?;
+                return Err(format!(
+                    "{} has local modifications and was not removed",
+                    target_skill_path.display()
+                ));
             }
-            // If it's not a symlink but exists, don't process (might be user manually placed)
         }
         _ => {
             return Err(format!("Invalid scope: {}", scope));
@@ -0,0 +1,629 @@
+use crate::commands::data::{read_app_data, read_settings, save_settings, write_app_data, DATA_MUTEX};
+use crate::types::{LibraryRelocationJournal, LibraryRelocationResult};
+use crate::utils::{collapse_tilde, expand_path, get_app_data_dir};
+use chrono::Utc;
+use std::fs;
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+/// Library subdirectories moved by a relocation (relative to the library root)
+const LIBRARY_SUBDIRS: &[&str] = &["skills", "mcps", "agents", "commands", "claude-md", "trash", "skill-versions"];
+
+/// Relocation steps, in execution order
+const STEP_MOVE_FILES: &str = "move_files";
+const STEP_REWRITE_SYMLINKS: &str = "rewrite_symlinks";
+const STEP_UPDATE_DATA: &str = "update_data";
+const STEP_UPDATE_SETTINGS: &str = "update_settings";
+
+// ============================================================================
+// Library location helpers
+// ============================================================================
+
+/// Get the library root directory
+///
+/// Falls back to the application data directory (~/.ensemble/) when no
+/// custom `library_dir` is configured.
+pub fn get_library_dir() -> PathBuf {
+    read_settings()
+        .ok()
+        .and_then(|s| s.library_dir)
+        .map(|dir| expand_path(&dir))
+        .unwrap_or_else(get_app_data_dir)
+}
+
+/// Get the relocation journal path (~/.ensemble/relocation.json)
+///
+/// The journal always lives in the application data directory, next to
+/// settings.json, so it can be found regardless of where the library is.
+fn get_journal_path() -> PathBuf {
+    get_app_data_dir().join("relocation.json")
+}
+
+fn read_journal() -> Result<Option<LibraryRelocationJournal>, String> {
+    let path = get_journal_path();
+    if !path.exists() {
+        return Ok(None);
+    }
+    let content = fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read relocation journal: {}", e))?;
+    serde_json::from_str(&content)
+        .map(Some)
+        .map_err(|e| format!("Failed to parse relocation journal: {}", e))
+}
+
+fn write_journal(journal: &LibraryRelocationJournal) -> Result<(), String> {
+    let path = get_journal_path();
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    let json = serde_json::to_string_pretty(journal).map_err(|e| e.to_string())?;
+    fs::write(&path, json).map_err(|e| format!("Failed to write relocation journal: {}", e))
+}
+
+/// Rebase `path` from `old_base` onto `new_base`
+///
+/// Returns None when `path` is not inside `old_base`.
+pub fn rebase_path(path: &str, old_base: &Path, new_base: &Path) -> Option<String> {
+    Path::new(path)
+        .strip_prefix(old_base)
+        .ok()
+        .map(|relative| {
+            if relative.as_os_str().is_empty() {
+                new_base.to_path_buf()
+            } else {
+                new_base.join(relative)
+            }
+        })
+        .map(|p| p.to_string_lossy().to_string())
+}
+
+// ============================================================================
+// Commands
+// ============================================================================
+
+/// Get the pending (interrupted) library relocation, if any
+#[tauri::command]
+pub fn get_pending_library_relocation() -> Result<Option<LibraryRelocationJournal>, String> {
+    read_journal()
+}
+
+/// Move the Ensemble library (skills, MCPs, agents, commands, CLAUDE.md storage, trash) to a new root
+///
+/// Flow:
+/// 1. Record a journal in ~/.ensemble/relocation.json
+/// 2. Move every library entry to `{target_dir}/{skills,mcps,agents,commands,claude-md,trash}`
+/// 3. Re-point managed skill, agent and command symlinks in known projects and ~/.claude
+/// 4. Rewrite skill/MCP/agent/command ids and CLAUDE.md managed paths in data.json
+/// 5. Point settings at the new directories and remove the journal
+///
+/// Every step is idempotent. Calling this again with the same `target_dir`
+/// after an interruption resumes from the first incomplete step.
+#[tauri::command]
+pub fn relocate_library(target_dir: String) -> Result<LibraryRelocationResult, String> {
+    let _guard = DATA_MUTEX.lock().map_err(|e| e.to_string())?;
+    let settings = read_settings()?;
+    let target = expand_path(&target_dir);
+
+    let mut journal = match read_journal()? {
+        Some(journal) => {
+            if expand_path(&journal.to_dir) != target {
+                return Err(format!(
+                    "A relocation to {} is already in progress. Resume it before starting another.",
+                    journal.to_dir
+                ));
+            }
+            journal
+        }
+        None => {
+            let from = get_library_dir();
+            let old_skill_dir = expand_path(&settings.skill_source_dir);
+            let old_mcp_dir = expand_path(&settings.mcp_source_dir);
+            validate_target(&from, &old_skill_dir, &old_mcp_dir, &target)?;
+
+            let journal = LibraryRelocationJournal {
+                from_dir: from.to_string_lossy().to_string(),
+                to_dir: target.to_string_lossy().to_string(),
+                old_skill_dir: old_skill_dir.to_string_lossy().to_string(),
+                old_mcp_dir: old_mcp_dir.to_string_lossy().to_string(),
+                started_at: Utc::now().to_rfc3339(),
+                completed_steps: vec![],
+            };
+            write_journal(&journal)?;
+            journal
+        }
+    };
+
+    let from = PathBuf::from(&journal.from_dir);
+    let sources = source_dirs(&journal);
+    let mut result = LibraryRelocationResult {
+        success: true,
+        library_dir: collapse_tilde(&target),
+        moved_items: 0,
+        rewritten_symlinks: 0,
+        updated_ids: 0,
+        errors: vec![],
+    };
+
+    // Step 1: move files
+    if !journal.completed_steps.iter().any(|s| s == STEP_MOVE_FILES) {
+        for (subdir, source) in &sources {
+            result.moved_items += move_dir_contents(source, &target.join(subdir))?;
+        }
+        complete_step(&mut journal, STEP_MOVE_FILES)?;
+    }
+
+    // Step 2: rewrite symlinks
+    let old_skill_dir = PathBuf::from(&journal.old_skill_dir);
+    let new_skill_dir = target.join("skills");
+    if !journal.completed_steps.iter().any(|s| s == STEP_REWRITE_SYMLINKS) {
+        let app_data = read_app_data()?;
+        let mut link_dirs: Vec<PathBuf> = app_data
+            .projects
+            .iter()
+            .map(|p| expand_path(&p.path).join(".claude").join("skills"))
+            .collect();
+        link_dirs.push(expand_path(&settings.claude_config_dir).join("skills"));
+
+        for dir in link_dirs {
+            let (count, mut errors) = rewrite_symlinks_in(&dir, &old_skill_dir, &new_skill_dir);
+            result.rewritten_symlinks += count;
+            result.errors.append(&mut errors);
+        }
+
+        let mut agent_link_dirs: Vec<PathBuf> = app_data
+            .projects
+            .iter()
+            .map(|p| expand_path(&p.path).join(".claude").join("agents"))
+            .collect();
+        agent_link_dirs.push(expand_path(&settings.claude_config_dir).join("agents"));
+        for dir in agent_link_dirs {
+            let (count, mut errors) = rewrite_symlinks_in(&dir, &from.join("agents"), &target.join("agents"));
+            result.rewritten_symlinks += count;
+            result.errors.append(&mut errors);
+        }
+
+        // Commands are linked inside namespace subfolders too
+        let mut command_link_dirs: Vec<PathBuf> = Vec::new();
+        for project in &app_data.projects {
+            let commands_dir = expand_path(&project.path).join(".claude").join("commands");
+            command_link_dirs.extend(
+                WalkDir::new(&commands_dir)
+                    .into_iter()
+                    .filter_map(|e| e.ok())
+                    .filter(|e| e.file_type().is_dir())
+                    .map(|e| e.into_path()),
+            );
+        }
+        for dir in command_link_dirs {
+            let (count, mut errors) = rewrite_symlinks_in(&dir, &from.join("commands"), &target.join("commands"));
+            result.rewritten_symlinks += count;
+            result.errors.append(&mut errors);
+        }
+        complete_step(&mut journal, STEP_REWRITE_SYMLINKS)?;
+    }
+
+    // Step 3: rewrite ids in data.json
+    if !journal.completed_steps.iter().any(|s| s == STEP_UPDATE_DATA) {
+        let old_mcp_dir = PathBuf::from(&journal.old_mcp_dir);
+        let new_mcp_dir = target.join("mcps");
+        let old_agent_dir = from.join("agents");
+        let new_agent_dir = target.join("agents");
+        let old_command_dir = from.join("commands");
+        let new_command_dir = target.join("commands");
+        let old_claude_md_dir = from.join("claude-md");
+        let new_claude_md_dir = target.join("claude-md");
+        let mut app_data = read_app_data()?;
+        let mut updated = 0u32;
+
+        let rebase_key = |key: String, old: &Path, new: &Path, updated: &mut u32| {
+            match rebase_path(&key, old, new) {
+                Some(rebased) => {
+                    *updated += 1;
+                    rebased
+                }
+                None => key,
+            }
+        };
+
+        app_data.skill_metadata = std::mem::take(&mut app_data.skill_metadata)
+            .into_iter()
+            .map(|(k, v)| (rebase_key(k, &old_skill_dir, &new_skill_dir, &mut updated), v))
+            .collect();
+        app_data.mcp_metadata = std::mem::take(&mut app_data.mcp_metadata)
+            .into_iter()
+            .map(|(k, v)| (rebase_key(k, &old_mcp_dir, &new_mcp_dir, &mut updated), v))
+            .collect();
+        app_data.agent_metadata = std::mem::take(&mut app_data.agent_metadata)
+            .into_iter()
+            .map(|(k, v)| (rebase_key(k, &old_agent_dir, &new_agent_dir, &mut updated), v))
+            .collect();
+        app_data.command_metadata = std::mem::take(&mut app_data.command_metadata)
+            .into_iter()
+            .map(|(k, v)| (rebase_key(k, &old_command_dir, &new_command_dir, &mut updated), v))
+            .collect();
+
+        for scene in app_data.scenes.iter_mut() {
+            for id in scene.skill_ids.iter_mut() {
+                *id = rebase_key(std::mem::take(id), &old_skill_dir, &new_skill_dir, &mut updated);
+            }
+            for id in scene.mcp_ids.iter_mut() {
+                *id = rebase_key(std::mem::take(id), &old_mcp_dir, &new_mcp_dir, &mut updated);
+            }
+            for id in scene.agent_ids.iter_mut() {
+                *id = rebase_key(std::mem::take(id), &old_agent_dir, &new_agent_dir, &mut updated);
+            }
+            for id in scene.command_ids.iter_mut() {
+                *id = rebase_key(std::mem::take(id), &old_command_dir, &new_command_dir, &mut updated);
+            }
+        }
+        for scene in app_data.trashed_scenes.iter_mut() {
+            for id in scene.skill_ids.iter_mut() {
+                *id = rebase_key(std::mem::take(id), &old_skill_dir, &new_skill_dir, &mut updated);
+            }
+            for id in scene.mcp_ids.iter_mut() {
+                *id = rebase_key(std::mem::take(id), &old_mcp_dir, &new_mcp_dir, &mut updated);
+            }
+            for id in scene.agent_ids.iter_mut() {
+                *id = rebase_key(std::mem::take(id), &old_agent_dir, &new_agent_dir, &mut updated);
+            }
+            for id in scene.command_ids.iter_mut() {
+                *id = rebase_key(std::mem::take(id), &old_command_dir, &new_command_dir, &mut updated);
+            }
+        }
+        for file in app_data.claude_md_files.iter_mut() {
+            if let Some(path) = file.managed_path.take() {
+                file.managed_path = Some(rebase_key(
+                    path,
+                    &old_claude_md_dir,
+                    &new_claude_md_dir,
+                    &mut updated,
+                ));
+            }
+        }
+
+        write_app_data(app_data)?;
+        result.updated_ids = updated;
+        complete_step(&mut journal, STEP_UPDATE_DATA)?;
+    }
+
+    // Step 4: point settings at the new location
+    if !journal.completed_steps.iter().any(|s| s == STEP_UPDATE_SETTINGS) {
+        let mut settings = read_settings()?;
+        settings.skill_source_dir = collapse_tilde(&new_skill_dir);
+        settings.mcp_source_dir = collapse_tilde(&target.join("mcps"));
+        settings.library_dir = if target == get_app_data_dir() {
+            None
+        } else {
+            Some(collapse_tilde(&target))
+        };
+        save_settings(&settings)?;
+        complete_step(&mut journal, STEP_UPDATE_SETTINGS)?;
+    }
+
+    fs::remove_file(get_journal_path())
+        .map_err(|e| format!("Failed to remove relocation journal: {}", e))?;
+
+    log::info!(
+        "Relocated library: moved {} items to {:?}, rewrote {} symlinks, updated {} ids",
+        result.moved_items, target, result.rewritten_symlinks, result.updated_ids
+    );
+
+    Ok(result)
+}
+
+// ============================================================================
+// Relocation steps
+// ============================================================================
+
+/// Source directory for each library subdirectory
+fn source_dirs(journal: &LibraryRelocationJournal) -> Vec<(&'static str, PathBuf)> {
+    let from = PathBuf::from(&journal.from_dir);
+    LIBRARY_SUBDIRS
+        .iter()
+        .map(|subdir| {
+            let source = match *subdir {
+                "skills" => PathBuf::from(&journal.old_skill_dir),
+                "mcps" => PathBuf::from(&journal.old_mcp_dir),
+                other => from.join(other),
+            };
+            (*subdir, source)
+        })
+        .collect()
+}
+
+/// Reject targets that would nest the library inside itself or clobber existing entries
+fn validate_target(
+    from: &Path,
+    old_skill_dir: &Path,
+    old_mcp_dir: &Path,
+    target: &Path,
+) -> Result<(), String> {
+    if target.as_os_str().is_empty() || target.is_relative() {
+        return Err("Target directory must be an absolute path".to_string());
+    }
+    if target == from
+        && old_skill_dir == from.join("skills")
+        && old_mcp_dir == from.join("mcps")
+    {
+        return Err(format!("Library is already located at {}", target.display()));
+    }
+
+    let journal = LibraryRelocationJournal {
+        from_dir: from.to_string_lossy().to_string(),
+        to_dir: target.to_string_lossy().to_string(),
+        old_skill_dir: old_skill_dir.to_string_lossy().to_string(),
+        old_mcp_dir: old_mcp_dir.to_string_lossy().to_string(),
+        started_at: String::new(),
+        completed_steps: vec![],
+    };
+
+    for (subdir, source) in source_dirs(&journal) {
+        let dest = target.join(subdir);
+        if source == dest || !source.exists() {
+            continue;
+        }
+        if dest.starts_with(&source) {
+            return Err(format!(
+                "Cannot move {} into its own subdirectory {}",
+                source.display(),
+                dest.display()
+            ));
+        }
+        if let Ok(entries) = fs::read_dir(&source) {
+            for entry in entries.filter_map(|e| e.ok()) {
+                let conflict = dest.join(entry.file_name());
+                if conflict.exists() || conflict.symlink_metadata().is_ok() {
+                    return Err(format!(
+                        "Target already contains {}. Choose an empty directory.",
+                        conflict.display()
+                    ));
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn complete_step(journal: &mut LibraryRelocationJournal, step: &str) -> Result<(), String> {
+    journal.completed_steps.push(step.to_string());
+    write_journal(journal)
+}
+
+/// Move every entry of `source` into `dest`, returning the number of entries moved
+///
+/// An entry already present in `dest` is one a previous (interrupted) run
+/// finished copying, because copies are only renamed into place once complete.
+fn move_dir_contents(source: &Path, dest: &Path) -> Result<u32, String> {
+    if source == dest || !source.exists() {
+        return Ok(0);
+    }
+    fs::create_dir_all(dest)
+        .map_err(|e| format!("Failed to create {}: {}", dest.display(), e))?;
+
+    let mut moved = 0u32;
+    let entries = fs::read_dir(source)
+        .map_err(|e| format!("Failed to read {}: {}", source.display(), e))?;
+
+    for entry in entries.filter_map(|e| e.ok()) {
+        let src_path = entry.path();
+        let dst_path = dest.join(entry.file_name());
+
+        if dst_path.exists() || dst_path.symlink_metadata().is_ok() {
+            remove_entry(&src_path)
+                .map_err(|e| format!("Failed to remove {}: {}", src_path.display(), e))?;
+        } else {
+            move_entry(&src_path, &dst_path)
+                .map_err(|e| format!("Failed to move {}: {}", src_path.display(), e))?;
+        }
+        moved += 1;
+    }
+
+    // Leave no empty husk behind (ignore failure: the directory may hold new files)
+    let _ = fs::remove_dir(source);
+
+    Ok(moved)
+}
+
+/// Move a single file, directory or symlink
+///
+/// Relative symlinks are rewritten as absolute so they survive the move.
+/// Falls back to copy + delete when `rename` fails (e.g. across volumes).
+fn move_entry(src: &Path, dst: &Path) -> std::io::Result<()> {
+    let is_symlink = src
+        .symlink_metadata()
+        .map(|m| m.file_type().is_symlink())
+        .unwrap_or(false);
+
+    if is_symlink {
+        create_symlink(&absolute_link_target(src)?, dst)?;
+        return fs::remove_file(src);
+    }
+
+    if fs::rename(src, dst).is_ok() {
+        return Ok(());
+    }
+
+    // Copy to a temporary name, then rename into place so a partial copy is
+    // never mistaken for a finished one when resuming.
+    let partial = dst.with_file_name(format!(
+        "{}.partial",
+        dst.file_name().map(|n| n.to_string_lossy()).unwrap_or_default()
+    ));
+    if partial.exists() {
+        remove_entry(&partial)?;
+    }
+    copy_entry(src, &partial)?;
+    fs::rename(&partial, dst)?;
+    remove_entry(src)
+}
+
+/// Recursively copy a file or directory, preserving symlinks
+fn copy_entry(src: &Path, dst: &Path) -> std::io::Result<()> {
+    let metadata = src.symlink_metadata()?;
+    if metadata.file_type().is_symlink() {
+        return create_symlink(&absolute_link_target(src)?, dst);
+    }
+    if metadata.is_dir() {
+        fs::create_dir_all(dst)?;
+        for entry in fs::read_dir(src)? {
+            let entry = entry?;
+            copy_entry(&entry.path(), &dst.join(entry.file_name()))?;
+        }
+        return Ok(());
+    }
+    fs::copy(src, dst).map(|_| ())
+}
+
+/// Resolve a symlink's target to an absolute path (relative targets are
+/// resolved against the link's parent directory)
+fn absolute_link_target(link: &Path) -> std::io::Result<PathBuf> {
+    let link_target = fs::read_link(link)?;
+    if link_target.is_absolute() {
+        return Ok(link_target);
+    }
+    let joined = link.parent().map(|p| p.join(&link_target)).unwrap_or(link_target);
+    Ok(fs::canonicalize(&joined).unwrap_or(joined))
+}
+
+fn remove_entry(path: &Path) -> std::io::Result<()> {
+    let metadata = path.symlink_metadata()?;
+    if metadata.is_dir() {
+        fs::remove_dir_all(path)
+    } else {
+        fs::remove_file(path)
+    }
+}
+
+fn create_symlink(source: &Path, target: &Path) -> std::io::Result<()> {
+    #[cfg(unix)]
+    {
+        std::os::unix::fs::symlink(source, target)
+    }
+
+    #[cfg(windows)]
+    {
+        if source.is_dir() {
+            std::os::windows::fs::symlink_dir(source, target)
+        } else {
+            std::os::windows::fs::symlink_file(source, target)
+        }
+    }
+}
+
+/// Re-point symlinks in `dir` that target `old_base` to the same entry under `new_base`
+///
+/// Returns (rewritten count, errors).
+fn rewrite_symlinks_in(dir: &Path, old_base: &Path, new_base: &Path) -> (u32, Vec<String>) {
+    let mut rewritten = 0u32;
+    let mut errors = Vec::new();
+
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(_) => return (0, errors),
+    };
+
+    for entry in entries.filter_map(|e| e.ok()) {
+        let link_path = entry.path();
+        let link_target = match fs::read_link(&link_path) {
+            Ok(target) => target,
+            Err(_) => continue, // Not a symlink
+        };
+
+        let new_target = match rebase_path(&link_target.to_string_lossy(), old_base, new_base) {
+            Some(t) => PathBuf::from(t),
+            None => continue,
+        };
+
+        let result = fs::remove_file(&link_path)
+            .and_then(|_| create_symlink(&new_target, &link_path));
+        match result {
+            Ok(_) => rewritten += 1,
+            Err(e) => errors.push(format!("{}: {}", link_path.display(), e)),
+        }
+    }
+
+    (rewritten, errors)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_rebase_path_inside_base() {
+        let result = rebase_path(
+            "/old/library/skills/commit",
+            Path::new("/old/library/skills"),
+            Path::new("/new/skills"),
+        );
+        assert_eq!(result, Some("/new/skills/commit".to_string()));
+    }
+
+    #[test]
+    fn test_rebase_path_outside_base() {
+        let result = rebase_path(
+            "/elsewhere/skills/commit",
+            Path::new("/old/library/skills"),
+            Path::new("/new/skills"),
+        );
+        assert_eq!(result, None);
+    }
+
+    #[test]
+    fn test_rebase_path_does_not_match_sibling_prefix() {
+        // "/old/skills-extra" shares a string prefix but is not inside "/old/skills"
+        let result = rebase_path(
+            "/old/skills-extra/commit",
+            Path::new("/old/skills"),
+            Path::new("/new/skills"),
+        );
+        assert_eq!(result, None);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_move_dir_contents_moves_entries_and_symlinks() {
+        let tmp = TempDir::new().unwrap();
+        let source = tmp.path().join("old");
+        let dest = tmp.path().join("new");
+        fs::create_dir_all(source.join("commit")).unwrap();
+        fs::write(source.join("commit").join("SKILL.md"), "# Commit").unwrap();
+        let external = tmp.path().join("external");
+        fs::create_dir_all(&external).unwrap();
+        std::os::unix::fs::symlink("../external", source.join("linked")).unwrap();
+
+        let moved = move_dir_contents(&source, &dest).unwrap();
+
+        assert_eq!(moved, 2);
+        assert!(dest.join("commit").join("SKILL.md").exists());
+        // Relative symlink is rewritten as absolute and still resolves
+        assert!(fs::read_link(dest.join("linked")).unwrap().is_absolute());
+        assert!(dest.join("linked").is_dir());
+        assert!(!source.exists());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_rewrite_symlinks_in_only_touches_old_base() {
+        let tmp = TempDir::new().unwrap();
+        let old_base = tmp.path().join("old");
+        let new_base = tmp.path().join("new");
+        let other = tmp.path().join("other");
+        for dir in [&old_base, &new_base, &other] {
+            fs::create_dir_all(dir.join("skill")).unwrap();
+        }
+        let links = tmp.path().join("links");
+        fs::create_dir_all(&links).unwrap();
+        std::os::unix::fs::symlink(old_base.join("skill"), links.join("managed")).unwrap();
+        std::os::unix::fs::symlink(other.join("skill"), links.join("unmanaged")).unwrap();
+
+        let (count, errors) = rewrite_symlinks_in(&links, &old_base, &new_base);
+
+        assert_eq!(count, 1);
+        assert!(errors.is_empty());
+        assert_eq!(fs::read_link(links.join("managed")).unwrap(), new_base.join("skill"));
+        assert_eq!(fs::read_link(links.join("unmanaged")).unwrap(), other.join("skill"));
+    }
+}
@@ -1,12 +1,29 @@
+pub mod agents;
 pub mod classify;
 pub mod claude_md;
+pub mod claude_md_analysis;
+pub mod claude_md_history;
+pub mod claude_md_imports;
+pub mod claude_md_scan;
+pub mod claude_md_sync;
 pub mod config;
 pub mod data;
+pub mod deploy;
 pub mod dialog;
+pub mod file_items;
 pub mod import;
+pub mod library;
 pub mod mcps;
 pub mod plugins;
+pub mod scene_permissions;
+pub mod settings_fragments;
+pub mod skill_duplicates;
+pub mod skill_sources;
+pub mod skill_versions;
 pub mod skills;
+pub mod slash_commands;
 pub mod symlink;
 pub mod trash;
 pub mod usage;
+pub mod usage_cost;
+pub mod usage_report;
@@ -0,0 +1,173 @@
+use crate::commands::data::{read_app_data, read_settings, write_app_data, DATA_MUTEX};
+use crate::commands::mcps::scan_mcps;
+use crate::commands::skills::scan_skills;
+use crate::types::{McpServer, PermissionRuleSource, ScenePermissions, Skill};
+use std::collections::BTreeMap;
+
+/// Server and tool names as they appear in `mcp__server__tool`; Claude Code
+/// replaces characters other than letters, digits, `_` and `-` with `_`
+fn normalize_mcp_name(name: &str) -> String {
+    name.chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '_' || c == '-' { c } else { '_' })
+        .collect()
+}
+
+/// Allow rules for the tools declared by skills and provided by MCP servers
+///
+/// Skills contribute their `allowed-tools` as written; MCP servers one
+/// `mcp__server__tool` rule per known tool. Returns the rules with their
+/// sources, and the servers without a tool list.
+fn build_rules(skills: &[Skill], mcps: &[McpServer]) -> (Vec<PermissionRuleSource>, Vec<String>) {
+    let mut rules: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    let mut add = |rule: String, source: String| {
+        let sources = rules.entry(rule).or_default();
+        if !sources.contains(&source) {
+            sources.push(source);
+        }
+    };
+
+    for skill in skills {
+        for tool in skill.allowed_tools.iter().flatten() {
+            let tool = tool.trim();
+            if !tool.is_empty() {
+                add(tool.to_string(), format!("skill:{}", skill.name));
+            }
+        }
+    }
+
+    let mut mcps_without_tools = Vec::new();
+    for mcp in mcps {
+        if mcp.provided_tools.is_empty() {
+            mcps_without_tools.push(mcp.name.clone());
+            continue;
+        }
+        let server = normalize_mcp_name(&mcp.name);
+        for tool in &mcp.provided_tools {
+            add(
+                format!("mcp__{}__{}", server, normalize_mcp_name(&tool.name)),
+                format!("mcp:{}", mcp.name),
+            );
+        }
+    }
+
+    let rules = rules
+        .into_iter()
+        .map(|(rule, sources)| PermissionRuleSource { rule, sources })
+        .collect();
+    (rules, mcps_without_tools)
+}
+
+// ============================================================================
+// Commands
+// ============================================================================
+
+/// Generate the least-privilege `permissions.allow` list for a Scene
+///
+/// Returns the rules derived from the Scene's skills and MCP tools for
+/// review, alongside the rules currently saved on the Scene.
+#[tauri::command]
+pub fn build_scene_permissions(scene_id: String) -> Result<ScenePermissions, String> {
+    let settings = read_settings()?;
+    let app_data = read_app_data()?;
+    let scene = app_data
+        .scenes
+        .iter()
+        .find(|s| s.id == scene_id)
+        .ok_or_else(|| format!("Scene not found: {}", scene_id))?;
+
+    let skills: Vec<Skill> = scan_skills(settings.skill_source_dir)?
+        .into_iter()
+        .filter(|s| scene.skill_ids.contains(&s.id))
+        .collect();
+    let mcps: Vec<McpServer> = scan_mcps(settings.mcp_source_dir)?
+        .into_iter()
+        .filter(|m| scene.mcp_ids.contains(&m.id))
+        .collect();
+
+    let (rules, mcps_without_tools) = build_rules(&skills, &mcps);
+    Ok(ScenePermissions {
+        scene_id,
+        rules,
+        mcps_without_tools,
+        applied: scene.permission_rules.clone(),
+    })
+}
+
+/// Save the reviewed permission rules of a Scene
+///
+/// The rules are written to each project's .claude/settings.local.json on
+/// its next sync; None stops managing the Scene's permissions.
+#[tauri::command]
+pub fn set_scene_permission_rules(scene_id: String, rules: Option<Vec<String>>) -> Result<(), String> {
+    let _guard = DATA_MUTEX.lock().map_err(|e| e.to_string())?;
+    let mut data = read_app_data()?;
+    let scene = data
+        .scenes
+        .iter_mut()
+        .find(|s| s.id == scene_id)
+        .ok_or_else(|| format!("Scene not found: {}", scene_id))?;
+
+    scene.permission_rules = rules.map(|rules| {
+        let mut unique: Vec<String> = Vec::new();
+        for rule in rules.into_iter().map(|r| r.trim().to_string()).filter(|r| !r.is_empty()) {
+            if !unique.contains(&rule) {
+                unique.push(rule);
+            }
+        }
+        unique
+    });
+    write_app_data(data)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_build_rules_from_skills_and_mcps() {
+        let skills: Vec<Skill> = vec![
+            serde_json::from_value(json!({
+                "id": "/lib/skills/commit", "name": "commit", "description": "", "category": "",
+                "tags": [], "enabled": true, "sourcePath": "", "scope": "user", "invocation": null,
+                "allowedTools": ["Bash(git status:*)", "Read"], "instructions": "",
+                "createdAt": "", "lastUsed": null, "usageCount": 0
+            }))
+            .unwrap(),
+            serde_json::from_value(json!({
+                "id": "/lib/skills/review", "name": "review", "description": "", "category": "",
+                "tags": [], "enabled": true, "sourcePath": "", "scope": "user", "invocation": null,
+                "allowedTools": ["Read"], "instructions": "",
+                "createdAt": "", "lastUsed": null, "usageCount": 0
+            }))
+            .unwrap(),
+        ];
+        let mcps: Vec<McpServer> = vec![
+            serde_json::from_value(json!({
+                "id": "/lib/mcps/gh.json", "name": "github", "description": "", "category": "",
+                "tags": [], "enabled": true, "sourcePath": "", "scope": "project", "command": "gh-mcp",
+                "args": [], "env": null, "createdAt": "", "lastUsed": null, "usageCount": 0,
+                "installedAt": null,
+                "providedTools": [{ "name": "create_issue", "description": "" }, { "name": "get.pr", "description": "" }]
+            }))
+            .unwrap(),
+            serde_json::from_value(json!({
+                "id": "/lib/mcps/db.json", "name": "db", "description": "", "category": "",
+                "tags": [], "enabled": true, "sourcePath": "", "scope": "project", "command": "db-mcp",
+                "args": [], "env": null, "createdAt": "", "lastUsed": null, "usageCount": 0,
+                "installedAt": null, "providedTools": []
+            }))
+            .unwrap(),
+        ];
+
+        let (rules, without_tools) = build_rules(&skills, &mcps);
+        let names: Vec<&str> = rules.iter().map(|r| r.rule.as_str()).collect();
+        assert_eq!(
+            names,
+            vec!["Bash(git status:*)", "Read", "mcp__github__create_issue", "mcp__github__get_pr"]
+        );
+        assert_eq!(rules[1].sources, vec!["skill:commit", "skill:review"]);
+        assert_eq!(rules[2].sources, vec!["mcp:github"]);
+        assert_eq!(without_tools, vec!["db"]);
+    }
+}
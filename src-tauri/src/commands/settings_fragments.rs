@@ -0,0 +1,608 @@
+use crate::commands::data::{read_app_data, write_app_data, DATA_MUTEX};
+use crate::types::{PermissionRules, SettingsFragment, SettingsPatch};
+use crate::utils::expand_path;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+use uuid::Uuid;
+
+/// Record of the settings entries Ensemble merged into each settings file
+/// of a project's .claude directory (file name -> entries)
+pub const SETTINGS_MANIFEST_FILE: &str = ".ensemble-settings.json";
+
+/// Fragments merged into the shared, checked-in settings.json
+pub const SETTINGS_TARGET_SHARED: &str = "shared";
+/// Fragments merged into the personal settings.local.json
+pub const SETTINGS_TARGET_LOCAL: &str = "local";
+
+const SETTINGS_FILES: &[&str] = &["settings.json", "settings.local.json"];
+
+#[derive(Serialize, Deserialize, Default)]
+struct SettingsManifest {
+    /// Entries merged into each settings file
+    #[serde(flatten)]
+    files: BTreeMap<String, SettingsPatch>,
+    /// Settings files that did not exist before Ensemble wrote them, removed
+    /// again once nothing is left in them
+    #[serde(default, rename = ".created", skip_serializing_if = "Vec::is_empty")]
+    created: Vec<String>,
+}
+
+fn settings_file_for(target: &str) -> Result<&'static str, String> {
+    match target {
+        SETTINGS_TARGET_SHARED => Ok("settings.json"),
+        SETTINGS_TARGET_LOCAL => Ok("settings.local.json"),
+        _ => Err(format!("Invalid settings target: {}", target)),
+    }
+}
+
+fn is_empty_patch(patch: &SettingsPatch) -> bool {
+    patch.hooks.values().all(Vec::is_empty)
+        && patch.permissions.allow.is_empty()
+        && patch.permissions.deny.is_empty()
+        && patch.permissions.ask.is_empty()
+        && patch.env.is_empty()
+        && patch.model.is_none()
+}
+
+/// Hook groups must be `{ "matcher"?, "hooks": [...] }` objects
+fn validate_patch(patch: &SettingsPatch) -> Result<(), String> {
+    for (event, groups) in &patch.hooks {
+        for group in groups {
+            if !group.get("hooks").map(Value::is_array).unwrap_or(false) {
+                return Err(format!("Invalid {} hook: expected an object with a hooks list", event));
+            }
+        }
+    }
+    Ok(())
+}
+
+fn push_unique<T: PartialEq + Clone>(list: &mut Vec<T>, items: &[T]) {
+    for item in items {
+        if !list.contains(item) {
+            list.push(item.clone());
+        }
+    }
+}
+
+/// Add `other` to `patch`; rules and hooks are unioned, later env values
+/// and model win
+pub(crate) fn extend_patch(patch: &mut SettingsPatch, other: &SettingsPatch) {
+    for (event, groups) in &other.hooks {
+        push_unique(patch.hooks.entry(event.clone()).or_default(), groups);
+    }
+    push_unique(&mut patch.permissions.allow, &other.permissions.allow);
+    push_unique(&mut patch.permissions.deny, &other.permissions.deny);
+    push_unique(&mut patch.permissions.ask, &other.permissions.ask);
+    patch.env.extend(other.env.clone());
+    if other.model.is_some() {
+        patch.model = other.model.clone();
+    }
+}
+
+/// Combine fragments into one patch per settings file
+fn merge_fragments(fragments: &[SettingsFragment]) -> Result<BTreeMap<String, SettingsPatch>, String> {
+    let mut merged: BTreeMap<String, SettingsPatch> = BTreeMap::new();
+    for fragment in fragments {
+        let file = settings_file_for(&fragment.target)?;
+        extend_patch(merged.entry(file.to_string()).or_default(), &fragment.settings);
+    }
+    Ok(merged)
+}
+
+fn object_mut<'a>(settings: &'a mut Map<String, Value>, key: &str) -> Option<&'a mut Map<String, Value>> {
+    settings
+        .entry(key)
+        .or_insert_with(|| Value::Object(Map::new()))
+        .as_object_mut()
+}
+
+/// Drop `key` when it holds an empty object or list
+fn remove_if_empty(settings: &mut Map<String, Value>, key: &str) {
+    let empty = match settings.get(key) {
+        Some(Value::Object(map)) => map.is_empty(),
+        Some(Value::Array(list)) => list.is_empty(),
+        _ => false,
+    };
+    if empty {
+        settings.remove(key);
+    }
+}
+
+/// Remove the entries Ensemble wrote earlier, unless the user has since changed them
+fn remove_entries(settings: &mut Map<String, Value>, owned: &SettingsPatch) {
+    if let Some(Value::Object(hooks)) = settings.get_mut("hooks") {
+        for (event, groups) in &owned.hooks {
+            if let Some(Value::Array(list)) = hooks.get_mut(event) {
+                list.retain(|g| !groups.contains(g));
+            }
+            remove_if_empty(hooks, event);
+        }
+    }
+    remove_if_empty(settings, "hooks");
+
+    if let Some(Value::Object(permissions)) = settings.get_mut("permissions") {
+        for (key, rules) in [
+            ("allow", &owned.permissions.allow),
+            ("deny", &owned.permissions.deny),
+            ("ask", &owned.permissions.ask),
+        ] {
+            if let Some(Value::Array(list)) = permissions.get_mut(key) {
+                list.retain(|r| !r.as_str().map(|r| rules.iter().any(|o| o == r)).unwrap_or(false));
+            }
+            remove_if_empty(permissions, key);
+        }
+    }
+    remove_if_empty(settings, "permissions");
+
+    if let Some(Value::Object(env)) = settings.get_mut("env") {
+        for (key, value) in &owned.env {
+            if env.get(key).and_then(Value::as_str) == Some(value.as_str()) {
+                env.remove(key);
+            }
+        }
+    }
+    remove_if_empty(settings, "env");
+
+    if owned.model.is_some() && settings.get("model").and_then(Value::as_str) == owned.model.as_deref() {
+        settings.remove("model");
+    }
+}
+
+/// Add the patch entries the settings don't have yet
+///
+/// Returns the entries added (now owned by Ensemble) and the keys left
+/// alone because the user set them to another value.
+fn add_entries(settings: &mut Map<String, Value>, patch: &SettingsPatch) -> (SettingsPatch, Vec<String>) {
+    let mut owned = SettingsPatch::default();
+    let mut conflicts = Vec::new();
+
+    for (event, groups) in &patch.hooks {
+        let Some(hooks) = object_mut(settings, "hooks") else {
+            conflicts.push("hooks".to_string());
+            break;
+        };
+        let Some(list) = hooks.entry(event.clone()).or_insert_with(|| Value::Array(Vec::new())).as_array_mut() else {
+            conflicts.push(format!("hooks.{}", event));
+            continue;
+        };
+        for group in groups {
+            if !list.contains(group) {
+                list.push(group.clone());
+                owned.hooks.entry(event.clone()).or_default().push(group.clone());
+            }
+        }
+    }
+
+    for (key, rules, owned_rules) in [
+        ("allow", &patch.permissions.allow, &mut owned.permissions.allow),
+        ("deny", &patch.permissions.deny, &mut owned.permissions.deny),
+        ("ask", &patch.permissions.ask, &mut owned.permissions.ask),
+    ] {
+        if rules.is_empty() {
+            continue;
+        }
+        let list = object_mut(settings, "permissions")
+            .and_then(|p| p.entry(key).or_insert_with(|| Value::Array(Vec::new())).as_array_mut());
+        let Some(list) = list else {
+            conflicts.push(format!("permissions.{}", key));
+            continue;
+        };
+        for rule in rules {
+            if !list.iter().any(|r| r.as_str() == Some(rule.as_str())) {
+                list.push(Value::String(rule.clone()));
+                owned_rules.push(rule.clone());
+            }
+        }
+    }
+
+    for (key, value) in &patch.env {
+        let Some(env) = object_mut(settings, "env") else {
+            conflicts.push("env".to_string());
+            break;
+        };
+        match env.get(key) {
+            None => {
+                env.insert(key.clone(), Value::String(value.clone()));
+                owned.env.insert(key.clone(), value.clone());
+            }
+            Some(current) if current.as_str() == Some(value.as_str()) => {}
+            Some(_) => conflicts.push(format!("env.{}", key)),
+        }
+    }
+
+    if let Some(model) = &patch.model {
+        match settings.get("model") {
+            None => {
+                settings.insert("model".to_string(), Value::String(model.clone()));
+                owned.model = Some(model.clone());
+            }
+            Some(current) if current.as_str() == Some(model.as_str()) => {}
+            Some(_) => conflicts.push("model".to_string()),
+        }
+    }
+
+    remove_if_empty(settings, "hooks");
+    remove_if_empty(settings, "permissions");
+    remove_if_empty(settings, "env");
+    (owned, conflicts)
+}
+
+fn read_settings_manifest(claude_dir: &Path) -> SettingsManifest {
+    fs::read_to_string(claude_dir.join(SETTINGS_MANIFEST_FILE))
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn write_settings_manifest(claude_dir: &Path, manifest: &SettingsManifest) -> Result<(), String> {
+    let path = claude_dir.join(SETTINGS_MANIFEST_FILE);
+    if manifest.files.is_empty() && manifest.created.is_empty() {
+        if path.exists() {
+            fs::remove_file(&path).map_err(|e| e.to_string())?;
+        }
+        return Ok(());
+    }
+    let json = serde_json::to_string_pretty(manifest).map_err(|e| e.to_string())?;
+    fs::write(&path, json).map_err(|e| e.to_string())
+}
+
+/// Merge settings patches into a project's .claude/settings.json and
+/// settings.local.json
+///
+/// Entries written by the previous sync are replaced; keys Ensemble doesn't
+/// own are preserved, and values the user set differently are kept and
+/// returned as conflicts ("settings.json: env.API_URL"). Files Ensemble
+/// created are removed once they are empty.
+pub(crate) fn apply_settings_patches(
+    claude_dir: &Path,
+    patches: &BTreeMap<String, SettingsPatch>,
+) -> Result<Vec<String>, String> {
+    let previous_manifest = read_settings_manifest(claude_dir);
+    let mut manifest = SettingsManifest::default();
+    let mut conflicts = Vec::new();
+
+    for file in SETTINGS_FILES {
+        let previous = previous_manifest.files.get(*file).cloned().unwrap_or_default();
+        let patch = patches.get(*file).cloned().unwrap_or_default();
+        if is_empty_patch(&previous) && is_empty_patch(&patch) {
+            continue;
+        }
+
+        let path = claude_dir.join(file);
+        let existed = path.exists();
+        let mut settings: Map<String, Value> = if existed {
+            let content = fs::read_to_string(&path).map_err(|e| e.to_string())?;
+            serde_json::from_str(&content).map_err(|e| format!("Failed to parse {}: {}", path.display(), e))?
+        } else {
+            Map::new()
+        };
+
+        remove_entries(&mut settings, &previous);
+        let (owned, mut file_conflicts) = add_entries(&mut settings, &patch);
+        conflicts.extend(file_conflicts.drain(..).map(|key| format!("{}: {}", file, key)));
+
+        let created = !existed || previous_manifest.created.iter().any(|f| f == file);
+        if settings.is_empty() && created {
+            if existed {
+                fs::remove_file(&path).map_err(|e| e.to_string())?;
+            }
+        } else if existed || !settings.is_empty() {
+            fs::create_dir_all(claude_dir).map_err(|e| e.to_string())?;
+            let json = serde_json::to_string_pretty(&settings).map_err(|e| e.to_string())?;
+            fs::write(&path, json).map_err(|e| e.to_string())?;
+        }
+        if !is_empty_patch(&owned) {
+            if created {
+                manifest.created.push(file.to_string());
+            }
+            manifest.files.insert(file.to_string(), owned);
+        }
+    }
+
+    if claude_dir.exists() {
+        write_settings_manifest(claude_dir, &manifest)?;
+    }
+    Ok(conflicts)
+}
+
+/// Patches of the given library fragments, per settings file
+pub(crate) fn fragment_patches(fragment_ids: &[String]) -> Result<BTreeMap<String, SettingsPatch>, String> {
+    let fragments = if fragment_ids.is_empty() {
+        Vec::new()
+    } else {
+        let app_data = read_app_data()?;
+        fragment_ids
+            .iter()
+            .filter_map(|id| app_data.settings_fragments.iter().find(|f| f.id == *id).cloned())
+            .collect()
+    };
+    merge_fragments(&fragments)
+}
+
+/// Add `permissions.allow` rules to the settings.local.json patch
+pub(crate) fn add_allow_rules(patches: &mut BTreeMap<String, SettingsPatch>, rules: Vec<String>) {
+    if rules.is_empty() {
+        return;
+    }
+    let allow = SettingsPatch {
+        permissions: PermissionRules {
+            allow: rules,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    extend_patch(patches.entry("settings.local.json".to_string()).or_default(), &allow);
+}
+
+/// Remove every settings entry Ensemble merged into a project
+pub(crate) fn remove_managed_settings(claude_dir: &Path) -> Result<(), String> {
+    apply_settings_patches(claude_dir, &BTreeMap::new()).map(|_| ())
+}
+
+/// Hooks, permission rules, env and model of a settings file
+fn extract_patch(settings: &Value) -> SettingsPatch {
+    let strings = |value: Option<&Value>| -> Vec<String> {
+        value
+            .and_then(Value::as_array)
+            .map(|list| list.iter().filter_map(|v| v.as_str().map(str::to_string)).collect())
+            .unwrap_or_default()
+    };
+    let permissions = settings.get("permissions");
+    SettingsPatch {
+        hooks: settings
+            .get("hooks")
+            .and_then(Value::as_object)
+            .map(|hooks| {
+                hooks
+                    .iter()
+                    .filter_map(|(event, groups)| Some((event.clone(), groups.as_array()?.clone())))
+                    .collect()
+            })
+            .unwrap_or_default(),
+        permissions: PermissionRules {
+            allow: strings(permissions.and_then(|p| p.get("allow"))),
+            deny: strings(permissions.and_then(|p| p.get("deny"))),
+            ask: strings(permissions.and_then(|p| p.get("ask"))),
+        },
+        env: settings
+            .get("env")
+            .and_then(Value::as_object)
+            .map(|env| {
+                env.iter()
+                    .filter_map(|(k, v)| Some((k.clone(), v.as_str()?.to_string())))
+                    .collect()
+            })
+            .unwrap_or_default(),
+        model: settings.get("model").and_then(Value::as_str).map(str::to_string),
+    }
+}
+
+// ============================================================================
+// Commands
+// ============================================================================
+
+/// Get all settings fragments in the library
+#[tauri::command]
+pub fn get_settings_fragments() -> Result<Vec<SettingsFragment>, String> {
+    let data = read_app_data()?;
+    Ok(data.settings_fragments)
+}
+
+/// Add a settings fragment
+///
+/// # Arguments
+/// * `target` - "shared" (settings.json) or "local" (settings.local.json)
+/// * `settings` - Hooks, permission rules, env and model to merge
+#[tauri::command]
+pub fn add_settings_fragment(
+    name: String,
+    description: String,
+    target: String,
+    settings: SettingsPatch,
+) -> Result<SettingsFragment, String> {
+    settings_file_for(&target)?;
+    validate_patch(&settings)?;
+    let _guard = DATA_MUTEX.lock().map_err(|e| e.to_string())?;
+    let mut data = read_app_data()?;
+
+    let now = chrono::Utc::now().to_rfc3339();
+    let fragment = SettingsFragment {
+        id: Uuid::new_v4().to_string(),
+        name,
+        description,
+        target,
+        settings,
+        category: String::new(),
+        tags: Vec::new(),
+        created_at: now.clone(),
+        updated_at: now,
+    };
+
+    data.settings_fragments.push(fragment.clone());
+    write_app_data(data)?;
+
+    Ok(fragment)
+}
+
+/// Update a settings fragment
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub fn update_settings_fragment(
+    id: String,
+    name: Option<String>,
+    description: Option<String>,
+    target: Option<String>,
+    settings: Option<SettingsPatch>,
+    category: Option<String>,
+    tags: Option<Vec<String>>,
+) -> Result<(), String> {
+    if let Some(t) = &target {
+        settings_file_for(t)?;
+    }
+    if let Some(s) = &settings {
+        validate_patch(s)?;
+    }
+    let _guard = DATA_MUTEX.lock().map_err(|e| e.to_string())?;
+    let mut data = read_app_data()?;
+
+    let fragment = data
+        .settings_fragments
+        .iter_mut()
+        .find(|f| f.id == id)
+        .ok_or_else(|| format!("Settings fragment not found: {}", id))?;
+    if let Some(n) = name {
+        fragment.name = n;
+    }
+    if let Some(d) = description {
+        fragment.description = d;
+    }
+    if let Some(t) = target {
+        fragment.target = t;
+    }
+    if let Some(s) = settings {
+        fragment.settings = s;
+    }
+    if let Some(c) = category {
+        fragment.category = c;
+    }
+    if let Some(t) = tags {
+        fragment.tags = t;
+    }
+    fragment.updated_at = chrono::Utc::now().to_rfc3339();
+
+    write_app_data(data)
+}
+
+/// Delete a settings fragment and remove it from Scenes
+///
+/// Projects keep the merged entries until their next sync.
+#[tauri::command]
+pub fn delete_settings_fragment(id: String) -> Result<(), String> {
+    let _guard = DATA_MUTEX.lock().map_err(|e| e.to_string())?;
+    let mut data = read_app_data()?;
+    data.settings_fragments.retain(|f| f.id != id);
+    for scene in data.scenes.iter_mut() {
+        scene.settings_fragment_ids.retain(|f| *f != id);
+    }
+    write_app_data(data)
+}
+
+/// Create a fragment from the hooks, permission rules, env and model of an
+/// existing settings file (e.g. ~/.claude/settings.json)
+#[tauri::command]
+pub fn import_settings_fragment(
+    settings_path: String,
+    name: String,
+    target: String,
+) -> Result<SettingsFragment, String> {
+    let path = expand_path(&settings_path);
+    let content = fs::read_to_string(&path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    let settings: Value =
+        serde_json::from_str(&content).map_err(|e| format!("Failed to parse {}: {}", path.display(), e))?;
+
+    let patch = extract_patch(&settings);
+    if is_empty_patch(&patch) {
+        return Err("No hooks, permissions, env or model found".to_string());
+    }
+    add_settings_fragment(name, format!("Imported from {}", settings_path), target, patch)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use tempfile::TempDir;
+
+    fn fragment(target: &str, settings: Value) -> SettingsFragment {
+        SettingsFragment {
+            id: Uuid::new_v4().to_string(),
+            name: "fragment".to_string(),
+            description: String::new(),
+            target: target.to_string(),
+            settings: serde_json::from_value(settings).unwrap(),
+            category: String::new(),
+            tags: Vec::new(),
+            created_at: String::new(),
+            updated_at: String::new(),
+        }
+    }
+
+    fn read_json(path: &Path) -> Value {
+        serde_json::from_str(&fs::read_to_string(path).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_apply_settings_preserves_user_keys() {
+        let tmp = TempDir::new().unwrap();
+        let claude_dir = tmp.path().join(".claude");
+        fs::create_dir_all(&claude_dir).unwrap();
+        fs::write(
+            claude_dir.join("settings.json"),
+            json!({
+                "includeCoAuthoredBy": false,
+                "permissions": { "allow": ["Bash(npm test)"] },
+                "env": { "API_URL": "http://localhost" }
+            })
+            .to_string(),
+        )
+        .unwrap();
+
+        let lint_hook = json!({ "matcher": "Edit", "hooks": [{ "type": "command", "command": "npm run lint" }] });
+        let fragments = vec![
+            fragment(
+                "shared",
+                json!({
+                    "hooks": { "PostToolUse": [lint_hook] },
+                    "permissions": { "allow": ["Bash(npm test)", "Bash(npm run lint)"] },
+                    "env": { "API_URL": "https://api.example.com", "CI": "1" }
+                }),
+            ),
+            fragment("local", json!({ "model": "opus" })),
+        ];
+        let conflicts = apply_settings_patches(&claude_dir, &merge_fragments(&fragments).unwrap()).unwrap();
+        assert_eq!(conflicts, vec!["settings.json: env.API_URL"]);
+
+        let shared = read_json(&claude_dir.join("settings.json"));
+        assert_eq!(shared["includeCoAuthoredBy"], json!(false));
+        assert_eq!(shared["permissions"]["allow"], json!(["Bash(npm test)", "Bash(npm run lint)"]));
+        assert_eq!(shared["env"], json!({ "API_URL": "http://localhost", "CI": "1" }));
+        assert_eq!(shared["hooks"]["PostToolUse"][0]["matcher"], json!("Edit"));
+        assert_eq!(read_json(&claude_dir.join("settings.local.json")), json!({ "model": "opus" }));
+        assert_eq!(read_json(&claude_dir.join(SETTINGS_MANIFEST_FILE))[".created"], json!(["settings.local.json"]));
+
+        // Dropping the fragments removes only what Ensemble added
+        remove_managed_settings(&claude_dir).unwrap();
+        assert_eq!(
+            read_json(&claude_dir.join("settings.json")),
+            json!({
+                "includeCoAuthoredBy": false,
+                "permissions": { "allow": ["Bash(npm test)"] },
+                "env": { "API_URL": "http://localhost" }
+            })
+        );
+        assert!(!claude_dir.join("settings.local.json").exists());
+        assert!(!claude_dir.join(SETTINGS_MANIFEST_FILE).exists());
+    }
+
+    #[test]
+    fn test_extract_patch_and_validation() {
+        let settings = json!({
+            "permissions": { "allow": ["Read"], "deny": ["Bash(rm:*)"] },
+            "hooks": { "Stop": [{ "hooks": [{ "type": "command", "command": "say done" }] }] },
+            "env": { "DEBUG": "1", "PORT": 3000 },
+            "statusLine": { "type": "command" }
+        });
+        let patch = extract_patch(&settings);
+        assert_eq!(patch.permissions.deny, vec!["Bash(rm:*)"]);
+        assert_eq!(patch.hooks["Stop"].len(), 1);
+        assert_eq!(patch.env.len(), 1);
+        assert!(validate_patch(&patch).is_ok());
+
+        let bad: SettingsPatch = serde_json::from_value(json!({ "hooks": { "Stop": ["say done"] } })).unwrap();
+        assert!(validate_patch(&bad).is_err());
+        assert!(settings_file_for("global").is_err());
+    }
+}
@@ -0,0 +1,415 @@
+use crate::commands::data::{read_app_data, write_app_data, DATA_MUTEX};
+use crate::commands::skill_versions::{equivalent_version, hash_working_tree};
+use crate::commands::skills::move_skill_to_trash;
+use crate::types::{AppData, SkillDuplicateGroup, SkillDuplicateMember, SkillMergeResult};
+use crate::utils::{expand_path, parse_skill_md};
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Default similarity above which two skills are reported as near-duplicates
+const DEFAULT_SIMILARITY_THRESHOLD: f64 = 0.8;
+
+/// Words per shingle used for fuzzy comparison
+const SHINGLE_SIZE: usize = 3;
+
+/// Content fingerprint of a single skill
+struct SkillFingerprint {
+    id: String,
+    name: String,
+    /// Hash over the normalized SKILL.md body and bundled file hashes
+    exact: String,
+    /// Word shingles of the normalized SKILL.md body and bundled text files
+    shingles: HashSet<String>,
+}
+
+/// Normalize text for comparison: lowercase words, punctuation and
+/// whitespace differences ignored
+fn normalize_words(text: &str) -> Vec<String> {
+    text.to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .map(|w| w.to_string())
+        .collect()
+}
+
+fn shingles(words: &[String]) -> HashSet<String> {
+    if words.len() < SHINGLE_SIZE {
+        return words.iter().cloned().collect();
+    }
+    words.windows(SHINGLE_SIZE).map(|w| w.join(" ")).collect()
+}
+
+/// Jaccard similarity of two shingle sets
+fn jaccard(a: &HashSet<String>, b: &HashSet<String>) -> f64 {
+    if a.is_empty() && b.is_empty() {
+        return 1.0;
+    }
+    let intersection = a.intersection(b).count() as f64;
+    let union = (a.len() + b.len()) as f64 - intersection;
+    intersection / union
+}
+
+/// Fingerprint a skill directory (SKILL.md body plus bundled files)
+fn fingerprint_skill(skill_dir: &Path) -> Result<SkillFingerprint, String> {
+    let content = fs::read_to_string(skill_dir.join("SKILL.md")).map_err(|e| e.to_string())?;
+    let (frontmatter, body) = parse_skill_md(&content);
+    let mut words = normalize_words(&body);
+
+    let mut hasher = Sha256::new();
+    hasher.update(words.join(" ").as_bytes());
+
+    let files: BTreeMap<String, String> = hash_working_tree(skill_dir)?;
+    for (path, hash) in files.iter().filter(|(p, _)| p.as_str() != "SKILL.md") {
+        hasher.update(path.as_bytes());
+        hasher.update([0]);
+        hasher.update(hash.as_bytes());
+        if let Ok(text) = fs::read_to_string(skill_dir.join(path)) {
+            words.extend(normalize_words(&text));
+        }
+    }
+
+    let dir_name = skill_dir
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+
+    Ok(SkillFingerprint {
+        id: skill_dir.to_string_lossy().to_string(),
+        name: frontmatter.name.unwrap_or(dir_name),
+        exact: hasher.finalize().iter().map(|b| format!("{:02x}", b)).collect(),
+        shingles: shingles(&words),
+    })
+}
+
+/// List skill directories in the source directory (same rules as `scan_skills`)
+fn list_skill_dirs(source_dir: &Path) -> Vec<PathBuf> {
+    let mut dirs: Vec<PathBuf> = fs::read_dir(source_dir)
+        .map(|entries| {
+            entries
+                .filter_map(|e| e.ok())
+                .map(|e| e.path())
+                .filter(|p| {
+                    !p.file_name()
+                        .map(|n| n.to_string_lossy().starts_with('.'))
+                        .unwrap_or(true)
+                })
+                .filter(|p| p.is_dir() && p.join("SKILL.md").exists())
+                .collect()
+        })
+        .unwrap_or_default();
+    dirs.sort();
+    dirs
+}
+
+fn find_root(parent: &mut [usize], mut i: usize) -> usize {
+    while parent[i] != i {
+        parent[i] = parent[parent[i]];
+        i = parent[i];
+    }
+    i
+}
+
+/// Group fingerprints whose content is identical or similar above `threshold`
+fn group_duplicates(prints: &[SkillFingerprint], threshold: f64) -> Vec<SkillDuplicateGroup> {
+    let n = prints.len();
+    let mut parent: Vec<usize> = (0..n).collect();
+
+    for i in 0..n {
+        for j in (i + 1)..n {
+            let matched = prints[i].exact == prints[j].exact
+                || jaccard(&prints[i].shingles, &prints[j].shingles) >= threshold;
+            if matched {
+                let (a, b) = (find_root(&mut parent, i), find_root(&mut parent, j));
+                if a != b {
+                    parent[b] = a;
+                }
+            }
+        }
+    }
+
+    let mut members: BTreeMap<usize, Vec<usize>> = BTreeMap::new();
+    for i in 0..n {
+        let root = find_root(&mut parent, i);
+        members.entry(root).or_default().push(i);
+    }
+
+    members
+        .into_values()
+        .filter(|indices| indices.len() > 1)
+        .map(|indices| {
+            let first = &prints[indices[0]];
+            let exact = indices.iter().all(|&i| prints[i].exact == first.exact);
+            SkillDuplicateGroup {
+                kind: if exact { "exact" } else { "similar" }.to_string(),
+                skills: indices
+                    .iter()
+                    .map(|&i| SkillDuplicateMember {
+                        id: prints[i].id.clone(),
+                        name: prints[i].name.clone(),
+                        similarity: if prints[i].exact == first.exact {
+                            1.0
+                        } else {
+                            jaccard(&first.shingles, &prints[i].shingles)
+                        },
+                    })
+                    .collect(),
+            }
+        })
+        .collect()
+}
+
+/// Find exact and near-duplicate skills in the skills directory
+///
+/// `threshold` is the minimum normalized-text similarity (0.0 - 1.0) for
+/// near-duplicates; defaults to 0.8.
+#[tauri::command]
+pub fn find_duplicate_skills(
+    source_dir: String,
+    threshold: Option<f64>,
+) -> Result<Vec<SkillDuplicateGroup>, String> {
+    let threshold = threshold.unwrap_or(DEFAULT_SIMILARITY_THRESHOLD);
+    if !(0.0..=1.0).contains(&threshold) {
+        return Err(format!("Invalid similarity threshold: {}", threshold));
+    }
+
+    let prints: Vec<SkillFingerprint> = list_skill_dirs(&expand_path(&source_dir))
+        .iter()
+        .filter_map(|dir| fingerprint_skill(dir).ok())
+        .collect();
+
+    Ok(group_duplicates(&prints, threshold))
+}
+
+/// Move project version pins of removed skills to the kept skill
+///
+/// A pin is carried over when `equivalent` finds a version of the kept skill
+/// with the same content; otherwise it is dropped, since the pinned
+/// history goes away with the removed skill. Pins already set on the kept
+/// skill win.
+fn remap_pinned_versions(
+    data: &mut AppData,
+    remove_names: &[String],
+    keep_name: &str,
+    equivalent: impl Fn(&str, &str) -> Option<String>,
+) {
+    for project in data.projects.iter_mut() {
+        for name in remove_names {
+            let Some(version_id) = project.pinned_skill_versions.remove(name) else {
+                continue;
+            };
+            if project.pinned_skill_versions.contains_key(keep_name) {
+                continue;
+            }
+            match equivalent(name, &version_id) {
+                Some(kept_version) => {
+                    project.pinned_skill_versions.insert(keep_name.to_string(), kept_version);
+                }
+                None => log::warn!(
+                    "Dropping pin of {} to {} in project {}: no matching version of {}",
+                    name,
+                    version_id,
+                    project.name,
+                    keep_name
+                ),
+            }
+        }
+    }
+}
+
+/// Merge duplicate skills: keep `keep_id`, move the others to trash and
+/// rewrite scene references to point at the kept skill
+///
+/// Usage counts and tags of the removed skills are folded into the kept one
+/// and project version pins are carried over. data.json is updated before
+/// any file is moved; skills that cannot be trashed are reported in
+/// `errors`.
+#[tauri::command]
+pub fn merge_duplicate_skills(
+    keep_id: String,
+    remove_ids: Vec<String>,
+    ensemble_dir: String,
+) -> Result<SkillMergeResult, String> {
+    let _guard = DATA_MUTEX.lock().map_err(|e| e.to_string())?;
+
+    if !Path::new(&keep_id).exists() {
+        return Err(format!("Skill not found: {}", keep_id));
+    }
+    let remove_ids: Vec<String> = remove_ids.into_iter().filter(|id| *id != keep_id).collect();
+    let ensemble_path = expand_path(&ensemble_dir);
+
+    let mut data = read_app_data()?;
+
+    // Rewrite scene references, keeping order and dropping duplicates
+    let mut updated_scene_ids = Vec::new();
+    for scene in data.scenes.iter_mut() {
+        if !scene.skill_ids.iter().any(|id| remove_ids.contains(id)) {
+            continue;
+        }
+        let mut seen = HashSet::new();
+        scene.skill_ids = scene
+            .skill_ids
+            .iter()
+            .map(|id| if remove_ids.contains(id) { keep_id.clone() } else { id.clone() })
+            .filter(|id| seen.insert(id.clone()))
+            .collect();
+        updated_scene_ids.push(scene.id.clone());
+    }
+    for scene in data.trashed_scenes.iter_mut() {
+        let mut seen = HashSet::new();
+        scene.skill_ids = scene
+            .skill_ids
+            .iter()
+            .map(|id| if remove_ids.contains(id) { keep_id.clone() } else { id.clone() })
+            .filter(|id| seen.insert(id.clone()))
+            .collect();
+    }
+
+    // Fold metadata of removed skills into the kept skill
+    let removed_metadata: Vec<_> = remove_ids
+        .iter()
+        .filter_map(|id| data.skill_metadata.remove(id))
+        .collect();
+    if let Some(kept) = data.skill_metadata.get_mut(&keep_id) {
+        for meta in removed_metadata {
+            kept.usage_count += meta.usage_count;
+            if meta.last_used > kept.last_used {
+                kept.last_used = meta.last_used;
+            }
+            for tag in meta.tags {
+                if !kept.tags.contains(&tag) {
+                    kept.tags.push(tag);
+                }
+            }
+        }
+    }
+
+    let file_name = |id: &str| Path::new(id).file_name().map(|n| n.to_string_lossy().to_string());
+    let remove_names: Vec<String> = remove_ids.iter().filter_map(|id| file_name(id)).collect();
+    if let Some(keep_name) = file_name(&keep_id) {
+        remap_pinned_versions(&mut data, &remove_names, &keep_name, |name, version_id| {
+            equivalent_version(name, version_id, &keep_name)
+        });
+    }
+
+    write_app_data(data)?;
+
+    let mut removed_ids = Vec::new();
+    let mut errors = Vec::new();
+    for id in &remove_ids {
+        let path = Path::new(id);
+        if path.exists() || path.symlink_metadata().is_ok() {
+            match move_skill_to_trash(path, &ensemble_path) {
+                Ok(_) => removed_ids.push(id.clone()),
+                Err(e) => errors.push(format!("{}: {}", id, e)),
+            }
+        }
+    }
+
+    Ok(SkillMergeResult {
+        kept_id: keep_id,
+        removed_ids,
+        updated_scene_ids,
+        errors,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn write_skill(root: &Path, name: &str, body: &str) -> PathBuf {
+        let dir = root.join(name);
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(
+            dir.join("SKILL.md"),
+            format!("---\nname: {}\n---\n{}", name, body),
+        )
+        .unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_normalize_words_ignores_case_and_punctuation() {
+        assert_eq!(
+            normalize_words("Run `cargo test`, then\n  COMMIT!"),
+            vec!["run", "cargo", "test", "then", "commit"]
+        );
+    }
+
+    #[test]
+    fn test_group_duplicates_exact_and_similar() {
+        let tmp = TempDir::new().unwrap();
+        let body = "Write a conventional commit message. Summarize the staged diff \
+                    in one line, then explain why the change was made in the body.";
+        write_skill(tmp.path(), "commit", body);
+        // Same body, different name and formatting: exact duplicate
+        write_skill(tmp.path(), "commit-copy", &body.to_uppercase().replace(". ", ".\n\n"));
+        // Slightly diverged copy: near-duplicate
+        write_skill(
+            tmp.path(),
+            "commit-old",
+            &format!("{} Always reference the issue number.", body),
+        );
+        write_skill(tmp.path(), "review", "Review the pull request for bugs and style issues.");
+
+        let prints: Vec<SkillFingerprint> = list_skill_dirs(tmp.path())
+            .iter()
+            .map(|d| fingerprint_skill(d).unwrap())
+            .collect();
+
+        let exact_only = group_duplicates(&prints, 1.0);
+        assert_eq!(exact_only.len(), 1);
+        assert_eq!(exact_only[0].kind, "exact");
+        assert_eq!(exact_only[0].skills.len(), 2);
+
+        let fuzzy = group_duplicates(&prints, 0.7);
+        assert_eq!(fuzzy.len(), 1);
+        assert_eq!(fuzzy[0].kind, "similar");
+        let names: Vec<&str> = fuzzy[0].skills.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, vec!["commit", "commit-copy", "commit-old"]);
+        assert!(fuzzy[0].skills[2].similarity < 1.0);
+    }
+
+    #[test]
+    fn test_bundled_files_are_part_of_the_fingerprint() {
+        let tmp = TempDir::new().unwrap();
+        let a = write_skill(tmp.path(), "a", "Same body");
+        let b = write_skill(tmp.path(), "b", "Same body");
+        fs::write(a.join("script.sh"), "echo one").unwrap();
+        fs::write(b.join("script.sh"), "echo two").unwrap();
+
+        let fa = fingerprint_skill(&a).unwrap();
+        let fb = fingerprint_skill(&b).unwrap();
+        assert_ne!(fa.exact, fb.exact);
+    }
+
+    #[test]
+    fn test_remap_pinned_versions() {
+        let mut data = AppData {
+            projects: serde_json::from_value(serde_json::json!([
+                { "id": "p1", "name": "a", "path": "/a", "sceneId": "s", "lastSynced": null,
+                  "pinnedSkillVersions": { "commit-copy": "v-copy" } },
+                { "id": "p2", "name": "b", "path": "/b", "sceneId": "s", "lastSynced": null,
+                  "pinnedSkillVersions": { "commit-old": "v-old" } },
+                { "id": "p3", "name": "c", "path": "/c", "sceneId": "s", "lastSynced": null,
+                  "pinnedSkillVersions": { "commit": "v-kept", "commit-copy": "v-copy" } }
+            ]))
+            .unwrap(),
+            ..Default::default()
+        };
+
+        let removed = vec!["commit-copy".to_string(), "commit-old".to_string()];
+        remap_pinned_versions(&mut data, &removed, "commit", |name, version| {
+            (name == "commit-copy" && version == "v-copy").then(|| "v-same".to_string())
+        });
+
+        let pins: Vec<_> = data.projects.iter().map(|p| p.pinned_skill_versions.clone()).collect();
+        assert_eq!(pins[0].get("commit").map(String::as_str), Some("v-same"));
+        assert!(pins[1].is_empty());
+        assert_eq!(pins[2].len(), 1);
+        assert_eq!(pins[2].get("commit").map(String::as_str), Some("v-kept"));
+    }
+}
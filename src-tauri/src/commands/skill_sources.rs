@@ -0,0 +1,515 @@
+use crate::commands::data::{read_app_data, write_app_data, DATA_MUTEX};
+use crate::commands::skill_versions::{diff_directories, hash_working_tree, record_skill_version};
+use crate::types::{SkillMetadata, SkillOrigin, SkillSourceImportResult, SkillSourceUpdate};
+use crate::utils::expand_path;
+use chrono::Utc;
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use uuid::Uuid;
+use walkdir::WalkDir;
+
+/// Maximum depth searched for SKILL.md files inside a source
+const MAX_DISCOVERY_DEPTH: usize = 4;
+
+/// Directories never searched for skills
+const SKIPPED_DIRS: &[&str] = &["node_modules", "target", "dist", "build"];
+
+// ============================================================================
+// Staging
+// ============================================================================
+
+/// Temporary directory holding a cloned or extracted source; removed on drop
+struct StagingDir(PathBuf);
+
+impl StagingDir {
+    fn new() -> Result<Self, String> {
+        let dir = std::env::temp_dir().join(format!("ensemble-import-{}", Uuid::new_v4()));
+        fs::create_dir_all(&dir).map_err(|e| format!("Failed to create staging directory: {}", e))?;
+        Ok(Self(dir))
+    }
+}
+
+impl Drop for StagingDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.0);
+    }
+}
+
+/// Run an external command and return its trimmed stdout
+fn run_command(program: &str, args: &[&str]) -> Result<String, String> {
+    let output = Command::new(program)
+        .args(args)
+        .output()
+        .map_err(|e| format!("Failed to execute {}: {}. Make sure it is installed and available in PATH.", program, e))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("{} failed: {}", program, stderr.trim()));
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+fn is_archive(source: &str) -> bool {
+    let lower = source.to_lowercase();
+    lower.ends_with(".zip") || lower.ends_with(".tar.gz") || lower.ends_with(".tgz")
+}
+
+/// Detect the source type: "git" for repositories, "archive" for .zip/.tar.gz
+fn detect_source_type(source: &str) -> Result<&'static str, String> {
+    let path = expand_path(source);
+    if path.is_file() && is_archive(source) {
+        return Ok("archive");
+    }
+    if path.is_dir() {
+        if path.join(".git").exists() {
+            return Ok("git");
+        }
+        return Err(format!("{} is not a git repository", source));
+    }
+    if source.contains("://") || source.starts_with("git@") {
+        return Ok("git");
+    }
+    Err(format!("Unsupported skill source: {}", source))
+}
+
+/// Default skill name for a skill at the root of a source
+fn source_name(source: &str) -> String {
+    let trimmed = source.trim_end_matches('/');
+    let name = trimmed.rsplit(['/', ':']).next().unwrap_or(trimmed);
+    let lower = name.to_lowercase();
+    for suffix in [".tar.gz", ".tgz", ".zip", ".git"] {
+        if lower.ends_with(suffix) {
+            return name[..name.len() - suffix.len()].to_string();
+        }
+    }
+    name.to_string()
+}
+
+/// Clone or extract a source into the staging directory
+///
+/// Returns the source root and its revision (git commit, or archive SHA-256).
+fn stage_source(
+    source_type: &str,
+    source: &str,
+    staging: &StagingDir,
+) -> Result<(PathBuf, String), String> {
+    let source_path = expand_path(source);
+
+    if source_type == "git" {
+        // A leading dash would be read by git as an option (--upload-pack=...)
+        if source.starts_with('-') {
+            return Err(format!("Invalid git source: {}", source));
+        }
+        let repo = staging.0.join("repo");
+        let location = if source_path.exists() {
+            source_path.to_string_lossy().to_string()
+        } else {
+            source.to_string()
+        };
+        let repo_str = repo.to_string_lossy().to_string();
+        run_command("git", &["clone", "--quiet", "--", &location, &repo_str])?;
+        let revision = run_command("git", &["-C", &repo_str, "rev-parse", "HEAD"])?;
+        return Ok((repo, revision));
+    }
+
+    let bytes = fs::read(&source_path).map_err(|e| format!("Failed to read archive: {}", e))?;
+    let revision: String = Sha256::digest(&bytes).iter().map(|b| format!("{:02x}", b)).collect();
+
+    let extracted = staging.0.join("archive");
+    fs::create_dir_all(&extracted).map_err(|e| e.to_string())?;
+    let archive_str = source_path.to_string_lossy().to_string();
+    let extracted_str = extracted.to_string_lossy().to_string();
+    if source.to_lowercase().ends_with(".zip") {
+        run_command("unzip", &["-q", &archive_str, "-d", &extracted_str])?;
+    } else {
+        run_command("tar", &["-xzf", &archive_str, "-C", &extracted_str])?;
+    }
+
+    // Archives usually wrap everything in a single versioned folder
+    // ("skills-1.2.0/"); unwrap it so subpaths stay stable across versions
+    let entries: Vec<PathBuf> = fs::read_dir(&extracted)
+        .map_err(|e| e.to_string())?
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|p| !p.file_name().map(|n| n.to_string_lossy().starts_with('.')).unwrap_or(false))
+        .collect();
+    let root = match entries.as_slice() {
+        [single] if single.is_dir() && !extracted.join("SKILL.md").exists() => single.clone(),
+        _ => extracted,
+    };
+
+    Ok((root, revision))
+}
+
+/// Find skill directories (containing SKILL.md) under `base`
+///
+/// Skills nested inside another skill are not reported separately.
+fn discover_skills(base: &Path) -> Vec<PathBuf> {
+    if base.join("SKILL.md").is_file() {
+        return vec![base.to_path_buf()];
+    }
+
+    let mut found: Vec<PathBuf> = Vec::new();
+    let walker = WalkDir::new(base)
+        .max_depth(MAX_DISCOVERY_DEPTH)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_entry(|e| {
+            let name = e.file_name().to_string_lossy();
+            e.depth() == 0 || !(name.starts_with('.') || SKIPPED_DIRS.contains(&name.as_ref()))
+        });
+
+    for entry in walker.filter_map(|e| e.ok()) {
+        if !entry.file_type().is_file() || entry.file_name() != "SKILL.md" {
+            continue;
+        }
+        if let Some(dir) = entry.path().parent() {
+            if !found.iter().any(|f| dir.starts_with(f)) {
+                found.push(dir.to_path_buf());
+            }
+        }
+    }
+
+    found
+}
+
+/// Path of `dir` relative to `root`, '/'-separated ("" for the root itself)
+fn relative_subpath(root: &Path, dir: &Path) -> String {
+    dir.strip_prefix(root)
+        .map(|p| {
+            p.components()
+                .map(|c| c.as_os_str().to_string_lossy().to_string())
+                .collect::<Vec<_>>()
+                .join("/")
+        })
+        .unwrap_or_default()
+}
+
+/// Copy a skill's versioned files (skipping .git and similar) into `dest`
+fn copy_skill_files(source: &Path, dest: &Path) -> Result<(), String> {
+    fs::create_dir_all(dest).map_err(|e| e.to_string())?;
+    for rel_path in hash_working_tree(source)?.keys() {
+        let target = dest.join(rel_path);
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+        }
+        fs::copy(source.join(rel_path), &target)
+            .map_err(|e| format!("Failed to copy {}: {}", rel_path, e))?;
+    }
+    Ok(())
+}
+
+/// Look up the recorded origin of an imported skill
+fn read_origin(skill_id: &str) -> Result<SkillOrigin, String> {
+    read_app_data()?
+        .skill_metadata
+        .get(skill_id)
+        .and_then(|m| m.origin.clone())
+        .ok_or_else(|| format!("Skill was not imported from a git repository or archive: {}", skill_id))
+}
+
+/// Stage the latest revision of an imported skill
+fn stage_latest(origin: &SkillOrigin, staging: &StagingDir) -> Result<(PathBuf, String), String> {
+    let (root, revision) = stage_source(&origin.source_type, &origin.source, staging)?;
+    let skill_dir = if origin.subpath.is_empty() {
+        root
+    } else {
+        root.join(&origin.subpath)
+    };
+    if !skill_dir.join("SKILL.md").is_file() {
+        return Err(format!(
+            "Skill no longer exists in {} at '{}'",
+            origin.source, origin.subpath
+        ));
+    }
+    Ok((skill_dir, revision))
+}
+
+// ============================================================================
+// Commands
+// ============================================================================
+
+/// Import a skill, or a folder of skills, from a git repository or a
+/// .zip/.tar.gz archive into the skills directory
+///
+/// `subpath` limits discovery to a folder inside the source. The origin and
+/// revision are recorded in skill metadata so the skill can be updated later.
+#[tauri::command]
+pub fn import_skills_from_source(
+    source: String,
+    dest_dir: String,
+    subpath: Option<String>,
+) -> Result<SkillSourceImportResult, String> {
+    let source_type = detect_source_type(&source)?;
+    let staging = StagingDir::new()?;
+    let (root, revision) = stage_source(source_type, &source, &staging)?;
+
+    let base = match subpath.as_deref().map(|s| s.trim_matches('/')) {
+        Some(sub) if !sub.is_empty() => root.join(sub),
+        _ => root.clone(),
+    };
+    let skill_dirs = discover_skills(&base);
+    if skill_dirs.is_empty() {
+        return Err(format!("No SKILL.md found in {}", source));
+    }
+
+    let dest_path = expand_path(&dest_dir);
+    fs::create_dir_all(&dest_path).map_err(|e| e.to_string())?;
+
+    let mut imported = Vec::new();
+    let mut errors = Vec::new();
+    let mut origins = Vec::new();
+
+    for skill_dir in skill_dirs {
+        let name = if skill_dir == root {
+            source_name(&source)
+        } else {
+            skill_dir
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_default()
+        };
+        let dest = dest_path.join(&name);
+        if dest.exists() || dest.symlink_metadata().is_ok() {
+            errors.push(format!("Skill '{}' already exists in destination", name));
+            continue;
+        }
+        if let Err(e) = copy_skill_files(&skill_dir, &dest) {
+            let _ = fs::remove_dir_all(&dest);
+            errors.push(format!("Failed to import skill '{}': {}", name, e));
+            continue;
+        }
+
+        let skill_id = dest.to_string_lossy().to_string();
+        origins.push((
+            skill_id.clone(),
+            SkillOrigin {
+                source_type: source_type.to_string(),
+                source: source.clone(),
+                subpath: relative_subpath(&root, &skill_dir),
+                revision: revision.clone(),
+                imported_at: Utc::now().to_rfc3339(),
+            },
+        ));
+        imported.push(skill_id);
+    }
+
+    if !origins.is_empty() {
+        let _guard = DATA_MUTEX.lock().map_err(|e| e.to_string())?;
+        let mut data = read_app_data()?;
+        for (skill_id, origin) in origins {
+            let metadata = data
+                .skill_metadata
+                .entry(skill_id)
+                .or_insert_with(|| SkillMetadata {
+                    enabled: true,
+                    ..Default::default()
+                });
+            metadata.origin = Some(origin);
+        }
+        write_app_data(data)?;
+    }
+
+    Ok(SkillSourceImportResult {
+        imported,
+        revision,
+        errors,
+    })
+}
+
+/// Check an imported skill for a newer revision and show what would change
+#[tauri::command]
+pub fn check_skill_source_update(skill_id: String) -> Result<SkillSourceUpdate, String> {
+    let origin = read_origin(&skill_id)?;
+    let staging = StagingDir::new()?;
+    let (latest_dir, latest_revision) = stage_latest(&origin, &staging)?;
+
+    let files = diff_directories(&expand_path(&skill_id), &latest_dir)?;
+
+    Ok(SkillSourceUpdate {
+        skill_id,
+        current_revision: origin.revision,
+        latest_revision,
+        has_changes: !files.is_empty(),
+        files,
+    })
+}
+
+/// Replace `skill_dir` with `replacement`
+///
+/// The current directory is moved aside first and only deleted once the
+/// replacement is in place; it is moved back if the swap fails.
+fn swap_skill_dir(replacement: &Path, skill_dir: &Path) -> Result<(), String> {
+    let name = skill_dir
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .ok_or("Invalid skill path")?;
+    let previous = skill_dir.with_file_name(format!(".{}.previous", name));
+    if previous.exists() {
+        fs::remove_dir_all(&previous).map_err(|e| e.to_string())?;
+    }
+
+    fs::rename(skill_dir, &previous).map_err(|e| format!("Failed to move current skill aside: {}", e))?;
+    if let Err(e) = fs::rename(replacement, skill_dir) {
+        return match fs::rename(&previous, skill_dir) {
+            Ok(()) => Err(format!("Failed to update skill: {}", e)),
+            Err(restore) => Err(format!(
+                "Failed to update skill: {}; previous version left at {}: {}",
+                e,
+                previous.display(),
+                restore
+            )),
+        };
+    }
+    if let Err(e) = fs::remove_dir_all(&previous) {
+        log::warn!("Failed to remove previous skill version {}: {}", previous.display(), e);
+    }
+    Ok(())
+}
+
+/// Apply an update previously shown by `check_skill_source_update`
+///
+/// `revision` must match the latest revision, so the user applies exactly
+/// what they reviewed. The current skill is snapshotted first so the update
+/// can be rolled back.
+#[tauri::command]
+pub fn apply_skill_source_update(skill_id: String, revision: String) -> Result<(), String> {
+    let origin = read_origin(&skill_id)?;
+    let staging = StagingDir::new()?;
+    let (latest_dir, latest_revision) = stage_latest(&origin, &staging)?;
+    if latest_revision != revision {
+        return Err(format!(
+            "Source changed since it was reviewed (now at {}); check for updates again",
+            latest_revision
+        ));
+    }
+
+    let skill_dir = expand_path(&skill_id);
+    if skill_dir
+        .symlink_metadata()
+        .map(|m| m.file_type().is_symlink())
+        .unwrap_or(false)
+    {
+        return Err("Symlinked skills cannot be updated".to_string());
+    }
+
+    record_skill_version(&skill_dir, None)?;
+
+    // Replace files in a sibling directory first, then swap it in
+    let name = skill_dir
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .ok_or("Invalid skill path")?;
+    let partial = skill_dir.with_file_name(format!(".{}.partial", name));
+    if partial.exists() {
+        fs::remove_dir_all(&partial).map_err(|e| e.to_string())?;
+    }
+    copy_skill_files(&latest_dir, &partial)?;
+    swap_skill_dir(&partial, &skill_dir)?;
+
+    record_skill_version(&skill_dir, Some(format!("Updated to {}", latest_revision)))?;
+
+    let _guard = DATA_MUTEX.lock().map_err(|e| e.to_string())?;
+    let mut data = read_app_data()?;
+    if let Some(metadata) = data.skill_metadata.get_mut(&skill_id) {
+        metadata.origin = Some(SkillOrigin {
+            revision: latest_revision,
+            imported_at: Utc::now().to_rfc3339(),
+            ..origin
+        });
+    }
+    write_app_data(data)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_source_name_strips_suffixes() {
+        assert_eq!(source_name("/tmp/my-skill.tar.gz"), "my-skill");
+        assert_eq!(source_name("/tmp/pack.zip"), "pack");
+        assert_eq!(source_name("https://example.com/org/repo.git"), "repo");
+        assert_eq!(source_name("git@example.com:org/repo.git"), "repo");
+        assert_eq!(source_name("/home/me/skills/"), "skills");
+    }
+
+    #[test]
+    fn test_discover_skills_skips_nested_and_hidden() {
+        let tmp = TempDir::new().unwrap();
+        let root = tmp.path();
+        for dir in ["skills/commit", "skills/commit/examples/inner", "skills/review", ".github/x"] {
+            fs::create_dir_all(root.join(dir)).unwrap();
+            fs::write(root.join(dir).join("SKILL.md"), "# Skill").unwrap();
+        }
+
+        let found: Vec<String> = discover_skills(root)
+            .iter()
+            .map(|p| relative_subpath(root, p))
+            .collect();
+        assert_eq!(found, vec!["skills/commit", "skills/review"]);
+    }
+
+    #[test]
+    fn test_stage_git_source_reports_commit() {
+        let tmp = TempDir::new().unwrap();
+        let repo = tmp.path().join("repo");
+        fs::create_dir_all(repo.join("commit")).unwrap();
+        fs::write(repo.join("commit").join("SKILL.md"), "# Commit").unwrap();
+        let repo_str = repo.to_string_lossy().to_string();
+        let git = |args: &[&str]| {
+            let mut full = vec!["-C", repo_str.as_str(), "-c", "user.name=t", "-c", "user.email=t@t"];
+            full.extend_from_slice(args);
+            run_command("git", &full)
+        };
+        if git(&["init", "--quiet"]).is_err() {
+            return; // git unavailable
+        }
+        git(&["add", "-A"]).unwrap();
+        git(&["commit", "--quiet", "-m", "init"]).unwrap();
+        let head = git(&["rev-parse", "HEAD"]).unwrap();
+
+        assert_eq!(detect_source_type(&repo_str).unwrap(), "git");
+        let staging = StagingDir::new().unwrap();
+        let (root, revision) = stage_source("git", &repo_str, &staging).unwrap();
+        assert_eq!(revision, head);
+        assert_eq!(discover_skills(&root), vec![root.join("commit")]);
+        assert!(!root.join("commit").join(".git").exists());
+    }
+
+    #[test]
+    fn test_stage_source_rejects_option_like_git_source() {
+        let tmp = TempDir::new().unwrap();
+        let marker = tmp.path().join("pwned");
+        let source = format!("--upload-pack=touch {}://x", marker.display());
+        assert_eq!(detect_source_type(&source).unwrap(), "git");
+
+        let staging = StagingDir::new().unwrap();
+        let err = stage_source("git", &source, &staging).unwrap_err();
+        assert!(err.starts_with("Invalid git source"));
+        assert!(!marker.exists());
+    }
+
+    #[test]
+    fn test_swap_skill_dir_keeps_old_version_on_failure() {
+        let tmp = TempDir::new().unwrap();
+        let skill = tmp.path().join("commit");
+        fs::create_dir_all(&skill).unwrap();
+        fs::write(skill.join("SKILL.md"), "old").unwrap();
+
+        // Missing replacement: the rename fails and the skill is restored
+        assert!(swap_skill_dir(&tmp.path().join("missing"), &skill).is_err());
+        assert_eq!(fs::read_to_string(skill.join("SKILL.md")).unwrap(), "old");
+
+        let partial = tmp.path().join(".commit.partial");
+        fs::create_dir_all(&partial).unwrap();
+        fs::write(partial.join("SKILL.md"), "new").unwrap();
+        swap_skill_dir(&partial, &skill).unwrap();
+        assert_eq!(fs::read_to_string(skill.join("SKILL.md")).unwrap(), "new");
+        assert!(!tmp.path().join(".commit.previous").exists());
+    }
+}
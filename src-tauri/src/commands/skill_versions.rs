@@ -0,0 +1,588 @@
+use crate::commands::data::{read_app_data, write_app_data, DATA_MUTEX};
+use crate::commands::deploy::DEPLOY_STAMP_FILE;
+use crate::commands::library::get_library_dir;
+use crate::types::{
+    Skill, SkillFileDiff, SkillVersion, SkillVersionDiff, SkillVersionFile,
+    SkillVersionHistory,
+};
+use crate::utils::diff::unified_diff;
+use crate::utils::expand_path;
+use chrono::Utc;
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::UNIX_EPOCH;
+use uuid::Uuid;
+use walkdir::WalkDir;
+
+/// Serialises access to the version store (history files and objects)
+static STORE_MUTEX: Mutex<()> = Mutex::new(());
+
+/// Entries never captured in a snapshot
+const IGNORED_NAMES: &[&str] = &[".git", ".DS_Store", DEPLOY_STAMP_FILE];
+
+// ============================================================================
+// Version store layout
+// ============================================================================
+
+/// Get the version store root ({library}/skill-versions/)
+fn get_store_dir() -> PathBuf {
+    get_library_dir().join("skill-versions")
+}
+
+/// Get the object path for a content hash (objects/{hash[0..2]}/{hash})
+fn get_object_path(store: &Path, hash: &str) -> PathBuf {
+    store.join("objects").join(&hash[..2]).join(hash)
+}
+
+/// Get the history file of a skill (history/{skill_name}.json)
+///
+/// History is keyed by the skill directory name rather than its full path
+/// so it survives library relocation.
+fn get_history_path(store: &Path, skill_name: &str) -> PathBuf {
+    store.join("history").join(format!("{}.json", skill_name))
+}
+
+fn read_history(store: &Path, skill_name: &str) -> Result<SkillVersionHistory, String> {
+    let path = get_history_path(store, skill_name);
+    if !path.exists() {
+        return Ok(SkillVersionHistory {
+            skill_name: skill_name.to_string(),
+            ..Default::default()
+        });
+    }
+    let content = fs::read_to_string(&path).map_err(|e| e.to_string())?;
+    serde_json::from_str(&content).map_err(|e| format!("Failed to parse {}: {}", path.display(), e))
+}
+
+fn write_history(store: &Path, history: &SkillVersionHistory) -> Result<(), String> {
+    let path = get_history_path(store, &history.skill_name);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    let json = serde_json::to_string_pretty(history).map_err(|e| e.to_string())?;
+    fs::write(&path, json).map_err(|e| e.to_string())
+}
+
+fn skill_name_of(skill_dir: &Path) -> Result<String, String> {
+    skill_dir
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .ok_or_else(|| format!("Invalid skill path: {}", skill_dir.display()))
+}
+
+fn hash_bytes(bytes: &[u8]) -> String {
+    let digest = Sha256::digest(bytes);
+    digest.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+// ============================================================================
+// Snapshotting
+// ============================================================================
+
+/// A file in a skill directory on disk
+struct WorkingFile {
+    rel_path: String,
+    abs_path: PathBuf,
+    size: u64,
+    mtime_nanos: u128,
+}
+
+/// List the files of a skill directory, sorted by relative path
+fn collect_working_files(skill_dir: &Path) -> Result<Vec<WorkingFile>, String> {
+    let mut files = Vec::new();
+    let walker = WalkDir::new(skill_dir)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_entry(|e| {
+            e.depth() == 0 || !IGNORED_NAMES.contains(&e.file_name().to_string_lossy().as_ref())
+        });
+
+    for entry in walker {
+        let entry = entry.map_err(|e| e.to_string())?;
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        let meta = entry.metadata().map_err(|e| e.to_string())?;
+        let rel = entry
+            .path()
+            .strip_prefix(skill_dir)
+            .map_err(|e| e.to_string())?
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy().to_string())
+            .collect::<Vec<_>>()
+            .join("/");
+        let mtime_nanos = meta
+            .modified()
+            .ok()
+            .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+            .map(|d| d.as_nanos())
+            .unwrap_or(0);
+        files.push(WorkingFile {
+            rel_path: rel,
+            abs_path: entry.path().to_path_buf(),
+            size: meta.len(),
+            mtime_nanos,
+        });
+    }
+
+    files.sort_by(|a, b| a.rel_path.cmp(&b.rel_path));
+    Ok(files)
+}
+
+/// Cheap change fingerprint: file count, total size and latest mtime
+fn fingerprint(files: &[WorkingFile]) -> String {
+    let total: u64 = files.iter().map(|f| f.size).sum();
+    let latest = files.iter().map(|f| f.mtime_nanos).max().unwrap_or(0);
+    format!("{}:{}:{}", files.len(), total, latest)
+}
+
+/// Hash over a sorted (path, hash) list, identifying a version's content
+fn content_hash<'a>(files: impl Iterator<Item = (&'a str, &'a str)>) -> String {
+    let mut hasher = Sha256::new();
+    for (path, hash) in files {
+        hasher.update(path.as_bytes());
+        hasher.update([0]);
+        hasher.update(hash.as_bytes());
+        hasher.update([b'\n']);
+    }
+    hasher.finalize().iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// Hash every file of a directory (relative path -> SHA-256), skipping
+/// entries that are never versioned (.git, deploy stamps)
+pub(crate) fn hash_working_tree(dir: &Path) -> Result<BTreeMap<String, String>, String> {
+    let mut map = BTreeMap::new();
+    for file in collect_working_files(dir)? {
+        let bytes = fs::read(&file.abs_path).map_err(|e| e.to_string())?;
+        map.insert(file.rel_path, hash_bytes(&bytes));
+    }
+    Ok(map)
+}
+
+/// Content hash of a hashed directory tree (matches `SkillVersion::content_hash`)
+pub(crate) fn tree_hash(files: &BTreeMap<String, String>) -> String {
+    content_hash(files.iter().map(|(p, h)| (p.as_str(), h.as_str())))
+}
+
+/// Snapshot a skill directory into the version store
+///
+/// Returns the new version, or None when the content is unchanged since the
+/// latest version. Unless `force` is set, skills whose fingerprint matches the
+/// last snapshot are skipped without hashing.
+fn snapshot_in_store(
+    store: &Path,
+    skill_dir: &Path,
+    force: bool,
+    note: Option<String>,
+) -> Result<Option<SkillVersion>, String> {
+    let skill_name = skill_name_of(skill_dir)?;
+    let working = collect_working_files(skill_dir)?;
+    let current_fingerprint = fingerprint(&working);
+
+    let mut history = read_history(store, &skill_name)?;
+    if !force && !history.versions.is_empty() && history.fingerprint == current_fingerprint {
+        return Ok(None);
+    }
+
+    let mut files = Vec::with_capacity(working.len());
+    for file in &working {
+        let bytes = fs::read(&file.abs_path).map_err(|e| e.to_string())?;
+        let hash = hash_bytes(&bytes);
+        let object_path = get_object_path(store, &hash);
+        if !object_path.exists() {
+            if let Some(parent) = object_path.parent() {
+                fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+            }
+            // Write to a temp name first so a crash never leaves a truncated object
+            let tmp_path = object_path.with_extension("tmp");
+            fs::write(&tmp_path, &bytes).map_err(|e| e.to_string())?;
+            fs::rename(&tmp_path, &object_path).map_err(|e| e.to_string())?;
+        }
+        files.push(SkillVersionFile {
+            path: file.rel_path.clone(),
+            hash,
+            size: file.size,
+        });
+    }
+
+    let hash = content_hash(files.iter().map(|f| (f.path.as_str(), f.hash.as_str())));
+    history.fingerprint = current_fingerprint;
+
+    if history.versions.last().map(|v| v.content_hash == hash).unwrap_or(false) {
+        write_history(store, &history)?;
+        return Ok(None);
+    }
+
+    let version = SkillVersion {
+        id: Uuid::new_v4().to_string(),
+        content_hash: hash,
+        created_at: Utc::now().to_rfc3339(),
+        total_size: files.iter().map(|f| f.size).sum(),
+        files,
+        note,
+    };
+    history.versions.push(version.clone());
+    write_history(store, &history)?;
+
+    Ok(Some(version))
+}
+
+/// Write the files of a version into `dest` (which must not contain other files)
+fn materialize_version(store: &Path, version: &SkillVersion, dest: &Path) -> Result<(), String> {
+    fs::create_dir_all(dest).map_err(|e| e.to_string())?;
+    for file in &version.files {
+        let object_path = get_object_path(store, &file.hash);
+        let target = dest.join(&file.path);
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+        }
+        fs::copy(&object_path, &target)
+            .map_err(|e| format!("Failed to restore {}: {}", file.path, e))?;
+    }
+    Ok(())
+}
+
+fn find_version<'a>(
+    history: &'a SkillVersionHistory,
+    version_id: &str,
+) -> Result<&'a SkillVersion, String> {
+    history
+        .versions
+        .iter()
+        .find(|v| v.id == version_id)
+        .ok_or_else(|| format!("Version not found: {}", version_id))
+}
+
+/// Version of `to_skill` with the same content as `version_id` of `from_skill`
+pub(crate) fn equivalent_version(from_skill: &str, version_id: &str, to_skill: &str) -> Option<String> {
+    let _guard = STORE_MUTEX.lock().ok()?;
+    let store = get_store_dir();
+    let from = read_history(&store, from_skill).ok()?;
+    let content_hash = find_version(&from, version_id).ok()?.content_hash.clone();
+    read_history(&store, to_skill)
+        .ok()?
+        .versions
+        .iter()
+        .rev()
+        .find(|v| v.content_hash == content_hash)
+        .map(|v| v.id.clone())
+}
+
+/// Record a version of a skill directory if its content changed
+pub(crate) fn record_skill_version(
+    skill_dir: &Path,
+    note: Option<String>,
+) -> Result<Option<SkillVersion>, String> {
+    let _guard = STORE_MUTEX.lock().map_err(|e| e.to_string())?;
+    snapshot_in_store(&get_store_dir(), skill_dir, true, note)
+}
+
+/// Snapshot every changed skill after a scan (best effort)
+///
+/// Plugin-installed skills are skipped; their content is owned by the plugin.
+pub(crate) fn snapshot_scanned_skills(skills: &[Skill]) {
+    let Ok(_guard) = STORE_MUTEX.lock() else {
+        return;
+    };
+    let store = get_store_dir();
+    for skill in skills {
+        if skill.install_source.as_deref() == Some("plugin") {
+            continue;
+        }
+        if let Err(e) = snapshot_in_store(&store, &expand_path(&skill.id), false, None) {
+            log::warn!("Failed to snapshot skill {}: {}", skill.name, e);
+        }
+    }
+}
+
+/// Write a stored version of the skill at `source` into `dest`
+pub(crate) fn materialize_skill_version(
+    source: &Path,
+    version_id: &str,
+    dest: &Path,
+) -> Result<SkillVersion, String> {
+    let _guard = STORE_MUTEX.lock().map_err(|e| e.to_string())?;
+    let store = get_store_dir();
+    let history = read_history(&store, &skill_name_of(source)?)?;
+    let version = find_version(&history, version_id)?;
+
+    materialize_version(&store, version, dest)?;
+    Ok(version.clone())
+}
+
+// ============================================================================
+// Diffing
+// ============================================================================
+
+/// Diff two hashed file trees; `load_from` / `load_to` read a file's
+/// content given its relative path and hash
+fn diff_file_maps(
+    from: &BTreeMap<String, String>,
+    to: &BTreeMap<String, String>,
+    load_from: impl Fn(&str, &str) -> Vec<u8>,
+    load_to: impl Fn(&str, &str) -> Vec<u8>,
+) -> Vec<SkillFileDiff> {
+    let paths: BTreeSet<&String> = from.keys().chain(to.keys()).collect();
+    let mut diffs = Vec::new();
+
+    for path in paths {
+        let (status, old, new) = match (from.get(path), to.get(path)) {
+            (Some(a), Some(b)) if a == b => continue,
+            (Some(a), Some(b)) => ("modified", load_from(path, a), load_to(path, b)),
+            (Some(a), None) => ("removed", load_from(path, a), Vec::new()),
+            (None, Some(b)) => ("added", Vec::new(), load_to(path, b)),
+            (None, None) => continue,
+        };
+
+        let diff = match (String::from_utf8(old), String::from_utf8(new)) {
+            (Ok(old_text), Ok(new_text)) => Some(unified_diff(&old_text, &new_text)),
+            _ => None,
+        };
+
+        diffs.push(SkillFileDiff {
+            path: path.clone(),
+            status: status.to_string(),
+            diff,
+        });
+    }
+
+    diffs
+}
+
+/// Diff two directories on disk file by file
+pub(crate) fn diff_directories(from_dir: &Path, to_dir: &Path) -> Result<Vec<SkillFileDiff>, String> {
+    let from = hash_working_tree(from_dir)?;
+    let to = hash_working_tree(to_dir)?;
+    Ok(diff_file_maps(
+        &from,
+        &to,
+        |path, _| fs::read(from_dir.join(path)).unwrap_or_default(),
+        |path, _| fs::read(to_dir.join(path)).unwrap_or_default(),
+    ))
+}
+
+fn version_file_map(version: &SkillVersion) -> BTreeMap<String, String> {
+    version
+        .files
+        .iter()
+        .map(|f| (f.path.clone(), f.hash.clone()))
+        .collect()
+}
+
+// ============================================================================
+// Commands
+// ============================================================================
+
+/// List versions of a skill, newest first
+#[tauri::command]
+pub fn list_skill_versions(skill_id: String) -> Result<Vec<SkillVersion>, String> {
+    let store = get_store_dir();
+    let history = read_history(&store, &skill_name_of(&expand_path(&skill_id))?)?;
+    let mut versions = history.versions;
+    versions.reverse();
+    Ok(versions)
+}
+
+/// Snapshot a skill now; returns None when nothing changed since the latest version
+#[tauri::command]
+pub fn snapshot_skill(skill_id: String) -> Result<Option<SkillVersion>, String> {
+    let skill_dir = expand_path(&skill_id);
+    if !skill_dir.is_dir() {
+        return Err(format!("Skill not found: {}", skill_id));
+    }
+    record_skill_version(&skill_dir, None)
+}
+
+/// Diff two versions of a skill; when `to_version_id` is None the current
+/// skill directory is used as the right-hand side
+#[tauri::command]
+pub fn diff_skill_versions(
+    skill_id: String,
+    from_version_id: String,
+    to_version_id: Option<String>,
+) -> Result<SkillVersionDiff, String> {
+    let store = get_store_dir();
+    let skill_dir = expand_path(&skill_id);
+    let history = read_history(&store, &skill_name_of(&skill_dir)?)?;
+    let from = version_file_map(find_version(&history, &from_version_id)?);
+
+    let (to, working) = match &to_version_id {
+        Some(id) => (version_file_map(find_version(&history, id)?), false),
+        None => (hash_working_tree(&skill_dir)?, true),
+    };
+
+    Ok(SkillVersionDiff {
+        from_version: from_version_id,
+        to_version: to_version_id,
+        files: diff_file_maps(
+            &from,
+            &to,
+            |_, hash| fs::read(get_object_path(&store, hash)).unwrap_or_default(),
+            |path, hash| {
+                let file_path = if working {
+                    skill_dir.join(path)
+                } else {
+                    get_object_path(&store, hash)
+                };
+                fs::read(file_path).unwrap_or_default()
+            },
+        ),
+    })
+}
+
+/// Roll a skill back to an earlier version
+///
+/// The current state is snapshotted first, and the rollback itself is
+/// recorded as a new version, so a rollback can always be undone.
+#[tauri::command]
+pub fn rollback_skill(skill_id: String, version_id: String) -> Result<SkillVersion, String> {
+    let _guard = STORE_MUTEX.lock().map_err(|e| e.to_string())?;
+    let store = get_store_dir();
+    let skill_dir = expand_path(&skill_id);
+
+    if skill_dir
+        .symlink_metadata()
+        .map(|m| m.file_type().is_symlink())
+        .unwrap_or(false)
+    {
+        return Err("Symlinked skills cannot be rolled back".to_string());
+    }
+    if !skill_dir.is_dir() {
+        return Err(format!("Skill not found: {}", skill_id));
+    }
+
+    let skill_name = skill_name_of(&skill_dir)?;
+    let target = find_version(&read_history(&store, &skill_name)?, &version_id)?.clone();
+
+    snapshot_in_store(&store, &skill_dir, true, None)?;
+
+    // Replace the directory contents, keeping ignored entries such as .git
+    for entry in fs::read_dir(&skill_dir).map_err(|e| e.to_string())? {
+        let entry = entry.map_err(|e| e.to_string())?;
+        if IGNORED_NAMES.contains(&entry.file_name().to_string_lossy().as_ref()) {
+            continue;
+        }
+        let path = entry.path();
+        if entry.file_type().map(|t| t.is_dir()).unwrap_or(false) {
+            fs::remove_dir_all(&path).map_err(|e| e.to_string())?;
+        } else {
+            fs::remove_file(&path).map_err(|e| e.to_string())?;
+        }
+    }
+    materialize_version(&store, &target, &skill_dir)?;
+
+    let note = Some(format!("Rolled back to {}", target.id));
+    match snapshot_in_store(&store, &skill_dir, true, note)? {
+        Some(version) => Ok(version),
+        // Content already matched the latest version
+        None => read_history(&store, &skill_name)?
+            .versions
+            .pop()
+            .ok_or_else(|| "Version history is empty".to_string()),
+    }
+}
+
+/// Pin a project to a specific version of a skill (None unpins)
+///
+/// Pinned skills are deployed as copies on the next project sync.
+#[tauri::command]
+pub fn pin_skill_version(
+    project_id: String,
+    skill_id: String,
+    version_id: Option<String>,
+) -> Result<(), String> {
+    let _guard = DATA_MUTEX.lock().map_err(|e| e.to_string())?;
+    let skill_name = skill_name_of(&expand_path(&skill_id))?;
+
+    if let Some(id) = &version_id {
+        let history = read_history(&get_store_dir(), &skill_name)?;
+        find_version(&history, id)?;
+    }
+
+    let mut data = read_app_data()?;
+    let project = data
+        .projects
+        .iter_mut()
+        .find(|p| p.id == project_id)
+        .ok_or_else(|| format!("Project not found: {}", project_id))?;
+
+    match version_id {
+        Some(id) => {
+            project.pinned_skill_versions.insert(skill_name, id);
+        }
+        None => {
+            project.pinned_skill_versions.remove(&skill_name);
+        }
+    }
+
+    write_app_data(data)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn setup() -> (TempDir, PathBuf, PathBuf) {
+        let tmp = TempDir::new().unwrap();
+        let store = tmp.path().join("store");
+        let skill = tmp.path().join("skills").join("commit");
+        fs::create_dir_all(skill.join("scripts")).unwrap();
+        fs::write(skill.join("SKILL.md"), "# Commit\nv1\n").unwrap();
+        fs::write(skill.join("scripts").join("run.sh"), "echo hi\n").unwrap();
+        (tmp, store, skill)
+    }
+
+    #[test]
+    fn test_snapshot_records_only_changes() {
+        let (_tmp, store, skill) = setup();
+
+        let first = snapshot_in_store(&store, &skill, false, None).unwrap().unwrap();
+        assert_eq!(first.files.len(), 2);
+        assert_eq!(first.files[1].path, "scripts/run.sh");
+
+        // Unchanged content is not recorded again, even when forced
+        assert!(snapshot_in_store(&store, &skill, true, None).unwrap().is_none());
+
+        fs::write(skill.join("SKILL.md"), "# Commit\nv2\n").unwrap();
+        let second = snapshot_in_store(&store, &skill, true, None).unwrap().unwrap();
+        assert_ne!(first.content_hash, second.content_hash);
+        assert_eq!(read_history(&store, "commit").unwrap().versions.len(), 2);
+    }
+
+    #[test]
+    fn test_snapshot_ignores_deploy_stamp_and_git() {
+        let (_tmp, store, skill) = setup();
+        fs::write(skill.join(DEPLOY_STAMP_FILE), "{}").unwrap();
+        fs::create_dir_all(skill.join(".git")).unwrap();
+        fs::write(skill.join(".git").join("HEAD"), "ref").unwrap();
+
+        let version = snapshot_in_store(&store, &skill, true, None).unwrap().unwrap();
+        assert!(version.files.iter().all(|f| f.path != DEPLOY_STAMP_FILE && !f.path.starts_with(".git")));
+    }
+
+    #[test]
+    fn test_materialize_and_diff_versions() {
+        let (tmp, store, skill) = setup();
+        let v1 = snapshot_in_store(&store, &skill, true, None).unwrap().unwrap();
+        fs::write(skill.join("SKILL.md"), "# Commit\nv2\n").unwrap();
+        fs::remove_file(skill.join("scripts").join("run.sh")).unwrap();
+        let v2 = snapshot_in_store(&store, &skill, true, None).unwrap().unwrap();
+
+        let load = |_: &str, hash: &str| fs::read(get_object_path(&store, hash)).unwrap();
+        let diffs = diff_file_maps(&version_file_map(&v1), &version_file_map(&v2), load, load);
+        assert_eq!(diffs.len(), 2);
+        assert_eq!(diffs[0].path, "SKILL.md");
+        assert_eq!(diffs[0].status, "modified");
+        assert!(diffs[0].diff.as_deref().unwrap().contains("-v1\n+v2"));
+        assert_eq!(diffs[1].status, "removed");
+
+        let restored = tmp.path().join("restored");
+        materialize_version(&store, &v1, &restored).unwrap();
+        assert_eq!(fs::read_to_string(restored.join("SKILL.md")).unwrap(), "# Commit\nv1\n");
+        assert!(restored.join("scripts").join("run.sh").exists());
+    }
+}
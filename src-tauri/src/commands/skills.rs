@@ -1,3 +1,4 @@
+use crate::commands::skill_versions::snapshot_scanned_skills;
 use crate::types::{Skill, SkillMetadata};
 use crate::utils::{expand_path, get_data_file_path, parse_skill_md};
 use std::fs;
@@ -46,6 +47,9 @@ pub fn scan_skills(source_dir: String) -> Result<Vec<Skill>, String> {
         }
     }
 
+    // Record a version for every skill that changed since the last scan
+    snapshot_scanned_skills(&skills);
+
     Ok(skills)
 }
 
@@ -223,6 +227,7 @@ fn parse_skill_file(
         plugin_name,
         marketplace,
         plugin_enabled,
+        origin: metadata.and_then(|m| m.origin.clone()),
     };
 
     Ok(skill)
@@ -240,20 +245,11 @@ fn load_skill_metadata() -> std::collections::HashMap<String, SkillMetadata> {
     std::collections::HashMap::new()
 }
 
-/// Delete a skill by moving it to the trash directory
-///
-/// Instead of permanently deleting, moves the skill to ~/.ensemble/trash/skills/
-/// for easy recovery if needed.
-#[tauri::command]
-pub fn delete_skill(skill_id: String, ensemble_dir: String) -> Result<(), String> {
-    let ensemble_path = expand_path(&ensemble_dir);
-    let skill_path = std::path::Path::new(&skill_id);
-
-    // Verify the skill exists
-    if !skill_path.exists() {
-        return Err(format!("Skill not found: {}", skill_id));
-    }
-
+/// Move a skill directory to ~/.ensemble/trash/skills/, returning its new path
+pub(crate) fn move_skill_to_trash(
+    skill_path: &std::path::Path,
+    ensemble_path: &std::path::Path,
+) -> Result<std::path::PathBuf, String> {
     // Get skill name from path
     let skill_name = skill_path
         .file_name()
@@ -272,10 +268,29 @@ pub fn delete_skill(skill_id: String, ensemble_dir: String) -> Result<(), String
         dest_path = trash_dir.join(format!("{}_{}", skill_name, timestamp));
     }
 
-    // Move skill to trash
     fs::rename(skill_path, &dest_path)
         .map_err(|e| format!("Failed to move skill to trash: {}", e))?;
 
+    Ok(dest_path)
+}
+
+/// Delete a skill by moving it to the trash directory
+///
+/// Instead of permanently deleting, moves the skill to ~/.ensemble/trash/skills/
+/// for easy recovery if needed.
+#[tauri::command]
+pub fn delete_skill(skill_id: String, ensemble_dir: String) -> Result<(), String> {
+    let ensemble_path = expand_path(&ensemble_dir);
+    let skill_path = std::path::Path::new(&skill_id);
+
+    // Verify the skill exists
+    if !skill_path.exists() {
+        return Err(format!("Skill not found: {}", skill_id));
+    }
+
+    // Move skill to trash
+    move_skill_to_trash(skill_path, &ensemble_path)?;
+
     // Remove metadata for this skill
     let data_path = get_data_file_path();
     if data_path.exists() {
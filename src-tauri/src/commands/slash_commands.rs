@@ -0,0 +1,475 @@
+use crate::commands::data::{read_app_data, write_app_data, DATA_MUTEX};
+use crate::commands::deploy::DEPLOY_MODE_COPY;
+use crate::commands::file_items::{installed_at, is_markdown_file, move_file_to_trash, update_file_item_metadata};
+use crate::types::{CommandMetadata, SlashCommand};
+use crate::utils::{expand_path, is_symlink, is_symlink_into, parse_skill_md};
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use walkdir::WalkDir;
+
+/// Manifest of command copies deployed by Ensemble, kept in the project's
+/// .claude/commands directory (relative path -> content hash at deploy time)
+pub const COMMAND_MANIFEST_FILE: &str = ".ensemble-commands.json";
+
+type CommandManifest = BTreeMap<String, String>;
+
+/// Relative path of a command below its commands/ directory with `/`
+/// separators ("frontend/review.md")
+fn rel_string(rel: &Path) -> String {
+    rel.components()
+        .filter_map(|c| match c {
+            Component::Normal(part) => Some(part.to_string_lossy().to_string()),
+            _ => None,
+        })
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// Invocation name and namespace for a relative command path:
+/// "frontend/review.md" is `/frontend:review` in namespace "frontend"
+fn command_name(rel: &str) -> (String, Option<String>) {
+    let stem = rel.strip_suffix(".md").unwrap_or(rel);
+    let namespace = stem.rsplit_once('/').map(|(ns, _)| ns.to_string());
+    (stem.replace('/', ":"), namespace)
+}
+
+/// Path of a command file relative to its commands/ directory
+///
+/// Library commands are relative to `library`; others to the closest
+/// enclosing `commands` directory, falling back to the file name.
+fn command_rel_path(path: &Path, library: &Path) -> PathBuf {
+    if let Ok(rel) = path.strip_prefix(library) {
+        return rel.to_path_buf();
+    }
+    path.ancestors()
+        .skip(1)
+        .find(|dir| dir.file_name().map(|n| n == "commands").unwrap_or(false))
+        .and_then(|root| path.strip_prefix(root).ok())
+        .map(Path::to_path_buf)
+        .unwrap_or_else(|| PathBuf::from(path.file_name().unwrap_or_default()))
+}
+
+fn parse_command_file(
+    command_path: &Path,
+    rel: &str,
+    scope: &str,
+    metadata_map: &HashMap<String, CommandMetadata>,
+) -> Result<SlashCommand, String> {
+    let content = fs::read_to_string(command_path).map_err(|e| e.to_string())?;
+    let (frontmatter, instructions) = parse_skill_md(&content);
+
+    let id = command_path.to_string_lossy().to_string();
+    let metadata = metadata_map.get(&id);
+    let (name, namespace) = command_name(rel);
+
+    // Claude Code shows the first line of the prompt when there is no description
+    let description = frontmatter.description.unwrap_or_else(|| {
+        instructions
+            .lines()
+            .map(str::trim)
+            .find(|l| !l.is_empty())
+            .unwrap_or_default()
+            .to_string()
+    });
+
+    Ok(SlashCommand {
+        id: id.clone(),
+        name,
+        namespace,
+        description,
+        argument_hint: frontmatter.argument_hint,
+        allowed_tools: frontmatter.allowed_tools,
+        model: frontmatter.model,
+        category: metadata.map(|m| m.category.clone()).unwrap_or_default(),
+        tags: metadata.map(|m| m.tags.clone()).unwrap_or_default(),
+        enabled: metadata.map(|m| m.enabled).unwrap_or(true),
+        source_path: id,
+        scope: scope.to_string(),
+        instructions,
+        last_used: metadata.and_then(|m| m.last_used.clone()),
+        usage_count: metadata.map(|m| m.usage_count).unwrap_or(0),
+        icon: metadata.and_then(|m| m.icon.clone()),
+        installed_at: installed_at(command_path),
+    })
+}
+
+/// Relative paths of the command files below `dir`, including symlinks
+fn list_command_files(dir: &Path) -> Vec<String> {
+    WalkDir::new(dir)
+        .min_depth(1)
+        .into_iter()
+        .filter_entry(|e| e.depth() == 0 || !e.file_name().to_string_lossy().starts_with('.'))
+        .filter_map(|e| e.ok())
+        .filter(|e| !e.file_type().is_dir() && e.path().extension().and_then(|x| x.to_str()) == Some("md"))
+        .filter_map(|e| e.path().strip_prefix(dir).ok().map(rel_string))
+        .collect()
+}
+
+/// Parse every command file below `dir`, namespaced by subfolder; with
+/// `skip_managed`, commands deployed from the library (symlinks and
+/// tracked copies) are left out
+fn read_commands_dir(
+    dir: &Path,
+    scope: &str,
+    skip_managed: bool,
+    metadata_map: &HashMap<String, CommandMetadata>,
+) -> Vec<SlashCommand> {
+    let manifest = if skip_managed { read_manifest(dir) } else { CommandManifest::new() };
+    let mut commands: Vec<SlashCommand> = list_command_files(dir)
+        .into_iter()
+        .filter(|rel| !manifest.contains_key(rel))
+        .map(|rel| (dir.join(&rel), rel))
+        .filter(|(path, _)| is_markdown_file(path) && !(skip_managed && is_symlink(path)))
+        .filter_map(|(path, rel)| parse_command_file(&path, &rel, scope, metadata_map).ok())
+        .collect();
+    commands.sort_by(|a, b| a.name.cmp(&b.name));
+    commands
+}
+
+fn load_command_metadata() -> HashMap<String, CommandMetadata> {
+    read_app_data().map(|data| data.command_metadata).unwrap_or_default()
+}
+
+fn file_hash(path: &Path) -> Option<String> {
+    let content = fs::read(path).ok()?;
+    Some(Sha256::digest(&content).iter().map(|b| format!("{:02x}", b)).collect())
+}
+
+fn read_manifest(commands_dir: &Path) -> CommandManifest {
+    fs::read_to_string(commands_dir.join(COMMAND_MANIFEST_FILE))
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn write_manifest(commands_dir: &Path, manifest: &CommandManifest) -> Result<(), String> {
+    let path = commands_dir.join(COMMAND_MANIFEST_FILE);
+    if manifest.is_empty() {
+        if path.exists() {
+            fs::remove_file(&path).map_err(|e| e.to_string())?;
+        }
+        return Ok(());
+    }
+    let json = serde_json::to_string_pretty(manifest).map_err(|e| e.to_string())?;
+    fs::write(&path, json).map_err(|e| e.to_string())
+}
+
+/// Remove the namespace folders of removed commands once they are empty,
+/// deepest first; other folders under the commands directory are left alone
+fn remove_emptied_dirs(commands_dir: &Path, removed: &[String]) {
+    let mut dirs: Vec<&Path> = removed
+        .iter()
+        .flat_map(|rel| Path::new(rel).ancestors().skip(1))
+        .filter(|dir| !dir.as_os_str().is_empty())
+        .collect();
+    dirs.sort();
+    dirs.dedup();
+    dirs.sort_by_key(|dir| std::cmp::Reverse(dir.components().count()));
+    for dir in dirs {
+        // Fails for non-empty directories, which are kept
+        let _ = fs::remove_dir(commands_dir.join(dir));
+    }
+}
+
+fn link_command(source: &Path, target: &Path) -> Result<(), String> {
+    #[cfg(unix)]
+    std::os::unix::fs::symlink(source, target).map_err(|e| e.to_string())?;
+    #[cfg(windows)]
+    std::os::windows::fs::symlink_file(source, target).map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+/// Deploy library command files into a project's .claude/commands directory
+///
+/// Commands keep their namespace subfolders. In symlink mode they are
+/// linked to the library; in copy mode they are copied and recorded in
+/// the manifest. Links into `library` and unmodified copies no longer
+/// wanted are removed and unmodified copies are refreshed from the library;
+/// locally modified copies and the user's own files and links are never
+/// touched. Returns the deployed and the skipped (modified) relative paths.
+pub(crate) fn deploy_commands(
+    commands_dir: &Path,
+    command_paths: &[PathBuf],
+    library: &Path,
+    mode: &str,
+) -> Result<(Vec<String>, Vec<String>), String> {
+    let copy = mode == DEPLOY_MODE_COPY;
+    let wanted: BTreeMap<String, &PathBuf> = command_paths
+        .iter()
+        .map(|p| (rel_string(&command_rel_path(p, library)), p))
+        .collect();
+    let mut manifest = read_manifest(commands_dir);
+    let mut skipped = Vec::new();
+    let mut removed = Vec::new();
+
+    // Prune links and copies that are no longer wanted or use the other mode
+    for rel in list_command_files(commands_dir) {
+        let path = commands_dir.join(&rel);
+        if let Ok(current) = fs::read_link(&path) {
+            if is_symlink_into(&path, library) && (copy || Some(&current) != wanted.get(&rel).copied()) {
+                fs::remove_file(&path).map_err(|e| e.to_string())?;
+                removed.push(rel);
+            }
+            continue;
+        }
+        let Some(hash) = manifest.get(&rel) else {
+            continue;
+        };
+        if file_hash(&path).as_ref() != Some(hash) {
+            skipped.push(rel);
+        } else if !(copy && wanted.contains_key(&rel)) {
+            fs::remove_file(&path).map_err(|e| e.to_string())?;
+            manifest.remove(&rel);
+            removed.push(rel);
+        }
+    }
+
+    let mut deployed = Vec::new();
+    for (rel, source) in &wanted {
+        let target = commands_dir.join(rel);
+        if skipped.contains(rel) {
+            continue;
+        }
+        if !source.exists() {
+            return Err(format!("Command not found: {}", source.display()));
+        }
+        if !copy && fs::read_link(&target).ok().as_ref() == Some(*source) {
+            deployed.push(rel.clone());
+            continue;
+        }
+        let managed_copy = copy && manifest.contains_key(rel);
+        if target.exists() && !managed_copy {
+            log::warn!("Skipping command {}: {} is not managed by Ensemble", rel, target.display());
+            continue;
+        }
+
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+        }
+        if copy {
+            let source_hash = file_hash(source).ok_or_else(|| format!("Failed to read {}", source.display()))?;
+            if manifest.get(rel) != Some(&source_hash) {
+                fs::copy(source, &target).map_err(|e| format!("Failed to copy command {}: {}", rel, e))?;
+                manifest.insert(rel.clone(), source_hash);
+            }
+        } else {
+            link_command(source, &target)?;
+        }
+        deployed.push(rel.clone());
+    }
+
+    if commands_dir.exists() {
+        write_manifest(commands_dir, &manifest)?;
+        remove_emptied_dirs(commands_dir, &removed);
+    }
+    skipped.sort();
+    Ok((deployed, skipped))
+}
+
+/// Remove command links into `library` and unmodified copies from a
+/// .claude/commands directory
+pub(crate) fn remove_deployed_commands(commands_dir: &Path, library: &Path) -> Result<(), String> {
+    deploy_commands(commands_dir, &[], library, DEPLOY_MODE_COPY).map(|_| ())
+}
+
+/// Number of commands deployed by Ensemble in a .claude/commands directory
+pub(crate) fn count_deployed_commands(commands_dir: &Path, library: &Path) -> u32 {
+    let manifest = read_manifest(commands_dir);
+    list_command_files(commands_dir)
+        .iter()
+        .filter(|rel| manifest.contains_key(*rel) || is_symlink_into(&commands_dir.join(rel), library))
+        .count() as u32
+}
+
+// ============================================================================
+// Commands
+// ============================================================================
+
+/// Scan the command library directory, including namespace subfolders
+#[tauri::command]
+pub fn scan_commands(source_dir: String) -> Result<Vec<SlashCommand>, String> {
+    let path = expand_path(&source_dir);
+    Ok(read_commands_dir(&path, "user", false, &load_command_metadata()))
+}
+
+/// Get a single command by ID
+#[tauri::command]
+pub fn get_command(source_dir: String, command_id: String) -> Result<Option<SlashCommand>, String> {
+    let commands = scan_commands(source_dir)?;
+    Ok(commands.into_iter().find(|c| c.id == command_id))
+}
+
+/// Update command metadata (category, tags, enabled status, icon)
+#[tauri::command]
+pub fn update_command_metadata(
+    command_id: String,
+    category: Option<String>,
+    tags: Option<Vec<String>>,
+    enabled: Option<bool>,
+    icon: Option<String>,
+) -> Result<(), String> {
+    update_file_item_metadata(|data| &mut data.command_metadata, command_id, category, tags, enabled, icon)
+}
+
+/// Find slash commands defined outside the library, for import
+///
+/// Lists ~/.claude/commands ("user" scope) and, when given, the project's
+/// .claude/commands ("project" scope). Commands deployed from the library
+/// are left out.
+#[tauri::command]
+pub fn detect_claude_commands(
+    claude_config_dir: String,
+    project_path: Option<String>,
+) -> Result<Vec<SlashCommand>, String> {
+    let metadata = HashMap::new();
+    let mut commands = read_commands_dir(&expand_path(&claude_config_dir).join("commands"), "user", true, &metadata);
+    if let Some(project) = project_path {
+        let dir = expand_path(&project).join(".claude").join("commands");
+        commands.extend(read_commands_dir(&dir, "project", true, &metadata));
+    }
+    Ok(commands)
+}
+
+/// Copy command files into the library, keeping their namespace subfolder
+///
+/// Commands whose path is already in the library are skipped. Returns the
+/// number of commands imported.
+#[tauri::command]
+pub fn import_commands(command_paths: Vec<String>, source_dir: String) -> Result<u32, String> {
+    let library = expand_path(&source_dir);
+    fs::create_dir_all(&library).map_err(|e| format!("Failed to create commands directory: {}", e))?;
+
+    let mut imported = 0;
+    for command_path in command_paths {
+        let source = expand_path(&command_path);
+        if !is_markdown_file(&source) {
+            return Err(format!("Not a command file: {}", command_path));
+        }
+        let dest = library.join(command_rel_path(&source, &library));
+        if dest.exists() {
+            log::warn!("Command {} already exists in the library, skipping", dest.display());
+            continue;
+        }
+        if let Some(parent) = dest.parent() {
+            fs::create_dir_all(parent).map_err(|e| format!("Failed to create namespace directory: {}", e))?;
+        }
+        fs::copy(&source, &dest).map_err(|e| format!("Failed to import command {}: {}", command_path, e))?;
+        imported += 1;
+    }
+    Ok(imported)
+}
+
+/// Delete a command by moving it to the trash directory
+///
+/// The file is moved to {ensemble}/trash/commands/ so it can be restored;
+/// its metadata and Scene memberships are removed.
+#[tauri::command]
+pub fn delete_command(command_id: String, ensemble_dir: String) -> Result<(), String> {
+    let _guard = DATA_MUTEX.lock().map_err(|e| e.to_string())?;
+    let command_path = Path::new(&command_id);
+    if !command_path.exists() {
+        return Err(format!("Command not found: {}", command_id));
+    }
+
+    // Keep the namespace folder in the trash
+    let ensemble_path = expand_path(&ensemble_dir);
+    let rel = command_rel_path(command_path, &ensemble_path.join("commands"));
+    let trash_dir = ensemble_path
+        .join("trash")
+        .join("commands")
+        .join(rel.parent().unwrap_or(Path::new("")));
+    move_file_to_trash(command_path, &trash_dir)?;
+
+    let mut app_data = read_app_data()?;
+    app_data.command_metadata.remove(&command_id);
+    for scene in app_data.scenes.iter_mut() {
+        scene.command_ids.retain(|id| *id != command_id);
+    }
+    write_app_data(app_data)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_read_commands_dir_namespaces_subfolders() {
+        let tmp = TempDir::new().unwrap();
+        fs::create_dir_all(tmp.path().join("frontend")).unwrap();
+        fs::write(
+            tmp.path().join("frontend/review.md"),
+            "---\ndescription: Review a component\nargument-hint: [file]\nallowed-tools: Read, Grep\n---\nReview $ARGUMENTS.",
+        )
+        .unwrap();
+        fs::write(tmp.path().join("commit.md"), "\nWrite a commit message.\n").unwrap();
+        fs::write(tmp.path().join("notes.txt"), "ignored").unwrap();
+
+        let commands = read_commands_dir(tmp.path(), "user", false, &HashMap::new());
+        assert_eq!(commands.len(), 2);
+        assert_eq!(commands[0].name, "commit");
+        assert_eq!(commands[0].description, "Write a commit message.");
+        let review = &commands[1];
+        assert_eq!(review.name, "frontend:review");
+        assert_eq!(review.namespace.as_deref(), Some("frontend"));
+        assert_eq!(review.argument_hint.as_deref(), Some("[file]"));
+        assert_eq!(review.allowed_tools, Some(vec!["Read".to_string(), "Grep".to_string()]));
+
+        assert_eq!(
+            command_rel_path(Path::new("/home/me/.claude/commands/ops/deploy.md"), Path::new("/lib/commands")),
+            PathBuf::from("ops/deploy.md")
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_deploy_commands_symlink_and_copy_modes() {
+        let tmp = TempDir::new().unwrap();
+        let library = tmp.path().join("library/commands");
+        let commands_dir = tmp.path().join("project/.claude/commands");
+        fs::create_dir_all(library.join("git")).unwrap();
+        fs::write(library.join("git/commit.md"), "Commit").unwrap();
+        fs::write(library.join("test.md"), "Test").unwrap();
+        fs::create_dir_all(commands_dir.join("drafts")).unwrap();
+        fs::write(commands_dir.join("mine.md"), "user command").unwrap();
+        let dotfiles = tmp.path().join("dotfiles/shared.md");
+        fs::create_dir_all(dotfiles.parent().unwrap()).unwrap();
+        fs::write(&dotfiles, "dotfiles command").unwrap();
+        std::os::unix::fs::symlink(&dotfiles, commands_dir.join("shared.md")).unwrap();
+
+        let both = [library.join("git/commit.md"), library.join("test.md")];
+        let (deployed, _) = deploy_commands(&commands_dir, &both, &library, "symlink").unwrap();
+        assert_eq!(deployed, vec!["git/commit.md", "test.md"]);
+        assert_eq!(fs::read_link(commands_dir.join("git/commit.md")).unwrap(), library.join("git/commit.md"));
+        assert_eq!(count_deployed_commands(&commands_dir, &library), 2);
+
+        // Switching to copies replaces the links
+        deploy_commands(&commands_dir, &both, &library, DEPLOY_MODE_COPY).unwrap();
+        assert!(!is_symlink(&commands_dir.join("test.md")));
+        assert_eq!(fs::read_to_string(commands_dir.join("git/commit.md")).unwrap(), "Commit");
+
+        // Library edits refresh unmodified copies; local edits are kept
+        fs::write(library.join("test.md"), "Test v2").unwrap();
+        fs::write(commands_dir.join("git/commit.md"), "My commit").unwrap();
+        let (_, skipped) = deploy_commands(&commands_dir, &both, &library, DEPLOY_MODE_COPY).unwrap();
+        assert_eq!(skipped, vec!["git/commit.md"]);
+        assert_eq!(fs::read_to_string(commands_dir.join("test.md")).unwrap(), "Test v2");
+        assert_eq!(fs::read_to_string(commands_dir.join("git/commit.md")).unwrap(), "My commit");
+
+        remove_deployed_commands(&commands_dir, &library).unwrap();
+        assert!(!commands_dir.join("test.md").exists());
+        assert!(commands_dir.join("git/commit.md").exists());
+        assert!(commands_dir.join("mine.md").exists());
+        assert!(is_symlink(&commands_dir.join("shared.md")));
+        assert!(commands_dir.join("drafts").is_dir());
+        assert_eq!(count_deployed_commands(&commands_dir, &library), 1);
+
+        // Namespace folders emptied by pruning are removed
+        fs::write(commands_dir.join("git/commit.md"), "Commit").unwrap();
+        remove_deployed_commands(&commands_dir, &library).unwrap();
+        assert!(!commands_dir.join("git").exists());
+        assert!(commands_dir.join("drafts").is_dir());
+    }
+}
@@ -1,10 +1,15 @@
+use crate::commands::claude_md::get_claude_md_storage_dir;
 use crate::commands::data::{read_app_data, write_app_data, DATA_MUTEX};
-use crate::types::{ClaudeMdFile, McpConfigFile, TrashedClaudeMd, TrashedItems, TrashedMcp, TrashedSkill};
-use crate::utils::{expand_path, get_app_data_dir, parse_skill_md};
+use crate::types::{
+    ClaudeMdFile, McpConfigFile, TrashedAgent, TrashedClaudeMd, TrashedCommand, TrashedItems, TrashedMcp,
+    TrashedSkill,
+};
+use crate::utils::{expand_path, parse_skill_frontmatter, parse_skill_md};
 use chrono::{DateTime, NaiveDateTime, Utc};
 use regex::Regex;
 use std::fs;
 use std::path::Path;
+use walkdir::WalkDir;
 
 // ============================================================================
 // Helper functions
@@ -101,7 +106,7 @@ fn read_claude_md_info(claude_md_dir: &Path) -> Option<String> {
 // Trash commands
 // ============================================================================
 
-/// List all trashed items (skills, MCPs, and CLAUDE.md files)
+/// List all trashed items (skills, MCPs, agents, slash commands and CLAUDE.md files)
 ///
 /// Scans the trash directories and returns information about deleted items.
 #[tauri::command]
@@ -242,8 +247,99 @@ pub fn list_trashed_items(ensemble_dir: String) -> Result<TrashedItems, String>
         }
     }
 
+    // Scan trashed agents
+    let mut agents: Vec<TrashedAgent> = Vec::new();
+    let agents_trash_dir = ensemble_path.join("trash").join("agents");
+    if let Ok(entries) = fs::read_dir(&agents_trash_dir) {
+        for entry in entries.filter_map(|e| e.ok()) {
+            let entry_path = entry.path();
+
+            // Only process .md files
+            if entry_path.extension().map_or(true, |ext| ext != "md") {
+                continue;
+            }
+
+            let Some(file_stem) = entry_path.file_stem().and_then(|n| n.to_str()) else {
+                continue;
+            };
+            if file_stem.starts_with('.') {
+                continue;
+            }
+
+            let (original_name, deleted_at) = parse_timestamp_from_name(file_stem, false);
+            let deleted_at = deleted_at
+                .or_else(|| get_file_modified_time(&entry_path))
+                .unwrap_or_else(|| Utc::now().to_rfc3339());
+
+            let description = fs::read_to_string(&entry_path)
+                .ok()
+                .and_then(|content| parse_skill_frontmatter(&content))
+                .and_then(|fm| fm.description)
+                .unwrap_or_default();
+
+            agents.push(TrashedAgent {
+                id: format!("{}.md", file_stem),
+                name: original_name,
+                path: entry_path.to_string_lossy().to_string(),
+                deleted_at,
+                description,
+            });
+        }
+    }
+
+    // Scan trashed slash commands (namespace subfolders are kept in trash)
+    let mut commands: Vec<TrashedCommand> = Vec::new();
+    let commands_trash_dir = ensemble_path.join("trash").join("commands");
+    for entry in WalkDir::new(&commands_trash_dir).min_depth(1).into_iter().filter_map(|e| e.ok()) {
+        let entry_path = entry.path();
+
+        // Only process .md files
+        if !entry.file_type().is_file() || entry_path.extension().map_or(true, |ext| ext != "md") {
+            continue;
+        }
+
+        let Some(file_stem) = entry_path.file_stem().and_then(|n| n.to_str()) else {
+            continue;
+        };
+        if file_stem.starts_with('.') {
+            continue;
+        }
+
+        let (original_name, deleted_at) = parse_timestamp_from_name(file_stem, false);
+        let deleted_at = deleted_at
+            .or_else(|| get_file_modified_time(entry_path))
+            .unwrap_or_else(|| Utc::now().to_rfc3339());
+
+        let description = fs::read_to_string(entry_path)
+            .ok()
+            .and_then(|content| parse_skill_frontmatter(&content))
+            .and_then(|fm| fm.description)
+            .unwrap_or_default();
+
+        let rel = entry_path.strip_prefix(&commands_trash_dir).unwrap_or(entry_path);
+        let namespace: Vec<String> = rel
+            .parent()
+            .map(|p| p.iter().map(|c| c.to_string_lossy().to_string()).collect())
+            .unwrap_or_default();
+        let name = namespace
+            .into_iter()
+            .chain(std::iter::once(original_name))
+            .collect::<Vec<_>>()
+            .join(":");
+
+        commands.push(TrashedCommand {
+            id: rel.to_string_lossy().to_string(),
+            name,
+            path: entry_path.to_string_lossy().to_string(),
+            deleted_at,
+            description,
+        });
+    }
+
     // Sort by deleted_at (newest first)
     skills.sort_by(|a, b| b.deleted_at.cmp(&a.deleted_at));
+    agents.sort_by(|a, b| b.deleted_at.cmp(&a.deleted_at));
+    commands.sort_by(|a, b| b.deleted_at.cmp(&a.deleted_at));
     mcps.sort_by(|a, b| b.deleted_at.cmp(&a.deleted_at));
     claude_md_files.sort_by(|a, b| b.deleted_at.cmp(&a.deleted_at));
 
@@ -251,6 +347,8 @@ pub fn list_trashed_items(ensemble_dir: String) -> Result<TrashedItems, String>
         skills,
         mcps,
         claude_md_files,
+        agents,
+        commands,
     })
 }
 
@@ -332,6 +430,75 @@ pub fn restore_mcp(trash_path: String, ensemble_dir: String) -> Result<(), Strin
     Ok(())
 }
 
+/// Restore an agent from trash
+///
+/// Moves the agent file from trash back to the agents directory.
+/// Returns error if an agent with the same file name already exists.
+#[tauri::command]
+pub fn restore_agent(trash_path: String, ensemble_dir: String) -> Result<(), String> {
+    let trash_path = expand_path(&trash_path);
+    let ensemble_path = expand_path(&ensemble_dir);
+
+    if !trash_path.exists() {
+        return Err(format!("Trash path not found: {}", trash_path.display()));
+    }
+
+    let file_stem = trash_path.file_stem()
+        .and_then(|n| n.to_str())
+        .ok_or("Invalid trash path")?;
+    let (original_name, _) = parse_timestamp_from_name(file_stem, false);
+
+    let agents_dir = ensemble_path.join("agents");
+    let target_path = agents_dir.join(format!("{}.md", original_name));
+    if target_path.exists() {
+        return Err("An agent with the same name already exists".to_string());
+    }
+
+    fs::create_dir_all(&agents_dir)
+        .map_err(|e| format!("Failed to create agents directory: {}", e))?;
+    fs::rename(&trash_path, &target_path)
+        .map_err(|e| format!("Failed to restore agent: {}", e))?;
+
+    Ok(())
+}
+
+/// Restore a slash command from trash
+///
+/// Moves the command file from trash back to its namespace folder in the
+/// commands directory. Returns error if a command with the same name
+/// already exists.
+#[tauri::command]
+pub fn restore_command(trash_path: String, ensemble_dir: String) -> Result<(), String> {
+    let trash_path = expand_path(&trash_path);
+    let ensemble_path = expand_path(&ensemble_dir);
+
+    if !trash_path.exists() {
+        return Err(format!("Trash path not found: {}", trash_path.display()));
+    }
+
+    let file_stem = trash_path.file_stem()
+        .and_then(|n| n.to_str())
+        .ok_or("Invalid trash path")?;
+    let (original_name, _) = parse_timestamp_from_name(file_stem, false);
+
+    let namespace = trash_path
+        .parent()
+        .and_then(|p| p.strip_prefix(ensemble_path.join("trash").join("commands")).ok())
+        .unwrap_or(Path::new(""));
+    let commands_dir = ensemble_path.join("commands").join(namespace);
+    let target_path = commands_dir.join(format!("{}.md", original_name));
+    if target_path.exists() {
+        return Err("A command with the same name already exists".to_string());
+    }
+
+    fs::create_dir_all(&commands_dir)
+        .map_err(|e| format!("Failed to create commands directory: {}", e))?;
+    fs::rename(&trash_path, &target_path)
+        .map_err(|e| format!("Failed to restore command: {}", e))?;
+
+    Ok(())
+}
+
 /// Restore a CLAUDE.md file from trash
 ///
 /// Moves the CLAUDE.md directory from trash back to the claude-md directory
@@ -354,7 +521,7 @@ pub fn restore_claude_md(trash_path: String) -> Result<(), String> {
     let (original_id, _) = parse_timestamp_from_name(dir_name, false);
 
     // Target path in claude-md directory
-    let claude_md_dir = get_app_data_dir().join("claude-md");
+    let claude_md_dir = get_claude_md_storage_dir();
     let target_path = claude_md_dir.join(&original_id);
 
     // Check if claude-md with same ID already exists
@@ -404,6 +571,7 @@ pub fn restore_claude_md(trash_path: String) -> Result<(), String> {
                 updated_at: chrono::Utc::now().to_rfc3339(),
                 size: file_info.size,
                 icon: file_info.icon,
+                imports: file_info.imports,
             };
 
             app_data.claude_md_files.push(restored_file);
@@ -439,6 +607,7 @@ pub fn restore_claude_md(trash_path: String) -> Result<(), String> {
                 updated_at: now,
                 size,
                 icon: None,
+                imports: Vec::new(),
             };
 
             app_data.claude_md_files.push(restored_file);
@@ -1,30 +1,947 @@
+use chrono::{Datelike, NaiveDate};
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use std::collections::HashMap;
 use std::fs::{self, File};
-use std::io::{BufRead, BufReader};
-use std::path::Path;
+use std::io::{BufRead, BufReader, Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
 
-use crate::utils::expand_path;
+use crate::utils::{expand_path, find_repo_root, get_app_data_dir, parse_skill_frontmatter};
 
 /// Usage statistics for a skill
-#[derive(Serialize, Default, Clone, Debug)]
+#[derive(Serialize, Deserialize, Default, Clone, Debug)]
 pub struct SkillUsage {
     pub call_count: u32,
     pub last_used: Option<String>,
 }
 
 /// Usage statistics for an MCP server
-#[derive(Serialize, Default, Clone, Debug)]
+#[derive(Serialize, Deserialize, Default, Clone, Debug)]
 pub struct McpUsage {
     pub total_calls: u32,
     pub last_used: Option<String>,
+    /// Calls per tool of the server
+    #[serde(default)]
+    pub tools: HashMap<String, u32>,
+}
+
+/// Usage statistics for a slash command, subagent or hook
+#[derive(Serialize, Deserialize, Default, Clone, Debug)]
+pub struct InvocationUsage {
+    pub call_count: u32,
+    pub last_used: Option<String>,
 }
 
 /// Aggregated usage statistics
-#[derive(Serialize, Default, Debug)]
+#[derive(Serialize, Deserialize, Default, Clone, Debug)]
 pub struct UsageStats {
     pub skills: HashMap<String, SkillUsage>,
     pub mcps: HashMap<String, McpUsage>,
+    /// Slash commands by name (without the leading /)
+    #[serde(default)]
+    pub commands: HashMap<String, InvocationUsage>,
+    /// Task subagents by subagent type
+    #[serde(default)]
+    pub agents: HashMap<String, InvocationUsage>,
+    /// Hooks by "Event:matcher" name
+    #[serde(default)]
+    pub hooks: HashMap<String, InvocationUsage>,
+}
+
+impl UsageStats {
+    /// Add the counters of `other` to these statistics
+    fn merge(&mut self, other: &UsageStats) {
+        for (name, usage) in &other.skills {
+            let total = self.skills.entry(name.clone()).or_default();
+            total.call_count += usage.call_count;
+            if let Some(ts) = &usage.last_used {
+                update_last_used(&mut total.last_used, ts.clone());
+            }
+        }
+        for (name, usage) in &other.mcps {
+            let total = self.mcps.entry(name.clone()).or_default();
+            total.total_calls += usage.total_calls;
+            if let Some(ts) = &usage.last_used {
+                update_last_used(&mut total.last_used, ts.clone());
+            }
+            for (tool, calls) in &usage.tools {
+                *total.tools.entry(tool.clone()).or_default() += calls;
+            }
+        }
+        for (totals, others) in [
+            (&mut self.commands, &other.commands),
+            (&mut self.agents, &other.agents),
+            (&mut self.hooks, &other.hooks),
+        ] {
+            for (name, usage) in others {
+                let total = totals.entry(name.clone()).or_default();
+                total.call_count += usage.call_count;
+                if let Some(ts) = &usage.last_used {
+                    update_last_used(&mut total.last_used, ts.clone());
+                }
+            }
+        }
+    }
+}
+
+/// Calls of one skill, MCP server or MCP tool on one day
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+struct UsageBucket {
+    /// UTC date (YYYY-MM-DD)
+    date: String,
+    /// "skill" | "mcp" | "command" | "agent" | "hook"
+    kind: String,
+    /// Skill, MCP server, command, subagent type or hook name
+    name: String,
+    /// MCP tool name
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    tool: Option<String>,
+    count: u32,
+}
+
+/// Usage query over the indexed transcripts
+#[derive(Deserialize, Default, Debug)]
+pub struct UsageQuery {
+    /// "skill" | "mcp" | "command" | "agent" | "hook" (None = all)
+    pub kind: Option<String>,
+    /// Only this skill, MCP server, command, subagent type or hook
+    pub name: Option<String>,
+    /// Only this MCP tool
+    pub tool: Option<String>,
+    /// First day of the window, inclusive (YYYY-MM-DD)
+    pub since: Option<String>,
+    /// Last day of the window, inclusive (YYYY-MM-DD)
+    pub until: Option<String>,
+    /// "day" | "week" (default: day)
+    pub granularity: Option<String>,
+    /// Length of the top-N lists (default: 10)
+    pub top: Option<usize>,
+}
+
+/// Calls in one period of a usage histogram
+#[derive(Serialize, Clone, Debug, PartialEq)]
+pub struct UsagePeriod {
+    /// Day (YYYY-MM-DD) or ISO week (YYYY-Www)
+    pub period: String,
+    pub count: u32,
+}
+
+/// An entry of a top-N list
+#[derive(Serialize, Clone, Debug, PartialEq)]
+pub struct UsageRank {
+    pub name: String,
+    pub count: u32,
+}
+
+/// Result of a usage query
+#[derive(Serialize, Default, Debug)]
+pub struct UsageTimeline {
+    pub total: u32,
+    /// Calls per period over the window, including empty periods
+    pub histogram: Vec<UsagePeriod>,
+    /// Skills ("skill:name") and MCP servers ("mcp:server")
+    pub top_items: Vec<UsageRank>,
+    /// MCP tools ("server/tool")
+    pub top_tools: Vec<UsageRank>,
+    /// Project directories the sessions ran in
+    pub top_projects: Vec<UsageRank>,
+    pub top_sessions: Vec<UsageRank>,
+}
+
+/// A failed MCP tool call
+#[derive(Serialize, Clone, Debug, PartialEq)]
+pub struct McpToolError {
+    pub tool: Option<String>,
+    pub timestamp: Option<String>,
+    pub message: String,
+}
+
+/// Error rate and latency of one MCP tool
+#[derive(Serialize, Default, Clone, Debug, PartialEq)]
+pub struct McpToolHealth {
+    pub tool: String,
+    /// Calls with a result
+    pub calls: u32,
+    pub errors: u32,
+    pub error_rate: f64,
+    pub median_latency_ms: Option<u64>,
+    pub p90_latency_ms: Option<u64>,
+}
+
+/// Error rate and latency of an MCP server, overall and per tool
+#[derive(Serialize, Default, Clone, Debug, PartialEq)]
+pub struct McpHealth {
+    pub server: String,
+    /// Calls with a result
+    pub calls: u32,
+    pub errors: u32,
+    pub error_rate: f64,
+    pub median_latency_ms: Option<u64>,
+    pub p90_latency_ms: Option<u64>,
+    /// Tools, most errors first
+    pub tools: Vec<McpToolHealth>,
+    /// Most recent errors, newest first
+    pub recent_errors: Vec<McpToolError>,
+}
+
+/// A slash command, subagent or hook with its usage and definition files
+#[derive(Serialize, Clone, Debug, PartialEq)]
+pub struct InvocationSummary {
+    /// "command" | "agent" | "hook"
+    pub kind: String,
+    pub name: String,
+    pub call_count: u32,
+    pub last_used: Option<String>,
+    /// Files under .claude/commands or .claude/agents defining it; empty for
+    /// built-ins and hooks
+    pub files: Vec<String>,
+}
+
+// ============================================================================
+// Persistent Index
+// ============================================================================
+
+/// Bump when the data kept per transcript changes, forcing a full rebuild
+const USAGE_INDEX_VERSION: u32 = 6;
+
+/// Bytes at the start of a transcript hashed to notice a replaced file
+const HEAD_BYTES: u64 = 1024;
+
+/// Transcripts not written to for this long are finished; MCP calls still
+/// waiting for a result in them never get one
+const FINISHED_AFTER: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Per-transcript scan state, so rescans only parse appended bytes
+#[derive(Serialize, Deserialize, Default, Debug)]
+struct UsageIndex {
+    version: u32,
+    /// Claude directory the index was built from
+    claude_dir: String,
+    /// Transcript path -> indexed state
+    files: HashMap<String, IndexedTranscript>,
+}
+
+/// Indexed state of one transcript file
+#[derive(Serialize, Deserialize, Default, Clone, Debug)]
+struct IndexedTranscript {
+    size: u64,
+    modified_at: String,
+    /// Bytes parsed so far, always at a line boundary
+    offset: u64,
+    /// Hash of the first parsed bytes (up to `HEAD_BYTES`)
+    head_hash: String,
+    /// Usage counted from the parsed bytes
+    stats: UsageStats,
+    /// Session the transcript belongs to
+    #[serde(default)]
+    session_id: Option<String>,
+    /// Working directory the session ran in
+    #[serde(default)]
+    project_dir: Option<String>,
+    /// Calls per day and item
+    #[serde(default)]
+    daily: Vec<UsageBucket>,
+    /// MCP calls still waiting for their result, by tool_use id
+    #[serde(default)]
+    pending_calls: HashMap<String, PendingMcpCall>,
+    /// Completed MCP calls per day and tool
+    #[serde(default)]
+    mcp_calls: Vec<McpCallTally>,
+    /// Most recent failed MCP calls, at most `RECENT_ERRORS` per server,
+    /// oldest first
+    #[serde(default)]
+    mcp_errors: Vec<McpCallError>,
+    /// Folder under projects/ the transcript is in
+    #[serde(default)]
+    project_folder: Option<String>,
+    /// Tokens per day and model
+    #[serde(default)]
+    tokens: Vec<TokenBucket>,
+    /// Last assistant message counted, as its content blocks are written as
+    /// separate lines repeating the message usage
+    #[serde(default)]
+    last_message: Option<(String, TokenCounts)>,
+}
+
+/// Token counts of assistant messages
+#[derive(Serialize, Deserialize, Default, Clone, Copy, Debug, PartialEq)]
+pub(crate) struct TokenCounts {
+    pub(crate) input: u64,
+    pub(crate) output: u64,
+    pub(crate) cache_creation: u64,
+    pub(crate) cache_read: u64,
+}
+
+impl TokenCounts {
+    fn add(&mut self, other: &TokenCounts) {
+        self.input += other.input;
+        self.output += other.output;
+        self.cache_creation += other.cache_creation;
+        self.cache_read += other.cache_read;
+    }
+
+    /// Growth from `previous` to these counts
+    fn since(&self, previous: &TokenCounts) -> TokenCounts {
+        TokenCounts {
+            input: self.input.saturating_sub(previous.input),
+            output: self.output.saturating_sub(previous.output),
+            cache_creation: self.cache_creation.saturating_sub(previous.cache_creation),
+            cache_read: self.cache_read.saturating_sub(previous.cache_read),
+        }
+    }
+}
+
+/// Tokens of one model on one day
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+struct TokenBucket {
+    /// UTC date (YYYY-MM-DD)
+    date: String,
+    model: String,
+    tokens: TokenCounts,
+}
+
+/// Tokens of one model in one project directory
+#[derive(Debug, PartialEq)]
+pub(crate) struct ProjectTokens {
+    /// Working directory of the sessions (falls back to the projects/ folder name)
+    pub(crate) project_dir: String,
+    /// Folder under projects/ the transcripts are in
+    pub(crate) project_folder: Option<String>,
+    pub(crate) model: String,
+    pub(crate) tokens: TokenCounts,
+}
+
+/// An MCP tool_use whose tool_result has not been seen yet
+#[derive(Serialize, Deserialize, Clone, Debug)]
+struct PendingMcpCall {
+    server: String,
+    tool: Option<String>,
+    started_at: Option<String>,
+}
+
+/// Completed calls of one MCP tool on one day
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+struct McpCallTally {
+    /// UTC date (YYYY-MM-DD) of the results, empty when unknown
+    date: String,
+    server: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    tool: Option<String>,
+    #[serde(flatten)]
+    health: HealthTally,
+}
+
+/// An MCP tool_result flagged `is_error`
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+struct McpCallError {
+    server: String,
+    tool: Option<String>,
+    /// Timestamp of the result
+    timestamp: Option<String>,
+    message: String,
+}
+
+/// Get the usage index path (~/.ensemble/usage-index.json)
+fn get_usage_index_path() -> PathBuf {
+    get_app_data_dir().join("usage-index.json")
+}
+
+fn read_usage_index() -> UsageIndex {
+    fs::read_to_string(get_usage_index_path())
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn write_usage_index(index: &UsageIndex) {
+    if let Ok(json) = serde_json::to_string(index) {
+        if let Err(e) = fs::write(get_usage_index_path(), json) {
+            log::warn!("Failed to write usage index: {}", e);
+        }
+    }
+}
+
+/// Hash of the first `min(len, HEAD_BYTES)` bytes of a file
+fn head_hash(path: &Path, len: u64) -> Result<String, String> {
+    let file = File::open(path).map_err(|e| format!("Failed to open file: {}", e))?;
+    let mut head = Vec::new();
+    file.take(len.min(HEAD_BYTES))
+        .read_to_end(&mut head)
+        .map_err(|e| e.to_string())?;
+    Ok(Sha256::digest(&head).iter().map(|b| format!("{:02x}", b)).collect())
+}
+
+/// Bring the indexed state of a transcript up to date
+///
+/// Unchanged files are returned as they are, appended bytes are parsed from
+/// the previous offset, and truncated or replaced files are parsed again
+/// from the start. A trailing line without newline is left for the next scan
+/// unless it is already complete JSON.
+fn index_transcript(path: &Path, previous: Option<IndexedTranscript>) -> Result<IndexedTranscript, String> {
+    let metadata = fs::metadata(path).map_err(|e| e.to_string())?;
+    let size = metadata.len();
+    let modified_at = metadata
+        .modified()
+        .ok()
+        .map(|t| chrono::DateTime::<chrono::Utc>::from(t).to_rfc3339())
+        .unwrap_or_default();
+
+    let finished = metadata
+        .modified()
+        .ok()
+        .and_then(|t| t.elapsed().ok())
+        .is_some_and(|age| age >= FINISHED_AFTER);
+
+    let mut entry = match previous {
+        Some(mut prev) if prev.size == size && prev.modified_at == modified_at => {
+            if finished {
+                prev.pending_calls.clear();
+            }
+            return Ok(prev);
+        }
+        Some(prev) if size >= prev.offset && head_hash(path, prev.offset)? == prev.head_hash => prev,
+        _ => IndexedTranscript::default(),
+    };
+
+    let mut file = File::open(path).map_err(|e| format!("Failed to open file: {}", e))?;
+    file.seek(SeekFrom::Start(entry.offset)).map_err(|e| e.to_string())?;
+    let mut reader = BufReader::new(file);
+    let mut buf = Vec::new();
+    loop {
+        buf.clear();
+        let read = reader.read_until(b'\n', &mut buf).map_err(|e| e.to_string())?;
+        if read == 0 {
+            break;
+        }
+        let line = String::from_utf8_lossy(&buf);
+        if !buf.ends_with(b"\n") && serde_json::from_str::<serde_json::Value>(&line).is_err() {
+            // Still being written
+            break;
+        }
+        process_line(&line, &mut entry);
+        entry.offset += read as u64;
+    }
+
+    if entry.session_id.is_none() {
+        entry.session_id = path.file_stem().map(|s| s.to_string_lossy().to_string());
+    }
+    if finished {
+        entry.pending_calls.clear();
+    }
+    entry.size = size;
+    entry.modified_at = modified_at;
+    entry.head_hash = head_hash(path, entry.offset)?;
+    Ok(entry)
+}
+
+/// Update `index` from the transcripts under `claude_path` and return the
+/// aggregated statistics
+///
+/// The index is rebuilt when it was written by another version or for
+/// another Claude directory; transcripts that no longer exist are dropped.
+fn update_index(claude_path: &Path, index: &mut UsageIndex) -> Result<UsageStats, String> {
+    let claude_dir = claude_path.to_string_lossy().to_string();
+    if index.version != USAGE_INDEX_VERSION || index.claude_dir != claude_dir {
+        *index = UsageIndex {
+            version: USAGE_INDEX_VERSION,
+            claude_dir,
+            files: HashMap::new(),
+        };
+    }
+
+    let mut files = HashMap::new();
+    for path in list_transcript_files(claude_path)? {
+        let key = path.to_string_lossy().to_string();
+        let previous = index.files.remove(&key);
+        match index_transcript(&path, previous) {
+            Ok(mut entry) => {
+                entry.project_folder = project_folder_of(claude_path, &path);
+                files.insert(key, entry);
+            }
+            Err(e) => {
+                log::warn!("Failed to process transcript file {:?}: {}", path, e);
+            }
+        }
+    }
+    index.files = files;
+
+    let mut stats = UsageStats::default();
+    for entry in index.files.values() {
+        stats.merge(&entry.stats);
+    }
+    Ok(stats)
+}
+
+/// Name of the projects/ folder a transcript is in
+fn project_folder_of(claude_path: &Path, transcript: &Path) -> Option<String> {
+    transcript
+        .strip_prefix(claude_path.join("projects"))
+        .ok()?
+        .components()
+        .next()
+        .map(|c| c.as_os_str().to_string_lossy().to_string())
+}
+
+// ============================================================================
+// Usage Queries
+// ============================================================================
+
+pub(crate) fn parse_date(value: &str) -> Result<NaiveDate, String> {
+    NaiveDate::parse_from_str(value, "%Y-%m-%d").map_err(|e| format!("Invalid date {}: {}", value, e))
+}
+
+/// Histogram period label of a day
+fn period_of(date: NaiveDate, weekly: bool) -> String {
+    if weekly {
+        let week = date.iso_week();
+        format!("{}-W{:02}", week.year(), week.week())
+    } else {
+        date.format("%Y-%m-%d").to_string()
+    }
+}
+
+/// Sort counts descending (ties by name) and keep the first `top`
+fn top_n(counts: HashMap<String, u32>, top: usize) -> Vec<UsageRank> {
+    let mut ranks: Vec<UsageRank> = counts
+        .into_iter()
+        .map(|(name, count)| UsageRank { name, count })
+        .collect();
+    ranks.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.name.cmp(&b.name)));
+    ranks.truncate(top);
+    ranks
+}
+
+/// Aggregate the daily buckets of `index` matching `query`
+fn query_index(index: &UsageIndex, query: &UsageQuery) -> Result<UsageTimeline, String> {
+    let since = query.since.as_deref().map(parse_date).transpose()?;
+    let until = query.until.as_deref().map(parse_date).transpose()?;
+    let weekly = match query.granularity.as_deref() {
+        None | Some("day") => false,
+        Some("week") => true,
+        Some(other) => return Err(format!("Unknown granularity: {}", other)),
+    };
+    let top = query.top.unwrap_or(10);
+
+    let mut timeline = UsageTimeline::default();
+    let mut per_day: HashMap<NaiveDate, u32> = HashMap::new();
+    let mut items: HashMap<String, u32> = HashMap::new();
+    let mut tools: HashMap<String, u32> = HashMap::new();
+    let mut projects: HashMap<String, u32> = HashMap::new();
+    let mut sessions: HashMap<String, u32> = HashMap::new();
+
+    for transcript in index.files.values() {
+        for bucket in &transcript.daily {
+            let Ok(date) = parse_date(&bucket.date) else {
+                continue;
+            };
+            let matches = since.map(|d| date >= d).unwrap_or(true)
+                && until.map(|d| date <= d).unwrap_or(true)
+                && query.kind.as_ref().map(|k| *k == bucket.kind).unwrap_or(true)
+                && query.name.as_ref().map(|n| *n == bucket.name).unwrap_or(true)
+                && query.tool.as_ref().map(|t| Some(t) == bucket.tool.as_ref()).unwrap_or(true);
+            if !matches {
+                continue;
+            }
+
+            timeline.total += bucket.count;
+            *per_day.entry(date).or_default() += bucket.count;
+            *items.entry(format!("{}:{}", bucket.kind, bucket.name)).or_default() += bucket.count;
+            if let Some(tool) = &bucket.tool {
+                *tools.entry(format!("{}/{}", bucket.name, tool)).or_default() += bucket.count;
+            }
+            if let Some(project) = &transcript.project_dir {
+                *projects.entry(project.clone()).or_default() += bucket.count;
+            }
+            if let Some(session) = &transcript.session_id {
+                *sessions.entry(session.clone()).or_default() += bucket.count;
+            }
+        }
+    }
+
+    // Every period of the window, so gaps show up as zero
+    let first = since.or_else(|| per_day.keys().min().copied());
+    let last = until.or_else(|| per_day.keys().max().copied());
+    if let (Some(first), Some(last)) = (first, last) {
+        for date in first.iter_days().take_while(|d| *d <= last) {
+            let period = period_of(date, weekly);
+            let count = per_day.get(&date).copied().unwrap_or(0);
+            match timeline.histogram.last_mut() {
+                Some(p) if p.period == period => p.count += count,
+                _ => timeline.histogram.push(UsagePeriod { period, count }),
+            }
+        }
+    }
+
+    timeline.top_items = top_n(items, top);
+    timeline.top_tools = top_n(tools, top);
+    timeline.top_projects = top_n(projects, top);
+    timeline.top_sessions = top_n(sessions, top);
+    Ok(timeline)
+}
+
+/// Calls of a skill or MCP server, overall and within a window
+#[derive(Default, Debug)]
+pub(crate) struct ItemActivity {
+    pub(crate) total_calls: u32,
+    pub(crate) last_used: Option<String>,
+    pub(crate) window_calls: u32,
+    /// Project directory -> calls within the window
+    pub(crate) window_projects: HashMap<String, u32>,
+}
+
+/// Summarise the activity of every skill and MCP server in `index`, keyed
+/// by ("skill" | "mcp", name)
+fn activity_from_index(index: &UsageIndex, since: NaiveDate) -> HashMap<(String, String), ItemActivity> {
+    let mut activity: HashMap<(String, String), ItemActivity> = HashMap::new();
+    let mut stats = UsageStats::default();
+    for transcript in index.files.values() {
+        stats.merge(&transcript.stats);
+        for bucket in &transcript.daily {
+            if parse_date(&bucket.date).map(|d| d < since).unwrap_or(true) {
+                continue;
+            }
+            let item = activity.entry((bucket.kind.clone(), bucket.name.clone())).or_default();
+            item.window_calls += bucket.count;
+            if let Some(project) = &transcript.project_dir {
+                *item.window_projects.entry(project.clone()).or_default() += bucket.count;
+            }
+        }
+    }
+
+    for (name, usage) in stats.skills {
+        let item = activity.entry(("skill".to_string(), name)).or_default();
+        item.total_calls = usage.call_count;
+        item.last_used = usage.last_used;
+    }
+    for (name, usage) in stats.mcps {
+        let item = activity.entry(("mcp".to_string(), name)).or_default();
+        item.total_calls = usage.total_calls;
+        item.last_used = usage.last_used;
+    }
+    activity
+}
+
+/// Bring the usage index up to date and summarise activity per item since
+/// `since`, keyed by ("skill" | "mcp", name)
+pub(crate) fn collect_item_activity(
+    claude_path: &Path,
+    since: NaiveDate,
+) -> Result<HashMap<(String, String), ItemActivity>, String> {
+    let mut index = read_usage_index();
+    update_index(claude_path, &mut index)?;
+    write_usage_index(&index);
+    Ok(activity_from_index(&index, since))
+}
+
+/// Upper bounds of the buckets MCP call latencies are counted in; slower
+/// calls go into one more bucket
+const LATENCY_BUCKETS_MS: [u64; 10] = [50, 100, 250, 500, 1_000, 2_000, 5_000, 10_000, 30_000, 60_000];
+
+/// Calls, errors and latency histogram of an MCP server or tool
+#[derive(Serialize, Deserialize, Default, Clone, Debug, PartialEq)]
+struct HealthTally {
+    /// Calls with a result
+    calls: u32,
+    errors: u32,
+    /// Calls per `LATENCY_BUCKETS_MS` bucket, the last one for slower calls
+    #[serde(default)]
+    latency_buckets: Vec<u32>,
+    /// Slowest call, reported for the last bucket
+    #[serde(default)]
+    max_latency_ms: Option<u64>,
+}
+
+impl HealthTally {
+    fn record(&mut self, latency_ms: Option<u64>, error: bool) {
+        self.calls += 1;
+        if error {
+            self.errors += 1;
+        }
+        if let Some(latency) = latency_ms {
+            let bucket = LATENCY_BUCKETS_MS
+                .iter()
+                .position(|bound| latency <= *bound)
+                .unwrap_or(LATENCY_BUCKETS_MS.len());
+            self.latency_buckets.resize(LATENCY_BUCKETS_MS.len() + 1, 0);
+            self.latency_buckets[bucket] += 1;
+            self.max_latency_ms = self.max_latency_ms.max(Some(latency));
+        }
+    }
+
+    fn add(&mut self, other: &HealthTally) {
+        self.calls += other.calls;
+        self.errors += other.errors;
+        if self.latency_buckets.len() < other.latency_buckets.len() {
+            self.latency_buckets.resize(other.latency_buckets.len(), 0);
+        }
+        for (count, other) in self.latency_buckets.iter_mut().zip(&other.latency_buckets) {
+            *count += other;
+        }
+        self.max_latency_ms = self.max_latency_ms.max(other.max_latency_ms);
+    }
+
+    fn error_rate(&self) -> f64 {
+        if self.calls == 0 {
+            0.0
+        } else {
+            self.errors as f64 / self.calls as f64
+        }
+    }
+
+    /// Median and 90th percentile latency
+    fn latency(&self) -> (Option<u64>, Option<u64>) {
+        (self.percentile(50), self.percentile(90))
+    }
+
+    /// Nearest-rank percentile, as the upper bound of the bucket it falls in
+    fn percentile(&self, pct: usize) -> Option<u64> {
+        let samples: usize = self.latency_buckets.iter().map(|c| *c as usize).sum();
+        if samples == 0 {
+            return None;
+        }
+        let rank = (samples * pct).div_ceil(100).max(1);
+        let mut seen = 0;
+        for (bucket, count) in self.latency_buckets.iter().enumerate() {
+            seen += *count as usize;
+            if seen >= rank {
+                return LATENCY_BUCKETS_MS.get(bucket).copied().or(self.max_latency_ms);
+            }
+        }
+        None
+    }
+}
+
+/// Health tallies of an MCP server while summarising
+#[derive(Default)]
+struct ServerTally {
+    total: HealthTally,
+    tools: HashMap<String, HealthTally>,
+    errors: Vec<McpToolError>,
+}
+
+/// Summarise the completed MCP calls of `index`, optionally for one server
+/// and from a day on; servers with the highest error rate come first
+fn mcp_health_from_index(index: &UsageIndex, server: Option<&str>, since: Option<NaiveDate>) -> Vec<McpHealth> {
+    let mut servers: HashMap<String, ServerTally> = HashMap::new();
+
+    let selected = |name: &str, timestamp: Option<&str>| {
+        if server.is_some_and(|s| s != name) {
+            return false;
+        }
+        match since {
+            Some(since) => {
+                let day = timestamp.and_then(|ts| ts.get(..10)).map(parse_date);
+                matches!(day, Some(Ok(d)) if d >= since)
+            }
+            None => true,
+        }
+    };
+
+    for transcript in index.files.values() {
+        for calls in &transcript.mcp_calls {
+            if !selected(&calls.server, Some(&calls.date)) {
+                continue;
+            }
+            let tally = servers.entry(calls.server.clone()).or_default();
+            tally.total.add(&calls.health);
+            if let Some(tool) = &calls.tool {
+                tally.tools.entry(tool.clone()).or_default().add(&calls.health);
+            }
+        }
+        for error in &transcript.mcp_errors {
+            if !selected(&error.server, error.timestamp.as_deref()) {
+                continue;
+            }
+            servers.entry(error.server.clone()).or_default().errors.push(McpToolError {
+                tool: error.tool.clone(),
+                timestamp: error.timestamp.clone(),
+                message: error.message.clone(),
+            });
+        }
+    }
+
+    let mut health: Vec<McpHealth> = servers
+        .into_iter()
+        .map(|(server, tally)| {
+            let ServerTally { total, tools, mut errors } = tally;
+            let mut tools: Vec<McpToolHealth> = tools
+                .into_iter()
+                .map(|(tool, tally)| {
+                    let (median_latency_ms, p90_latency_ms) = tally.latency();
+                    McpToolHealth {
+                        tool,
+                        calls: tally.calls,
+                        errors: tally.errors,
+                        error_rate: tally.error_rate(),
+                        median_latency_ms,
+                        p90_latency_ms,
+                    }
+                })
+                .collect();
+            tools.sort_by(|a, b| b.errors.cmp(&a.errors).then_with(|| a.tool.cmp(&b.tool)));
+
+            errors.sort_by(|a, b| b.timestamp.cmp(&a.timestamp));
+            errors.truncate(RECENT_ERRORS);
+
+            let (median_latency_ms, p90_latency_ms) = total.latency();
+            McpHealth {
+                server,
+                calls: total.calls,
+                errors: total.errors,
+                error_rate: total.error_rate(),
+                median_latency_ms,
+                p90_latency_ms,
+                tools,
+                recent_errors: errors,
+            }
+        })
+        .collect();
+    health.sort_by(|a, b| {
+        b.error_rate
+            .total_cmp(&a.error_rate)
+            .then_with(|| a.server.cmp(&b.server))
+    });
+    health
+}
+
+/// Sum the tokens of `index` per project directory and model, for the days
+/// between `since` and `until` (inclusive)
+fn tokens_from_index(index: &UsageIndex, since: Option<NaiveDate>, until: Option<NaiveDate>) -> Vec<ProjectTokens> {
+    let mut totals: HashMap<(String, Option<String>, String), TokenCounts> = HashMap::new();
+    for transcript in index.files.values() {
+        let Some(project_dir) = transcript.project_dir.clone().or_else(|| transcript.project_folder.clone()) else {
+            continue;
+        };
+        for bucket in &transcript.tokens {
+            let Ok(date) = parse_date(&bucket.date) else {
+                continue;
+            };
+            if since.map(|d| date < d).unwrap_or(false) || until.map(|d| date > d).unwrap_or(false) {
+                continue;
+            }
+            let key = (project_dir.clone(), transcript.project_folder.clone(), bucket.model.clone());
+            totals.entry(key).or_default().add(&bucket.tokens);
+        }
+    }
+
+    let mut rows: Vec<ProjectTokens> = totals
+        .into_iter()
+        .map(|((project_dir, project_folder, model), tokens)| ProjectTokens {
+            project_dir,
+            project_folder,
+            model,
+            tokens,
+        })
+        .collect();
+    rows.sort_by(|a, b| a.project_dir.cmp(&b.project_dir).then_with(|| a.model.cmp(&b.model)));
+    rows
+}
+
+/// Bring the usage index up to date and sum tokens per project directory
+/// and model between `since` and `until` (inclusive)
+pub(crate) fn collect_project_tokens(
+    claude_path: &Path,
+    since: Option<NaiveDate>,
+    until: Option<NaiveDate>,
+) -> Result<Vec<ProjectTokens>, String> {
+    let mut index = read_usage_index();
+    update_index(claude_path, &mut index)?;
+    write_usage_index(&index);
+    Ok(tokens_from_index(&index, since, until))
+}
+
+/// Definition files of a slash command or subagent, looked up in the
+/// Claude directory and in the projects (and their repository roots) it
+/// was used from
+fn definition_files(kind: &str, name: &str, claude_path: &Path, project_dirs: &[String]) -> Vec<PathBuf> {
+    let folder = match kind {
+        "command" => "commands",
+        "agent" => "agents",
+        _ => return Vec::new(),
+    };
+    let mut roots = vec![claude_path.join(folder)];
+    for dir in project_dirs {
+        let dir = Path::new(dir);
+        roots.push(dir.join(".claude").join(folder));
+        if let Some(repo) = find_repo_root(dir).filter(|r| r != dir) {
+            roots.push(repo.join(".claude").join(folder));
+        }
+    }
+    roots.dedup();
+
+    let mut files = Vec::new();
+    for root in roots {
+        if kind == "command" {
+            // Namespaced commands ("frontend:review") live in subdirectories
+            let path = root.join(format!("{}.md", name.replace(':', "/")));
+            if path.is_file() && !files.contains(&path) {
+                files.push(path);
+            }
+            continue;
+        }
+        // Agents are named by their frontmatter, usually matching the file name
+        let Ok(entries) = fs::read_dir(&root) else {
+            continue;
+        };
+        for path in entries.filter_map(|e| e.ok()).map(|e| e.path()) {
+            if path.extension().and_then(|e| e.to_str()) != Some("md") || files.contains(&path) {
+                continue;
+            }
+            let declared = fs::read_to_string(&path)
+                .ok()
+                .and_then(|content| parse_skill_frontmatter(&content))
+                .and_then(|fm| fm.name);
+            let matches = match declared {
+                Some(declared) => declared == name,
+                None => path.file_stem().and_then(|s| s.to_str()) == Some(name),
+            };
+            if matches {
+                files.push(path);
+            }
+        }
+    }
+    files
+}
+
+/// Summarise slash commands, subagents and hooks in `index`, most used first
+fn invocations_from_index(index: &UsageIndex, claude_path: &Path) -> Vec<InvocationSummary> {
+    let mut stats = UsageStats::default();
+    let mut project_dirs: HashMap<(String, String), Vec<String>> = HashMap::new();
+    for transcript in index.files.values() {
+        stats.merge(&transcript.stats);
+        let Some(project) = &transcript.project_dir else {
+            continue;
+        };
+        for bucket in &transcript.daily {
+            let dirs = project_dirs.entry((bucket.kind.clone(), bucket.name.clone())).or_default();
+            if !dirs.contains(project) {
+                dirs.push(project.clone());
+            }
+        }
+    }
+
+    let mut summaries = Vec::new();
+    for (kind, usages) in [("command", stats.commands), ("agent", stats.agents), ("hook", stats.hooks)] {
+        for (name, usage) in usages {
+            let dirs = project_dirs
+                .get(&(kind.to_string(), name.clone()))
+                .map(Vec::as_slice)
+                .unwrap_or_default();
+            summaries.push(InvocationSummary {
+                kind: kind.to_string(),
+                files: definition_files(kind, &name, claude_path, dirs)
+                    .into_iter()
+                    .map(|p| p.to_string_lossy().to_string())
+                    .collect(),
+                name,
+                call_count: usage.call_count,
+                last_used: usage.last_used,
+            });
+        }
+    }
+    summaries.sort_by(|a, b| b.call_count.cmp(&a.call_count).then_with(|| a.name.cmp(&b.name)));
+    summaries
 }
 
 // ============================================================================
@@ -37,7 +954,12 @@ struct TranscriptEntry {
     #[serde(rename = "type")]
     entry_type: Option<String>,
     timestamp: Option<String>,
+    #[serde(rename = "sessionId")]
+    session_id: Option<String>,
+    cwd: Option<String>,
     message: Option<Message>,
+    /// Payload of "progress" entries, such as hook runs
+    data: Option<ProgressData>,
     // Legacy flat format support
     tool_name: Option<String>,
     tool_input: Option<ToolInputLegacy>,
@@ -46,22 +968,80 @@ struct TranscriptEntry {
 /// Message containing content array
 #[derive(Deserialize, Debug)]
 struct Message {
+    id: Option<String>,
+    model: Option<String>,
+    usage: Option<MessageUsage>,
+    #[serde(default, deserialize_with = "deserialize_content")]
     content: Option<Vec<ContentItem>>,
 }
 
+/// Message content is either a list of items or, for typed user input,
+/// a plain string (read as a single text item)
+fn deserialize_content<'de, D>(deserializer: D) -> Result<Option<Vec<ContentItem>>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Content {
+        Text(String),
+        Items(Vec<ContentItem>),
+    }
+    Ok(Option::<Content>::deserialize(deserializer)?.map(|content| match content {
+        Content::Text(text) => vec![ContentItem {
+            item_type: Some("text".to_string()),
+            text: Some(text),
+            id: None,
+            name: None,
+            input: None,
+            tool_use_id: None,
+            is_error: None,
+            content: None,
+        }],
+        Content::Items(items) => items,
+    }))
+}
+
+/// Progress entry payload
+#[derive(Deserialize, Debug)]
+struct ProgressData {
+    #[serde(rename = "type")]
+    data_type: Option<String>,
+    #[serde(rename = "hookEvent")]
+    hook_event: Option<String>,
+    /// "Event:matcher"
+    #[serde(rename = "hookName")]
+    hook_name: Option<String>,
+}
+
+/// Token usage of an assistant message
+#[derive(Deserialize, Debug)]
+struct MessageUsage {
+    input_tokens: Option<u64>,
+    output_tokens: Option<u64>,
+    cache_creation_input_tokens: Option<u64>,
+    cache_read_input_tokens: Option<u64>,
+}
+
 /// Content item - can be text, tool_use, or tool_result
 #[derive(Deserialize, Debug)]
 struct ContentItem {
     #[serde(rename = "type")]
     item_type: Option<String>,
+    text: Option<String>,  // text items
+    id: Option<String>,    // tool_use id
     name: Option<String>,  // Tool name for tool_use items
     input: Option<ToolInput>,
+    tool_use_id: Option<String>, // tool_result items
+    is_error: Option<bool>,
+    content: Option<serde_json::Value>,
 }
 
-/// Tool input for Skill tool calls
+/// Tool input for Skill and Task tool calls
 #[derive(Deserialize, Debug)]
 struct ToolInput {
     skill: Option<String>,
+    subagent_type: Option<String>,
 }
 
 /// Legacy tool input structure
@@ -75,39 +1055,104 @@ struct ToolInputLegacy {
 // ============================================================================
 
 /// Scan Claude Code transcripts and projects to extract usage statistics
+///
+/// Per-transcript results are kept in a persistent index, so a rescan only
+/// parses bytes appended since the previous scan and new files.
+/// `full_rescan` discards the index and parses everything again.
 #[tauri::command]
-pub async fn scan_usage_stats(claude_dir: String) -> Result<UsageStats, String> {
+pub async fn scan_usage_stats(claude_dir: String, full_rescan: Option<bool>) -> Result<UsageStats, String> {
     let claude_path = expand_path(&claude_dir);
-    let mut stats = UsageStats::default();
+    let mut index = if full_rescan.unwrap_or(false) {
+        UsageIndex::default()
+    } else {
+        read_usage_index()
+    };
+
+    let stats = update_index(&claude_path, &mut index)?;
+    write_usage_index(&index);
+
+    Ok(stats)
+}
+
+/// Query usage over time from the transcript index
+///
+/// The index is brought up to date first, then the daily buckets matching
+/// `query` are aggregated into a per-day or per-week histogram and top-N
+/// lists of items, MCP tools, project directories and sessions.
+#[tauri::command]
+pub async fn query_usage(claude_dir: String, query: UsageQuery) -> Result<UsageTimeline, String> {
+    let claude_path = expand_path(&claude_dir);
+    let mut index = read_usage_index();
+    update_index(&claude_path, &mut index)?;
+    write_usage_index(&index);
+
+    query_index(&index, &query)
+}
+
+/// Error rates and latency of MCP servers and their tools
+///
+/// MCP tool_use entries are paired with their tool_result by id; calls
+/// still waiting for a result are not counted. Latencies are counted in
+/// buckets, so percentiles are reported as the bucket's upper bound.
+///
+/// # Arguments
+/// * `server` - Only this MCP server (None = all)
+/// * `since` - Only calls from this day on (YYYY-MM-DD)
+#[tauri::command]
+pub async fn get_mcp_health(
+    claude_dir: String,
+    server: Option<String>,
+    since: Option<String>,
+) -> Result<Vec<McpHealth>, String> {
+    let since = since.as_deref().map(parse_date).transpose()?;
+    let claude_path = expand_path(&claude_dir);
+    let mut index = read_usage_index();
+    update_index(&claude_path, &mut index)?;
+    write_usage_index(&index);
+
+    Ok(mcp_health_from_index(&index, server.as_deref(), since))
+}
+
+/// Slash commands, Task subagents and hooks invoked in transcripts, with
+/// the command and agent files they resolve to
+#[tauri::command]
+pub async fn get_invocation_usage(claude_dir: String) -> Result<Vec<InvocationSummary>, String> {
+    let claude_path = expand_path(&claude_dir);
+    let mut index = read_usage_index();
+    update_index(&claude_path, &mut index)?;
+    write_usage_index(&index);
+
+    Ok(invocations_from_index(&index, &claude_path))
+}
+
+/// List transcript files: `ses_*.jsonl` in transcripts/, and all `.jsonl`
+/// files in projects/, their session subdirectories and subagents folders
+fn list_transcript_files(claude_path: &Path) -> Result<Vec<PathBuf>, String> {
+    let mut files = Vec::new();
 
     // 1. Scan transcripts directory (ses_*.jsonl files)
     let transcripts_dir = claude_path.join("transcripts");
     if transcripts_dir.exists() {
-        scan_directory(&transcripts_dir, &mut stats, false)?;
+        list_directory(&transcripts_dir, false, &mut files)?;
     }
 
     // 2. Scan projects directory (project-specific transcripts)
     let projects_dir = claude_path.join("projects");
     if projects_dir.exists() {
-        scan_projects_directory(&projects_dir, &mut stats)?;
+        list_projects_directory(&projects_dir, &mut files)?;
     }
 
-    Ok(stats)
+    Ok(files)
 }
 
 /// Recursively scan projects directory, including session subdirectories and subagents folders
-fn scan_projects_directory(projects_dir: &Path, stats: &mut UsageStats) -> Result<(u32, u32), String> {
-    let mut total_files = 0u32;
-    let mut total_calls = 0u32;
-
+fn list_projects_directory(projects_dir: &Path, files: &mut Vec<PathBuf>) -> Result<(), String> {
     if let Ok(project_entries) = fs::read_dir(projects_dir) {
         for project_entry in project_entries.filter_map(|e| e.ok()) {
             let project_path = project_entry.path();
             if project_path.is_dir() {
                 // Scan the project directory for .jsonl files
-                let (fp, tc) = scan_directory(&project_path, stats, true)?;
-                total_files += fp;
-                total_calls += tc;
+                list_directory(&project_path, true, files)?;
 
                 // Scan session subdirectories (they have session IDs as names)
                 if let Ok(session_entries) = fs::read_dir(&project_path) {
@@ -115,16 +1160,12 @@ fn scan_projects_directory(projects_dir: &Path, stats: &mut UsageStats) -> Resul
                         let session_path = session_entry.path();
                         if session_path.is_dir() {
                             // Scan the session directory for .jsonl files
-                            let (fp, tc) = scan_directory(&session_path, stats, true)?;
-                            total_files += fp;
-                            total_calls += tc;
+                            list_directory(&session_path, true, files)?;
 
                             // Scan subagents directory if it exists
                             let subagents_dir = session_path.join("subagents");
                             if subagents_dir.exists() && subagents_dir.is_dir() {
-                                let (fp, tc) = scan_directory(&subagents_dir, stats, true)?;
-                                total_files += fp;
-                                total_calls += tc;
+                                list_directory(&subagents_dir, true, files)?;
                             }
                         }
                     }
@@ -132,14 +1173,11 @@ fn scan_projects_directory(projects_dir: &Path, stats: &mut UsageStats) -> Resul
             }
         }
     }
-    Ok((total_files, total_calls))
+    Ok(())
 }
 
-/// Scan a directory for .jsonl files and process them
-fn scan_directory(dir: &Path, stats: &mut UsageStats, include_all_jsonl: bool) -> Result<(u32, u32), String> {
-    let mut files_processed = 0u32;
-    let mut tool_calls_found = 0u32;
-
+/// Collect the transcript .jsonl files of a directory
+fn list_directory(dir: &Path, include_all_jsonl: bool, files: &mut Vec<PathBuf>) -> Result<(), String> {
     let entries = fs::read_dir(dir).map_err(|e| {
         format!("Failed to read directory {:?}: {}", dir, e)
     })?;
@@ -155,83 +1193,93 @@ fn scan_directory(dir: &Path, stats: &mut UsageStats, include_all_jsonl: bool) -
             };
 
             if should_process {
-                match process_transcript_file(&path, stats) {
-                    Ok(calls) => {
-                        files_processed += 1;
-                        tool_calls_found += calls;
-                    }
-                    Err(e) => {
-                        log::warn!("Failed to process transcript file {:?}: {}", path, e);
-                    }
-                }
+                files.push(path);
             }
         }
     }
 
-    Ok((files_processed, tool_calls_found))
+    Ok(())
 }
 
 // ============================================================================
 // File Processing
 // ============================================================================
 
-/// Process a single transcript file and update statistics
-fn process_transcript_file(path: &Path, stats: &mut UsageStats) -> Result<u32, String> {
-    let file = File::open(path).map_err(|e| format!("Failed to open file: {}", e))?;
-    let reader = BufReader::new(file);
-    let mut tool_calls = 0u32;
-
-    for line in reader.lines() {
-        let line = match line {
-            Ok(l) => l,
-            Err(_) => continue,
-        };
+/// Process a single transcript line and update the transcript's statistics
+fn process_line(line: &str, transcript: &mut IndexedTranscript) {
+    if line.trim().is_empty() {
+        return;
+    }
 
-        if line.trim().is_empty() {
-            continue;
-        }
+    let entry: TranscriptEntry = match serde_json::from_str(line) {
+        Ok(e) => e,
+        Err(_) => return,
+    };
 
-        let entry: TranscriptEntry = match serde_json::from_str(&line) {
-            Ok(e) => e,
-            Err(_) => continue,
-        };
+    let timestamp = entry.timestamp.clone();
+    if transcript.session_id.is_none() {
+        transcript.session_id = entry.session_id.clone();
+    }
+    if transcript.project_dir.is_none() {
+        transcript.project_dir = entry.cwd.clone();
+    }
 
-        let timestamp = entry.timestamp.clone();
-
-        // Try nested format first (Claude Code's actual format)
-        if entry.entry_type.as_deref() == Some("assistant") {
-            if let Some(ref message) = entry.message {
-                if let Some(ref content) = message.content {
-                    for item in content {
-                        if item.item_type.as_deref() == Some("tool_use") {
-                            if let Some(ref name) = item.name {
-                                if process_tool_call(name, &item.input, timestamp.clone(), stats) {
-                                    tool_calls += 1;
-                                }
-                            }
+    // Try nested format first (Claude Code's actual format)
+    if entry.entry_type.as_deref() == Some("assistant") {
+        if let Some(ref message) = entry.message {
+            record_tokens(message, timestamp.as_deref(), transcript);
+            if let Some(ref content) = message.content {
+                for item in content {
+                    if item.item_type.as_deref() == Some("tool_use") {
+                        if let Some(ref name) = item.name {
+                            record_daily(&mut transcript.daily, name, &item.input, timestamp.as_deref());
+                            process_tool_call(name, &item.input, timestamp.clone(), &mut transcript.stats);
+                            track_mcp_call(item, name, timestamp.as_deref(), &mut transcript.pending_calls);
                         }
                     }
                 }
             }
         }
+    }
 
-        // Also try legacy flat format
-        if entry.entry_type.as_deref() == Some("tool_use") {
-            if let Some(ref tool_name) = entry.tool_name {
-                let legacy_input = entry.tool_input.as_ref().map(|ti| ToolInput {
-                    skill: ti.skill.clone(),
-                });
-                if process_tool_call(tool_name, &legacy_input, timestamp, stats) {
-                    tool_calls += 1;
+    // Tool results and slash commands come in user entries
+    if entry.entry_type.as_deref() == Some("user") {
+        if let Some(content) = entry.message.as_ref().and_then(|m| m.content.as_ref()) {
+            for item in content {
+                match item.item_type.as_deref() {
+                    Some("tool_result") => complete_mcp_call(item, timestamp.as_deref(), transcript),
+                    Some("text") => {
+                        if let Some(command) = item.text.as_deref().and_then(slash_command_name) {
+                            record_invocation("command", command, timestamp.as_deref(), transcript);
+                        }
+                    }
+                    _ => {}
                 }
             }
         }
     }
 
-    Ok(tool_calls)
+    // Hook runs are logged as progress entries
+    if let Some(data) = entry.data.as_ref().filter(|d| d.data_type.as_deref() == Some("hook_progress")) {
+        if let Some(hook) = data.hook_name.as_deref().or(data.hook_event.as_deref()) {
+            record_invocation("hook", hook, timestamp.as_deref(), transcript);
+        }
+    }
+
+    // Also try legacy flat format
+    if entry.entry_type.as_deref() == Some("tool_use") {
+        if let Some(ref tool_name) = entry.tool_name {
+            let legacy_input = entry.tool_input.as_ref().map(|ti| ToolInput {
+                skill: ti.skill.clone(),
+                subagent_type: None,
+            });
+            record_daily(&mut transcript.daily, tool_name, &legacy_input, timestamp.as_deref());
+            process_tool_call(tool_name, &legacy_input, timestamp, &mut transcript.stats);
+        }
+    }
 }
 
-/// Process a tool call and update statistics. Returns true if it was an MCP or Skill call.
+/// Process a tool call and update statistics. Returns true if it was an MCP, Skill or Task call.
 fn process_tool_call(
     tool_name: &str,
     tool_input: &Option<ToolInput>,
@@ -244,24 +1292,248 @@ fn process_tool_call(
     } else if tool_name == "Skill" {
         process_skill_tool(tool_input, timestamp, stats);
         true
+    } else if let Some(agent) = subagent_type(tool_name, tool_input) {
+        let usage = stats.agents.entry(agent.to_string()).or_default();
+        usage.call_count += 1;
+        if let Some(ts) = timestamp {
+            update_last_used(&mut usage.last_used, ts);
+        }
+        true
     } else {
         false
     }
 }
 
+/// Split `mcp__server__tool` into server and tool names
+fn split_mcp_tool(tool_name: &str) -> Option<(&str, Option<&str>)> {
+    let mut parts = tool_name.splitn(3, "__").skip(1);
+    let server = parts.next().filter(|s| !s.is_empty())?;
+    Some((server, parts.next().filter(|t| !t.is_empty())))
+}
+
 /// Process an MCP tool call and update statistics
 fn process_mcp_tool(tool_name: &str, timestamp: Option<String>, stats: &mut UsageStats) {
-    let parts: Vec<&str> = tool_name.split("__").collect();
-    if parts.len() >= 2 {
-        let server_name = parts[1].to_string();
-        let mcp_usage = stats.mcps.entry(server_name).or_default();
+    if let Some((server_name, tool)) = split_mcp_tool(tool_name) {
+        let mcp_usage = stats.mcps.entry(server_name.to_string()).or_default();
         mcp_usage.total_calls += 1;
+        if let Some(tool) = tool {
+            *mcp_usage.tools.entry(tool.to_string()).or_default() += 1;
+        }
         if let Some(ts) = timestamp {
             update_last_used(&mut mcp_usage.last_used, ts);
         }
     }
 }
 
+/// Subagent type of a Task (or Agent) tool call
+fn subagent_type<'a>(tool_name: &str, tool_input: &'a Option<ToolInput>) -> Option<&'a str> {
+    if tool_name != "Task" && tool_name != "Agent" {
+        return None;
+    }
+    tool_input.as_ref().and_then(|i| i.subagent_type.as_deref())
+}
+
+/// Name of the slash command a user message invoked (`<command-name>/review</command-name>`)
+fn slash_command_name(text: &str) -> Option<&str> {
+    let start = text.find("<command-name>")? + "<command-name>".len();
+    let end = start + text[start..].find("</command-name>")?;
+    Some(text[start..end].trim().trim_start_matches('/')).filter(|name| !name.is_empty())
+}
+
+/// Count a slash command or hook run in the statistics and per-day buckets
+fn record_invocation(kind: &str, name: &str, timestamp: Option<&str>, transcript: &mut IndexedTranscript) {
+    let usages = match kind {
+        "command" => &mut transcript.stats.commands,
+        _ => &mut transcript.stats.hooks,
+    };
+    let usage = usages.entry(name.to_string()).or_default();
+    usage.call_count += 1;
+    if let Some(ts) = timestamp {
+        update_last_used(&mut usage.last_used, ts.to_string());
+    }
+    if let Some(date) = timestamp.and_then(|ts| ts.get(..10)) {
+        add_daily(&mut transcript.daily, date, kind, name, None);
+    }
+}
+
+/// Count a Skill, MCP or Task call in the per-day buckets
+fn record_daily(daily: &mut Vec<UsageBucket>, tool_name: &str, tool_input: &Option<ToolInput>, timestamp: Option<&str>) {
+    let Some(date) = timestamp.and_then(|ts| ts.get(..10)) else {
+        return;
+    };
+    let (kind, name, tool) = if tool_name == "Skill" {
+        match tool_input.as_ref().and_then(|i| i.skill.as_deref()) {
+            Some(skill) => ("skill", skill, None),
+            None => return,
+        }
+    } else if let Some(agent) = subagent_type(tool_name, tool_input) {
+        ("agent", agent, None)
+    } else {
+        match split_mcp_tool(tool_name) {
+            Some((server, tool)) if tool_name.starts_with("mcp__") => ("mcp", server, tool),
+            _ => return,
+        }
+    };
+    add_daily(daily, date, kind, name, tool);
+}
+
+fn add_daily(daily: &mut Vec<UsageBucket>, date: &str, kind: &str, name: &str, tool: Option<&str>) {
+    let existing = daily.iter_mut().find(|b| {
+        b.date == date && b.kind == kind && b.name == name && b.tool.as_deref() == tool
+    });
+    match existing {
+        Some(bucket) => bucket.count += 1,
+        None => daily.push(UsageBucket {
+            date: date.to_string(),
+            kind: kind.to_string(),
+            name: name.to_string(),
+            tool: tool.map(|t| t.to_string()),
+            count: 1,
+        }),
+    }
+}
+
+/// Count the tokens of an assistant message in the per-day buckets
+///
+/// Lines repeating the previous message only add what its usage grew by.
+fn record_tokens(message: &Message, timestamp: Option<&str>, transcript: &mut IndexedTranscript) {
+    let (Some(usage), Some(date)) = (message.usage.as_ref(), timestamp.and_then(|ts| ts.get(..10))) else {
+        return;
+    };
+    let model = message.model.as_deref().unwrap_or("unknown");
+    if model == "<synthetic>" {
+        return;
+    }
+
+    let counts = TokenCounts {
+        input: usage.input_tokens.unwrap_or(0),
+        output: usage.output_tokens.unwrap_or(0),
+        cache_creation: usage.cache_creation_input_tokens.unwrap_or(0),
+        cache_read: usage.cache_read_input_tokens.unwrap_or(0),
+    };
+    let added = match (&transcript.last_message, &message.id) {
+        (Some((last_id, previous)), Some(id)) if last_id == id => counts.since(previous),
+        _ => counts,
+    };
+    if let Some(id) = &message.id {
+        transcript.last_message = Some((id.clone(), counts));
+    }
+
+    match transcript.tokens.iter_mut().find(|b| b.date == date && b.model == model) {
+        Some(bucket) => bucket.tokens.add(&added),
+        None => transcript.tokens.push(TokenBucket {
+            date: date.to_string(),
+            model: model.to_string(),
+            tokens: added,
+        }),
+    }
+}
+
+/// Longest error message kept per failed call
+const MAX_ERROR_LENGTH: usize = 300;
+
+/// Errors listed per MCP server in its health summary
+const RECENT_ERRORS: usize = 5;
+
+/// Remember an MCP tool_use until its tool_result arrives
+fn track_mcp_call(
+    item: &ContentItem,
+    tool_name: &str,
+    timestamp: Option<&str>,
+    pending: &mut HashMap<String, PendingMcpCall>,
+) {
+    let (Some(id), Some((server, tool))) = (item.id.as_ref(), split_mcp_tool(tool_name)) else {
+        return;
+    };
+    if !tool_name.starts_with("mcp__") {
+        return;
+    }
+    pending.insert(
+        id.clone(),
+        PendingMcpCall {
+            server: server.to_string(),
+            tool: tool.map(|t| t.to_string()),
+            started_at: timestamp.map(|t| t.to_string()),
+        },
+    );
+}
+
+/// Pair a tool_result with its pending MCP call, if any
+fn complete_mcp_call(item: &ContentItem, timestamp: Option<&str>, transcript: &mut IndexedTranscript) {
+    let Some(call) = item
+        .tool_use_id
+        .as_ref()
+        .and_then(|id| transcript.pending_calls.remove(id))
+    else {
+        return;
+    };
+
+    let latency_ms = match (call.started_at.as_deref(), timestamp) {
+        (Some(start), Some(end)) => latency_between(start, end),
+        _ => None,
+    };
+    let error = item.is_error.unwrap_or(false);
+    let date = timestamp.and_then(|ts| ts.get(..10)).unwrap_or("");
+    let existing = transcript
+        .mcp_calls
+        .iter_mut()
+        .find(|t| t.date == date && t.server == call.server && t.tool == call.tool);
+    match existing {
+        Some(tally) => tally.health.record(latency_ms, error),
+        None => {
+            let mut health = HealthTally::default();
+            health.record(latency_ms, error);
+            transcript.mcp_calls.push(McpCallTally {
+                date: date.to_string(),
+                server: call.server.clone(),
+                tool: call.tool.clone(),
+                health,
+            });
+        }
+    }
+
+    if error {
+        let errors = &mut transcript.mcp_errors;
+        if errors.iter().filter(|e| e.server == call.server).count() >= RECENT_ERRORS {
+            if let Some(oldest) = errors.iter().position(|e| e.server == call.server) {
+                errors.remove(oldest);
+            }
+        }
+        errors.push(McpCallError {
+            server: call.server,
+            tool: call.tool,
+            timestamp: timestamp.map(|t| t.to_string()),
+            message: result_text(item.content.as_ref()),
+        });
+    }
+}
+
+/// Milliseconds between two RFC 3339 timestamps (None if unparsable or negative)
+fn latency_between(start: &str, end: &str) -> Option<u64> {
+    let start = chrono::DateTime::parse_from_rfc3339(start).ok()?;
+    let end = chrono::DateTime::parse_from_rfc3339(end).ok()?;
+    u64::try_from((end - start).num_milliseconds()).ok()
+}
+
+/// Text of a tool_result content (a string or a list of text blocks),
+/// shortened to `MAX_ERROR_LENGTH` characters
+fn result_text(content: Option<&serde_json::Value>) -> String {
+    let text = match content {
+        Some(serde_json::Value::String(text)) => text.clone(),
+        Some(serde_json::Value::Array(blocks)) => blocks
+            .iter()
+            .filter_map(|b| b.get("text").and_then(|t| t.as_str()))
+            .collect::<Vec<_>>()
+            .join("\n"),
+        _ => String::new(),
+    };
+    let text = text.trim();
+    match text.char_indices().nth(MAX_ERROR_LENGTH) {
+        Some((end, _)) => format!("{}…", &text[..end]),
+        None => text.to_string(),
+    }
+}
+
 /// Process a Skill tool call and update statistics
 fn process_skill_tool(tool_input: &Option<ToolInput>, timestamp: Option<String>, stats: &mut UsageStats) {
     if let Some(ref input) = tool_input {
@@ -355,6 +1627,7 @@ mod tests {
         let mut stats = UsageStats::default();
         let input = Some(ToolInput {
             skill: Some("commit".to_string()),
+            subagent_type: None,
         });
         process_skill_tool(&input, Some("2026-01-15T08:00:00Z".to_string()), &mut stats);
 
@@ -382,7 +1655,7 @@ mod tests {
     #[test]
     fn test_process_tool_call_skill() {
         let mut stats = UsageStats::default();
-        let input = Some(ToolInput { skill: Some("review-pr".to_string()) });
+        let input = Some(ToolInput { skill: Some("review-pr".to_string()), subagent_type: None });
         let result = process_tool_call("Skill", &input, Some("2026-01-01T00:00:00Z".to_string()), &mut stats);
         assert!(result);
         assert_eq!(stats.skills.len(), 1);
@@ -397,6 +1670,269 @@ mod tests {
         assert_eq!(stats.skills.len(), 0);
     }
 
+    const MCP_CALL: &str = r#"{"type":"assistant","timestamp":"2026-01-01T10:00:00Z","message":{"content":[{"type":"tool_use","name":"mcp__pencil__batch_design","input":{}}]}}"#;
+
+    fn mcp_calls(entry: &IndexedTranscript) -> u32 {
+        entry.stats.mcps.get("pencil").map(|m| m.total_calls).unwrap_or(0)
+    }
+
+    #[test]
+    fn test_index_transcript_parses_only_appended_lines() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let path = tmp.path().join("session.jsonl");
+        fs::write(&path, format!("{}\n", MCP_CALL)).unwrap();
+
+        let first = index_transcript(&path, None).unwrap();
+        assert_eq!(mcp_calls(&first), 1);
+
+        // A partially written line is left for the next scan
+        let partial = &MCP_CALL[..20];
+        fs::write(&path, format!("{}\n{}\n{}", MCP_CALL, MCP_CALL, partial)).unwrap();
+        let second = index_transcript(&path, Some(first.clone())).unwrap();
+        assert_eq!(mcp_calls(&second), 2);
+        assert_eq!(second.offset, 2 * (MCP_CALL.len() as u64 + 1));
+
+        let unchanged = index_transcript(&path, Some(second.clone())).unwrap();
+        assert_eq!(mcp_calls(&unchanged), 2);
+    }
+
+    #[test]
+    fn test_index_transcript_rebuilds_truncated_or_replaced_files() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let path = tmp.path().join("session.jsonl");
+        fs::write(&path, format!("{}\n{}\n", MCP_CALL, MCP_CALL)).unwrap();
+        let indexed = index_transcript(&path, None).unwrap();
+        assert_eq!(mcp_calls(&indexed), 2);
+
+        fs::write(&path, format!("{}\n", MCP_CALL)).unwrap();
+        let truncated = index_transcript(&path, Some(indexed.clone())).unwrap();
+        assert_eq!(mcp_calls(&truncated), 1);
+
+        let replaced = MCP_CALL.replace("10:00", "11:00");
+        fs::write(&path, format!("{}\n{}\n{}\n", replaced, replaced, replaced)).unwrap();
+        let rebuilt = index_transcript(&path, Some(indexed)).unwrap();
+        assert_eq!(mcp_calls(&rebuilt), 3);
+    }
+
+    #[test]
+    fn test_update_index_drops_removed_transcripts() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let session_dir = tmp.path().join("projects/-home-me-app");
+        fs::create_dir_all(&session_dir).unwrap();
+        fs::write(session_dir.join("a.jsonl"), format!("{}\n", MCP_CALL)).unwrap();
+        fs::write(session_dir.join("b.jsonl"), format!("{}\n", MCP_CALL)).unwrap();
+
+        let mut index = UsageIndex::default();
+        let stats = update_index(tmp.path(), &mut index).unwrap();
+        assert_eq!(stats.mcps["pencil"].total_calls, 2);
+
+        fs::remove_file(session_dir.join("b.jsonl")).unwrap();
+        let stats = update_index(tmp.path(), &mut index).unwrap();
+        assert_eq!(stats.mcps["pencil"].total_calls, 1);
+        assert_eq!(index.files.len(), 1);
+    }
+
+    #[test]
+    fn test_process_mcp_tool_counts_tools() {
+        let mut stats = UsageStats::default();
+        process_mcp_tool("mcp__pencil__batch_design", None, &mut stats);
+        process_mcp_tool("mcp__pencil__batch_design", None, &mut stats);
+        process_mcp_tool("mcp__pencil__get_screenshot", None, &mut stats);
+
+        let pencil = stats.mcps.get("pencil").unwrap();
+        assert_eq!(pencil.tools.get("batch_design"), Some(&2));
+        assert_eq!(pencil.tools.get("get_screenshot"), Some(&1));
+    }
+
+    #[test]
+    fn test_query_index_histogram_and_top_lists() {
+        let mut index = UsageIndex::default();
+        let mut transcript = |session: &str, project: &str, lines: &[(&str, &str)]| {
+            let mut entry = IndexedTranscript {
+                session_id: Some(session.to_string()),
+                project_dir: Some(project.to_string()),
+                ..Default::default()
+            };
+            for (tool, ts) in lines {
+                record_daily(&mut entry.daily, tool, &None, Some(ts));
+            }
+            index.files.insert(session.to_string(), entry);
+        };
+        transcript("s1", "/work/app", &[
+            ("mcp__pencil__batch_design", "2026-01-05T10:00:00Z"),
+            ("mcp__pencil__batch_design", "2026-01-05T11:00:00Z"),
+            ("mcp__pencil__get_screenshot", "2026-01-07T10:00:00Z"),
+        ]);
+        transcript("s2", "/work/site", &[("mcp__roam__query", "2026-01-12T10:00:00Z")]);
+
+        let query = UsageQuery { kind: Some("mcp".to_string()), ..Default::default() };
+        let daily = query_index(&index, &query).unwrap();
+        assert_eq!(daily.total, 4);
+        assert_eq!(daily.histogram.len(), 8, "Jan 5 to Jan 12, gaps included");
+        assert_eq!(daily.histogram[0], UsagePeriod { period: "2026-01-05".to_string(), count: 2 });
+        assert_eq!(daily.top_items[0], UsageRank { name: "mcp:pencil".to_string(), count: 3 });
+        assert_eq!(daily.top_tools[0], UsageRank { name: "pencil/batch_design".to_string(), count: 2 });
+        assert_eq!(daily.top_projects[0].name, "/work/app");
+
+        let query = UsageQuery {
+            granularity: Some("week".to_string()),
+            since: Some("2026-01-06".to_string()),
+            ..Default::default()
+        };
+        let weekly = query_index(&index, &query).unwrap();
+        assert_eq!(
+            weekly.histogram,
+            vec![
+                UsagePeriod { period: "2026-W02".to_string(), count: 1 },
+                UsagePeriod { period: "2026-W03".to_string(), count: 1 },
+            ]
+        );
+        assert_eq!(weekly.top_sessions.len(), 2);
+    }
+
+    #[test]
+    fn test_pairs_mcp_tool_results_with_calls() {
+        let call = |id: &str, tool: &str, ts: &str| {
+            format!(
+                r#"{{"type":"assistant","timestamp":"{}","message":{{"content":[{{"type":"tool_use","id":"{}","name":"mcp__pencil__{}","input":{{}}}}]}}}}"#,
+                ts, id, tool
+            )
+        };
+        let result = |id: &str, ts: &str, error: bool, content: &str| {
+            format!(
+                r#"{{"type":"user","timestamp":"{}","message":{{"content":[{{"type":"tool_result","tool_use_id":"{}","is_error":{},"content":{}}}]}}}}"#,
+                ts, id, error, content
+            )
+        };
+
+        let mut transcript = IndexedTranscript::default();
+        let lines = [
+            call("t1", "batch_design", "2026-01-01T10:00:00.000Z"),
+            call("t2", "batch_design", "2026-01-01T10:00:01.000Z"),
+            result("t1", "2026-01-01T10:00:00.400Z", false, r#""ok""#),
+            result("t2", "2026-01-01T10:00:03.000Z", true, r#"[{"type":"text","text":"Timed out"}]"#),
+            call("t3", "get_screenshot", "2026-01-02T09:00:00.000Z"),
+            result("t3", "2026-01-02T09:00:00.100Z", false, r#""ok""#),
+            call("t4", "get_screenshot", "2026-01-02T09:00:05.000Z"),
+        ];
+        for line in &lines {
+            process_line(line, &mut transcript);
+        }
+        assert_eq!(transcript.mcp_calls.len(), 2);
+        assert!(transcript.pending_calls.contains_key("t4"));
+
+        let mut index = UsageIndex::default();
+        index.files.insert("s1".to_string(), transcript);
+        let health = mcp_health_from_index(&index, Some("pencil"), None);
+        assert_eq!(health.len(), 1);
+        let pencil = &health[0];
+        assert_eq!((pencil.calls, pencil.errors), (3, 1));
+        assert_eq!(pencil.median_latency_ms, Some(500));
+        assert_eq!(pencil.p90_latency_ms, Some(2000));
+        assert_eq!(pencil.tools[0].tool, "batch_design");
+        assert_eq!(pencil.tools[0].error_rate, 0.5);
+        assert_eq!(pencil.recent_errors[0].message, "Timed out");
+
+        let since = NaiveDate::from_ymd_opt(2026, 1, 2);
+        let recent = mcp_health_from_index(&index, None, since);
+        assert_eq!((recent[0].calls, recent[0].errors), (1, 0));
+    }
+
+    #[test]
+    fn test_keeps_only_recent_mcp_errors() {
+        let mut transcript = IndexedTranscript::default();
+        for i in 0..RECENT_ERRORS + 2 {
+            let ts = format!("2026-01-01T10:00:{:02}.000Z", i);
+            process_line(
+                &format!(
+                    r#"{{"type":"assistant","timestamp":"{}","message":{{"content":[{{"type":"tool_use","id":"t{}","name":"mcp__db__query","input":{{}}}}]}}}}"#,
+                    ts, i
+                ),
+                &mut transcript,
+            );
+            process_line(
+                &format!(
+                    r#"{{"type":"user","timestamp":"{}","message":{{"content":[{{"type":"tool_result","tool_use_id":"t{}","is_error":true,"content":"failure {}"}}]}}}}"#,
+                    ts, i, i
+                ),
+                &mut transcript,
+            );
+        }
+
+        assert_eq!(transcript.mcp_calls.len(), 1);
+        assert_eq!(transcript.mcp_calls[0].health.calls as usize, RECENT_ERRORS + 2);
+        assert_eq!(transcript.mcp_errors.len(), RECENT_ERRORS);
+        assert_eq!(transcript.mcp_errors[0].message, "failure 2");
+        assert!(transcript.pending_calls.is_empty());
+    }
+
+    #[test]
+    fn test_record_tokens_counts_repeated_message_once() {
+        let line = |id: &str, output: u32| {
+            format!(
+                r#"{{"type":"assistant","timestamp":"2026-01-01T10:00:00Z","message":{{"id":"{}","model":"claude-sonnet-4-5","usage":{{"input_tokens":100,"output_tokens":{},"cache_read_input_tokens":50}},"content":[{{"type":"text","text":"hi"}}]}}}}"#,
+                id, output
+            )
+        };
+        let mut transcript = IndexedTranscript {
+            project_dir: Some("/work/app".to_string()),
+            ..Default::default()
+        };
+        for l in [line("m1", 5), line("m1", 20), line("m2", 10)] {
+            process_line(&l, &mut transcript);
+        }
+        assert_eq!(transcript.tokens.len(), 1);
+        assert_eq!(
+            transcript.tokens[0].tokens,
+            TokenCounts { input: 200, output: 30, cache_creation: 0, cache_read: 100 }
+        );
+
+        let mut index = UsageIndex::default();
+        index.files.insert("s1".to_string(), transcript);
+        let rows = tokens_from_index(&index, NaiveDate::from_ymd_opt(2026, 1, 1), None);
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].project_dir, "/work/app");
+        assert!(tokens_from_index(&index, NaiveDate::from_ymd_opt(2026, 1, 2), None).is_empty());
+    }
+
+    #[test]
+    fn test_classifies_commands_agents_and_hooks() {
+        let lines = [
+            r#"{"type":"user","timestamp":"2026-01-01T10:00:00Z","message":{"content":"<command-message>review is running</command-message>\n<command-name>/frontend:review</command-name>"}}"#,
+            r#"{"type":"assistant","timestamp":"2026-01-01T10:01:00Z","message":{"content":[{"type":"tool_use","id":"t1","name":"Task","input":{"subagent_type":"code-reviewer","prompt":"Review"}}]}}"#,
+            r#"{"type":"progress","timestamp":"2026-01-01T10:02:00Z","data":{"type":"hook_progress","hookEvent":"PreToolUse","hookName":"PreToolUse:Bash"}}"#,
+            r#"{"type":"user","timestamp":"2026-01-01T10:03:00Z","message":{"content":"plain prompt"}}"#,
+        ];
+
+        let tmp = tempfile::TempDir::new().unwrap();
+        let project = tmp.path().join("app");
+        let claude_path = tmp.path().join(".claude");
+        fs::create_dir_all(project.join(".claude/commands/frontend")).unwrap();
+        fs::write(project.join(".claude/commands/frontend/review.md"), "Review the diff").unwrap();
+        fs::create_dir_all(claude_path.join("agents")).unwrap();
+        fs::write(claude_path.join("agents/reviewer.md"), "---\nname: code-reviewer\n---\nYou review code").unwrap();
+
+        let mut transcript = IndexedTranscript {
+            project_dir: Some(project.to_string_lossy().to_string()),
+            ..Default::default()
+        };
+        for line in lines {
+            process_line(line, &mut transcript);
+        }
+        assert_eq!(transcript.stats.commands["frontend:review"].call_count, 1);
+        assert_eq!(transcript.stats.agents["code-reviewer"].call_count, 1);
+        assert_eq!(transcript.stats.hooks["PreToolUse:Bash"].call_count, 1);
+        assert_eq!(transcript.daily.len(), 3);
+
+        let mut index = UsageIndex::default();
+        index.files.insert("s1".to_string(), transcript);
+        let summaries = invocations_from_index(&index, &claude_path);
+        let files = |kind: &str| summaries.iter().find(|s| s.kind == kind).unwrap().files.clone();
+        assert_eq!(files("command"), vec![project.join(".claude/commands/frontend/review.md").to_string_lossy()]);
+        assert_eq!(files("agent"), vec![claude_path.join("agents/reviewer.md").to_string_lossy()]);
+        assert!(files("hook").is_empty());
+    }
+
     #[test]
     fn test_update_last_used_none_to_some() {
         let mut current: Option<String> = None;
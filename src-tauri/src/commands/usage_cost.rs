@@ -0,0 +1,290 @@
+use crate::commands::data::{read_app_data, read_settings};
+use crate::commands::usage::{collect_project_tokens, parse_date, ProjectTokens, TokenCounts};
+use crate::types::{AppData, CostReport, CostRollup, CostRow, Project, TokenCost};
+use crate::utils::expand_path;
+use std::collections::{BTreeSet, HashMap};
+use std::fs;
+use std::path::Path;
+
+/// List prices in USD per million input / output tokens, matched in order
+/// against the model ID
+const MODEL_PRICES: &[(&str, f64, f64)] = &[
+    ("opus-4-2025", 15.0, 75.0),
+    ("opus-4-0", 15.0, 75.0),
+    ("opus-4-1", 15.0, 75.0),
+    ("3-opus", 15.0, 75.0),
+    ("opus", 5.0, 25.0),
+    ("3-haiku", 0.25, 1.25),
+    ("3-5-haiku", 0.8, 4.0),
+    ("haiku", 1.0, 5.0),
+    ("sonnet", 3.0, 15.0),
+];
+
+/// Cache writes and reads are billed relative to the input price
+const CACHE_WRITE_FACTOR: f64 = 1.25;
+const CACHE_READ_FACTOR: f64 = 0.1;
+
+fn model_price(model: &str) -> Option<(f64, f64)> {
+    MODEL_PRICES
+        .iter()
+        .find(|(pattern, _, _)| model.contains(pattern))
+        .map(|(_, input, output)| (*input, *output))
+}
+
+/// Token counts with their estimated cost (0 for unknown models)
+fn token_cost(model: &str, tokens: &TokenCounts) -> TokenCost {
+    let cost_usd = model_price(model)
+        .map(|(input, output)| {
+            (tokens.input as f64 * input
+                + tokens.cache_creation as f64 * input * CACHE_WRITE_FACTOR
+                + tokens.cache_read as f64 * input * CACHE_READ_FACTOR
+                + tokens.output as f64 * output)
+                / 1_000_000.0
+        })
+        .unwrap_or(0.0);
+    TokenCost {
+        input_tokens: tokens.input,
+        output_tokens: tokens.output,
+        cache_creation_tokens: tokens.cache_creation,
+        cache_read_tokens: tokens.cache_read,
+        cost_usd,
+    }
+}
+
+fn add_cost(total: &mut TokenCost, other: &TokenCost) {
+    total.input_tokens += other.input_tokens;
+    total.output_tokens += other.output_tokens;
+    total.cache_creation_tokens += other.cache_creation_tokens;
+    total.cache_read_tokens += other.cache_read_tokens;
+    total.cost_usd += other.cost_usd;
+}
+
+/// Folder name Claude Code stores a project's transcripts under
+/// (~/.claude/projects/-home-me-app for /home/me/app)
+fn encode_project_dir(path: &str) -> String {
+    path.chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+        .collect()
+}
+
+/// Managed project a transcript directory belongs to: the deepest project
+/// containing the working directory, or the one whose encoded path is the
+/// projects/ folder name
+fn find_project<'a>(app_data: &'a AppData, usage: &ProjectTokens) -> Option<&'a Project> {
+    let dir = Path::new(&usage.project_dir);
+    app_data
+        .projects
+        .iter()
+        .filter(|p| dir.starts_with(expand_path(&p.path)))
+        .max_by_key(|p| p.path.len())
+        .or_else(|| {
+            let folder = usage.project_folder.as_deref()?;
+            app_data
+                .projects
+                .iter()
+                .find(|p| encode_project_dir(&expand_path(&p.path).to_string_lossy()) == folder)
+        })
+}
+
+fn into_rollups(totals: HashMap<String, (String, TokenCost)>) -> Vec<CostRollup> {
+    let mut rollups: Vec<CostRollup> = totals
+        .into_iter()
+        .map(|(id, (name, usage))| CostRollup { id, name, usage })
+        .collect();
+    rollups.sort_by(|a, b| b.usage.cost_usd.total_cmp(&a.usage.cost_usd).then_with(|| a.name.cmp(&b.name)));
+    rollups
+}
+
+fn build_cost_report(
+    usage: Vec<ProjectTokens>,
+    app_data: &AppData,
+    since: Option<String>,
+    until: Option<String>,
+) -> CostReport {
+    let mut rows = Vec::new();
+    let mut projects: HashMap<String, (String, TokenCost)> = HashMap::new();
+    let mut scenes: HashMap<String, (String, TokenCost)> = HashMap::new();
+    let mut total = TokenCost::default();
+    let mut unpriced_models = BTreeSet::new();
+
+    for entry in usage {
+        let cost = token_cost(&entry.model, &entry.tokens);
+        if model_price(&entry.model).is_none() {
+            unpriced_models.insert(entry.model.clone());
+        }
+        add_cost(&mut total, &cost);
+
+        let project = find_project(app_data, &entry);
+        let scene = project.and_then(|p| app_data.scenes.iter().find(|s| s.id == p.scene_id));
+        if let Some(project) = project {
+            let (_, sum) = projects
+                .entry(project.id.clone())
+                .or_insert_with(|| (project.name.clone(), TokenCost::default()));
+            add_cost(sum, &cost);
+        }
+        if let Some(scene) = scene {
+            let (_, sum) = scenes
+                .entry(scene.id.clone())
+                .or_insert_with(|| (scene.name.clone(), TokenCost::default()));
+            add_cost(sum, &cost);
+        }
+
+        rows.push(CostRow {
+            project_dir: entry.project_dir,
+            project_id: project.map(|p| p.id.clone()),
+            project_name: project.map(|p| p.name.clone()),
+            scene_id: scene.map(|s| s.id.clone()),
+            scene_name: scene.map(|s| s.name.clone()),
+            model: entry.model,
+            usage: cost,
+        });
+    }
+    rows.sort_by(|a, b| b.usage.cost_usd.total_cmp(&a.usage.cost_usd).then_with(|| a.project_dir.cmp(&b.project_dir)));
+
+    CostReport {
+        since,
+        until,
+        rows,
+        projects: into_rollups(projects),
+        scenes: into_rollups(scenes),
+        total,
+        unpriced_models: unpriced_models.into_iter().collect(),
+    }
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// One line per project directory and model
+fn report_to_csv(report: &CostReport) -> String {
+    let mut csv = String::from(
+        "project_dir,project,scene,model,input_tokens,output_tokens,cache_creation_tokens,cache_read_tokens,cost_usd\n",
+    );
+    for row in &report.rows {
+        let fields = [
+            csv_field(&row.project_dir),
+            csv_field(row.project_name.as_deref().unwrap_or("")),
+            csv_field(row.scene_name.as_deref().unwrap_or("")),
+            csv_field(&row.model),
+            row.usage.input_tokens.to_string(),
+            row.usage.output_tokens.to_string(),
+            row.usage.cache_creation_tokens.to_string(),
+            row.usage.cache_read_tokens.to_string(),
+            format!("{:.4}", row.usage.cost_usd),
+        ];
+        csv.push_str(&fields.join(","));
+        csv.push('\n');
+    }
+    csv
+}
+
+fn load_cost_report(since: Option<String>, until: Option<String>) -> Result<CostReport, String> {
+    let settings = read_settings()?;
+    let app_data = read_app_data()?;
+    let since_date = since.as_deref().map(parse_date).transpose()?;
+    let until_date = until.as_deref().map(parse_date).transpose()?;
+
+    let usage = collect_project_tokens(&expand_path(&settings.claude_config_dir), since_date, until_date)?;
+    Ok(build_cost_report(usage, &app_data, since, until))
+}
+
+// ============================================================================
+// Commands
+// ============================================================================
+
+/// Token usage and estimated cost from transcripts, per project directory
+/// and model, rolled up to the linked projects and their Scenes
+///
+/// # Arguments
+/// * `since` - First day, inclusive (YYYY-MM-DD)
+/// * `until` - Last day, inclusive (YYYY-MM-DD)
+#[tauri::command]
+pub async fn get_cost_report(since: Option<String>, until: Option<String>) -> Result<CostReport, String> {
+    load_cost_report(since, until)
+}
+
+/// Write the cost report rows to a CSV file
+#[tauri::command]
+pub async fn export_cost_report_csv(
+    path: String,
+    since: Option<String>,
+    until: Option<String>,
+) -> Result<(), String> {
+    let report = load_cost_report(since, until)?;
+    fs::write(expand_path(&path), report_to_csv(&report)).map_err(|e| format!("Failed to write CSV: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tokens(project_dir: &str, folder: Option<&str>, model: &str, input: u64, output: u64) -> ProjectTokens {
+        ProjectTokens {
+            project_dir: project_dir.to_string(),
+            project_folder: folder.map(|f| f.to_string()),
+            model: model.to_string(),
+            tokens: TokenCounts {
+                input,
+                output,
+                ..Default::default()
+            },
+        }
+    }
+
+    #[test]
+    fn test_token_cost_uses_model_prices() {
+        let counts = TokenCounts {
+            input: 1_000_000,
+            output: 1_000_000,
+            cache_creation: 1_000_000,
+            cache_read: 1_000_000,
+        };
+        let sonnet = token_cost("claude-sonnet-4-5-20250929", &counts);
+        assert!((sonnet.cost_usd - (3.0 + 15.0 + 3.75 + 0.3)).abs() < 1e-9);
+        assert_eq!(model_price("claude-opus-4-1-20250805"), Some((15.0, 75.0)));
+        assert_eq!(model_price("claude-3-5-haiku-20241022"), Some((0.8, 4.0)));
+        assert_eq!(token_cost("gpt-4", &counts).cost_usd, 0.0);
+    }
+
+    #[test]
+    fn test_build_cost_report_rolls_up_to_projects_and_scenes() {
+        let app_data = AppData {
+            scenes: serde_json::from_value(serde_json::json!([{
+                "id": "web", "name": "Web", "description": "", "icon": "",
+                "skillIds": [], "mcpIds": [], "createdAt": "", "lastUsed": null
+            }]))
+            .unwrap(),
+            projects: serde_json::from_value(serde_json::json!([
+                { "id": "p1", "name": "site", "path": "/work/site", "sceneId": "web", "lastSynced": null },
+                { "id": "p2", "name": "my.app", "path": "/work/my.app", "sceneId": "web", "lastSynced": null }
+            ]))
+            .unwrap(),
+            ..Default::default()
+        };
+
+        let usage = vec![
+            tokens("/work/site/docs", None, "claude-sonnet-4-5", 1_000_000, 0),
+            // Session without a cwd, matched by its projects/ folder name
+            tokens("-work-my-app", Some("-work-my-app"), "claude-sonnet-4-5", 0, 1_000_000),
+            tokens("/tmp/scratch", None, "custom-model", 10, 10),
+        ];
+        let report = build_cost_report(usage, &app_data, None, None);
+
+        assert_eq!(report.rows[0].project_name.as_deref(), Some("my.app"));
+        assert_eq!(report.rows[1].scene_id.as_deref(), Some("web"));
+        assert_eq!(report.rows[2].project_id, None);
+        assert_eq!(report.projects.len(), 2);
+        assert_eq!(report.scenes.len(), 1);
+        assert!((report.scenes[0].usage.cost_usd - 18.0).abs() < 1e-9);
+        assert_eq!(report.unpriced_models, vec!["custom-model"]);
+
+        let csv = report_to_csv(&report);
+        assert_eq!(csv.lines().count(), 4);
+        assert!(csv.lines().nth(1).unwrap().starts_with("-work-my-app,my.app,Web,claude-sonnet-4-5,0,1000000,"));
+    }
+}
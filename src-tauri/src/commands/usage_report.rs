@@ -0,0 +1,401 @@
+use crate::commands::data::{read_app_data, read_settings, write_app_data, DATA_MUTEX};
+use crate::commands::import::{update_mcp_scope, update_skill_scope};
+use crate::commands::library::get_library_dir;
+use crate::commands::mcps::{delete_mcp, scan_mcps};
+use crate::commands::skills::{delete_skill, scan_skills};
+use crate::commands::usage::{collect_item_activity, ItemActivity};
+use crate::types::{AppData, AppSettings, UnusedItem, UnusedItemAction, UnusedItemsCleanupResult, UnusedItemsReport};
+use crate::utils::expand_path;
+use chrono::{Duration, Utc};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// Default report window
+const DEFAULT_WINDOW_DAYS: u32 = 30;
+
+/// Default number of calls within the window below which an item is "rarely used"
+const DEFAULT_MIN_CALLS: u32 = 1;
+
+/// A skill or MCP in the library
+struct LibraryItem {
+    id: String,
+    name: String,
+    kind: &'static str,
+    scope: String,
+}
+
+/// Skills are "user"-scoped when deployed to ~/.claude, MCPs "global"
+fn is_global_scope(scope: &str) -> bool {
+    scope == "global" || scope == "user"
+}
+
+/// Skills and MCPs of the library; plugin-provided items are left out since
+/// they are managed by their plugin
+fn list_library_items(settings: &AppSettings) -> Result<Vec<LibraryItem>, String> {
+    let skills = scan_skills(settings.skill_source_dir.clone())?
+        .into_iter()
+        .filter(|s| s.install_source.as_deref() != Some("plugin"))
+        .map(|s| LibraryItem {
+            id: s.id,
+            name: s.name,
+            kind: "skill",
+            scope: s.scope,
+        });
+    let mcps = scan_mcps(settings.mcp_source_dir.clone())?
+        .into_iter()
+        .filter(|m| m.install_source.as_deref() != Some("plugin"))
+        .map(|m| LibraryItem {
+            id: m.id,
+            name: m.name,
+            kind: "mcp",
+            scope: m.scope,
+        });
+    Ok(skills.chain(mcps).collect())
+}
+
+/// Scenes containing the item whose projects never invoked it in the window
+///
+/// Scenes without projects are skipped, since there is nothing to judge by.
+fn idle_scenes(item: &LibraryItem, activity: Option<&ItemActivity>, app_data: &AppData) -> Vec<String> {
+    let used_in = |project_path: &Path| {
+        activity
+            .map(|a| a.window_projects.keys().any(|dir| Path::new(dir).starts_with(project_path)))
+            .unwrap_or(false)
+    };
+
+    app_data
+        .scenes
+        .iter()
+        .filter(|scene| match item.kind {
+            "skill" => scene.skill_ids.contains(&item.id),
+            _ => scene.mcp_ids.contains(&item.id),
+        })
+        .filter_map(|scene| {
+            let project_paths: Vec<PathBuf> = app_data
+                .projects
+                .iter()
+                .filter(|p| p.scene_id == scene.id)
+                .map(|p| expand_path(&p.path))
+                .collect();
+            let idle = !project_paths.is_empty() && !project_paths.iter().any(|p| used_in(p));
+            idle.then(|| scene.id.clone())
+        })
+        .collect()
+}
+
+/// Check one item against the report rules; None when it is in regular use
+fn assess_item(
+    item: &LibraryItem,
+    activity: Option<&ItemActivity>,
+    app_data: &AppData,
+    min_calls: u32,
+) -> Option<UnusedItem> {
+    let total_calls = activity.map(|a| a.total_calls).unwrap_or(0);
+    let window_calls = activity.map(|a| a.window_calls).unwrap_or(0);
+    let mut reasons = Vec::new();
+    let mut suggestion = None;
+
+    if total_calls == 0 {
+        reasons.push("never_used".to_string());
+        suggestion = Some("trash".to_string());
+    } else if window_calls < min_calls {
+        reasons.push("rarely_used".to_string());
+        suggestion = Some("trash".to_string());
+    }
+
+    let idle_scene_ids = idle_scenes(item, activity, app_data);
+    if !idle_scene_ids.is_empty() {
+        reasons.push("unused_in_scenes".to_string());
+    }
+
+    let only_project = match activity {
+        Some(a) if is_global_scope(&item.scope) && window_calls >= min_calls && a.window_projects.len() == 1 => {
+            a.window_projects.keys().next().cloned()
+        }
+        _ => None,
+    };
+    if only_project.is_some() {
+        reasons.push("single_project_global".to_string());
+        suggestion.get_or_insert_with(|| "demote".to_string());
+    }
+
+    if reasons.is_empty() {
+        return None;
+    }
+    Some(UnusedItem {
+        id: item.id.clone(),
+        name: item.name.clone(),
+        kind: item.kind.to_string(),
+        scope: item.scope.clone(),
+        total_calls,
+        window_calls,
+        last_used: activity.and_then(|a| a.last_used.clone()),
+        reasons,
+        idle_scene_ids,
+        only_project,
+        suggestion,
+    })
+}
+
+fn build_report(
+    items: &[LibraryItem],
+    activity: &HashMap<(String, String), ItemActivity>,
+    app_data: &AppData,
+    min_calls: u32,
+) -> Vec<UnusedItem> {
+    let mut report: Vec<UnusedItem> = items
+        .iter()
+        .filter_map(|item| {
+            let key = (item.kind.to_string(), item.name.clone());
+            assess_item(item, activity.get(&key), app_data, min_calls)
+        })
+        .collect();
+    // Least used first
+    report.sort_by(|a, b| {
+        a.window_calls
+            .cmp(&b.window_calls)
+            .then_with(|| a.total_calls.cmp(&b.total_calls))
+            .then_with(|| a.name.cmp(&b.name))
+    });
+    report
+}
+
+// ============================================================================
+// Commands
+// ============================================================================
+
+/// Report skills and MCPs that look like dead weight
+///
+/// Lists items never used, or used fewer than `min_calls` times in the last
+/// `days` days; items in Scenes whose projects never invoked them; and
+/// global items only used from a single project, with a suggested cleanup.
+///
+/// # Arguments
+/// * `days` - Window length (default: 30)
+/// * `min_calls` - Calls within the window below which an item is rarely used (default: 1)
+#[tauri::command]
+pub async fn get_unused_items_report(
+    days: Option<u32>,
+    min_calls: Option<u32>,
+) -> Result<UnusedItemsReport, String> {
+    let settings = read_settings()?;
+    let app_data = read_app_data()?;
+    let days = days.unwrap_or(DEFAULT_WINDOW_DAYS);
+    let since = (Utc::now() - Duration::days(days as i64)).date_naive();
+
+    let activity = collect_item_activity(&expand_path(&settings.claude_config_dir), since)?;
+    let items = list_library_items(&settings)?;
+
+    Ok(UnusedItemsReport {
+        days,
+        since: since.format("%Y-%m-%d").to_string(),
+        items: build_report(&items, &activity, &app_data, min_calls.unwrap_or(DEFAULT_MIN_CALLS)),
+    })
+}
+
+/// Add a demoted item to the Scene of the registered project it was used
+/// from, so the project keeps it once it is no longer global
+///
+/// The path is a session directory, so the deepest registered project
+/// containing it is used. Other projects using the same Scene get the item
+/// too.
+fn add_to_project_scene(app_data: &mut AppData, action: &UnusedItemAction) -> Result<(), String> {
+    let project_path = action
+        .project_path
+        .as_deref()
+        .ok_or_else(|| format!("No project to keep the {} in", action.kind))?;
+
+    let dir = expand_path(project_path);
+    let scene_id = app_data
+        .projects
+        .iter()
+        .filter(|p| dir.starts_with(expand_path(&p.path)))
+        .max_by_key(|p| p.path.len())
+        .map(|p| p.scene_id.clone())
+        .ok_or_else(|| format!("{} is not in a registered project", project_path))?;
+
+    let Some(scene) = app_data.scenes.iter_mut().find(|s| s.id == scene_id) else {
+        return Err(format!("{} is not a registered project with a Scene", project_path));
+    };
+    let ids = if action.kind == "skill" { &mut scene.skill_ids } else { &mut scene.mcp_ids };
+    if !ids.contains(&action.id) {
+        ids.push(action.id.clone());
+    }
+    Ok(())
+}
+
+fn keep_in_project(action: &UnusedItemAction) -> Result<(), String> {
+    let _guard = DATA_MUTEX.lock().map_err(|e| e.to_string())?;
+    let mut app_data = read_app_data()?;
+    add_to_project_scene(&mut app_data, action)?;
+    write_app_data(app_data)
+}
+
+/// Apply cleanup actions from the unused item report
+///
+/// "trash" moves the item to the trash like deleting it; "demote" adds it to
+/// the Scene of the action's project, which gets it on its next sync, then
+/// changes its scope to project, removing it from the global Claude
+/// configuration. Actions are applied independently; failures are reported
+/// per item.
+#[tauri::command]
+pub fn cleanup_unused_items(actions: Vec<UnusedItemAction>) -> Result<UnusedItemsCleanupResult, String> {
+    let settings = read_settings()?;
+    let ensemble_dir = get_library_dir().to_string_lossy().to_string();
+
+    let mut result = UnusedItemsCleanupResult {
+        succeeded: Vec::new(),
+        errors: Vec::new(),
+    };
+    for action in actions {
+        let outcome = match (action.kind.as_str(), action.action.as_str()) {
+            ("skill", "trash") => delete_skill(action.id.clone(), ensemble_dir.clone()),
+            ("mcp", "trash") => delete_mcp(action.id.clone(), ensemble_dir.clone()),
+            ("skill", "demote") => keep_in_project(&action).and_then(|_| {
+                update_skill_scope(
+                    action.id.clone(),
+                    "project".to_string(),
+                    ensemble_dir.clone(),
+                    settings.claude_config_dir.clone(),
+                    None,
+                )
+            }),
+            ("mcp", "demote") => keep_in_project(&action).and_then(|_| {
+                update_mcp_scope(
+                    action.id.clone(),
+                    "project".to_string(),
+                    ensemble_dir.clone(),
+                    settings.claude_config_dir.clone(),
+                )
+            }),
+            (kind, name) => Err(format!("Unknown action {} for {}", name, kind)),
+        };
+        match outcome {
+            Ok(()) => result.succeeded.push(action.id),
+            Err(e) => result.errors.push(format!("{}: {}", action.id, e)),
+        }
+    }
+
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{Project, Scene};
+
+    fn item(name: &str, kind: &'static str, scope: &str) -> LibraryItem {
+        LibraryItem {
+            id: format!("/lib/{}", name),
+            name: name.to_string(),
+            kind,
+            scope: scope.to_string(),
+        }
+    }
+
+    fn activity(total: u32, projects: &[(&str, u32)]) -> ItemActivity {
+        ItemActivity {
+            total_calls: total,
+            last_used: None,
+            window_calls: projects.iter().map(|(_, c)| c).sum(),
+            window_projects: projects.iter().map(|(p, c)| (p.to_string(), *c)).collect(),
+        }
+    }
+
+    #[test]
+    fn test_build_report_reasons() {
+        let mut app_data = AppData::default();
+        app_data.scenes.push(
+            serde_json::from_value::<Scene>(serde_json::json!({
+                "id": "web", "name": "Web", "description": "", "icon": "",
+                "skillIds": ["/lib/lint"], "mcpIds": [], "createdAt": "", "lastUsed": null
+            }))
+            .unwrap(),
+        );
+        app_data.projects.push(
+            serde_json::from_value::<Project>(serde_json::json!({
+                "id": "p1", "name": "site", "path": "/work/site", "sceneId": "web", "lastSynced": null
+            }))
+            .unwrap(),
+        );
+
+        let items = vec![
+            item("unused", "skill", "project"),
+            item("stale", "mcp", "project"),
+            item("lint", "skill", "project"),
+            item("db", "mcp", "global"),
+            item("busy", "skill", "global"),
+        ];
+        let mut usage = HashMap::new();
+        usage.insert(("mcp".to_string(), "stale".to_string()), activity(5, &[]));
+        usage.insert(("skill".to_string(), "lint".to_string()), activity(3, &[("/work/api", 3)]));
+        usage.insert(("mcp".to_string(), "db".to_string()), activity(9, &[("/work/api/server", 4)]));
+        usage.insert(
+            ("skill".to_string(), "busy".to_string()),
+            activity(9, &[("/work/api", 2), ("/work/site/docs", 2)]),
+        );
+
+        let report = build_report(&items, &usage, &app_data, 1);
+        let summary: Vec<(&str, Vec<&str>, Option<&str>)> = report
+            .iter()
+            .map(|i| {
+                (
+                    i.name.as_str(),
+                    i.reasons.iter().map(String::as_str).collect(),
+                    i.suggestion.as_deref(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("unused", vec!["never_used"], Some("trash")),
+                ("stale", vec!["rarely_used"], Some("trash")),
+                ("lint", vec!["unused_in_scenes"], None),
+                ("db", vec!["single_project_global"], Some("demote")),
+            ]
+        );
+        assert_eq!(report[2].idle_scene_ids, vec!["web"]);
+        assert_eq!(report[3].only_project.as_deref(), Some("/work/api/server"));
+    }
+
+    #[test]
+    fn test_add_to_project_scene_from_subdirectory() {
+        let mut app_data = AppData::default();
+        for (id, skills) in [("api", vec![]), ("server", vec!["/lib/lint"])] {
+            app_data.scenes.push(
+                serde_json::from_value::<Scene>(serde_json::json!({
+                    "id": id, "name": id, "description": "", "icon": "",
+                    "skillIds": skills, "mcpIds": [], "createdAt": "", "lastUsed": null
+                }))
+                .unwrap(),
+            );
+        }
+        for (id, path, scene) in [("p1", "/work/api", "api"), ("p2", "/work/api/server", "server")] {
+            app_data.projects.push(
+                serde_json::from_value::<Project>(serde_json::json!({
+                    "id": id, "name": id, "path": path, "sceneId": scene, "lastSynced": null
+                }))
+                .unwrap(),
+            );
+        }
+        let action = |id: &str, kind: &str, path: &str| {
+            serde_json::from_value::<UnusedItemAction>(serde_json::json!({
+                "id": id, "kind": kind, "action": "demote", "projectPath": path
+            }))
+            .unwrap()
+        };
+
+        // The deepest project containing the session directory is used
+        add_to_project_scene(&mut app_data, &action("/lib/db", "mcp", "/work/api/server/src")).unwrap();
+        add_to_project_scene(&mut app_data, &action("/lib/cache", "mcp", "/work/api/docs")).unwrap();
+        assert_eq!(app_data.scenes[0].mcp_ids, vec!["/lib/cache"]);
+        assert_eq!(app_data.scenes[1].mcp_ids, vec!["/lib/db"]);
+
+        // Items already in the Scene are not added twice
+        add_to_project_scene(&mut app_data, &action("/lib/lint", "skill", "/work/api/server")).unwrap();
+        assert_eq!(app_data.scenes[1].skill_ids, vec!["/lib/lint"]);
+
+        assert!(add_to_project_scene(&mut app_data, &action("/lib/db", "mcp", "/work/apiserver")).is_err());
+    }
+}
@@ -3,7 +3,7 @@ pub mod types;
 mod utils;
 
 use commands::claude_md::migrate_claude_md_storage;
-use commands::{classify, claude_md, config, data, dialog, import, mcps, plugins, skills, symlink, trash, usage};
+use commands::{agents, classify, claude_md, claude_md_analysis, claude_md_history, claude_md_scan, claude_md_sync, config, data, dialog, import, library, mcps, plugins, scene_permissions, settings_fragments, skill_duplicates, skill_sources, skill_versions, skills, slash_commands, symlink, trash, usage, usage_cost, usage_report};
 use tauri::{Emitter, Manager, RunEvent, WindowEvent};
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
@@ -75,6 +75,20 @@ pub fn run() {
             mcps::update_mcp_metadata,
             mcps::delete_mcp,
             mcps::fetch_mcp_tools,
+            // Agents commands
+            agents::scan_agents,
+            agents::get_agent,
+            agents::update_agent_metadata,
+            agents::detect_claude_agents,
+            agents::import_agents,
+            agents::delete_agent,
+            // Slash commands
+            slash_commands::scan_commands,
+            slash_commands::get_command,
+            slash_commands::update_command_metadata,
+            slash_commands::detect_claude_commands,
+            slash_commands::import_commands,
+            slash_commands::delete_command,
             // Symlink commands
             symlink::create_symlink,
             symlink::remove_symlink,
@@ -109,12 +123,38 @@ pub fn run() {
             data::get_scenes,
             data::add_scene,
             data::update_scene,
+            data::set_scene_claude_md_subdirectory,
             data::delete_scene,
             // Projects
             data::get_projects,
             data::add_project,
             data::update_project,
             data::delete_project,
+            // Settings fragments
+            settings_fragments::get_settings_fragments,
+            settings_fragments::add_settings_fragment,
+            settings_fragments::update_settings_fragment,
+            settings_fragments::delete_settings_fragment,
+            settings_fragments::import_settings_fragment,
+            // Scene permission rules
+            scene_permissions::build_scene_permissions,
+            scene_permissions::set_scene_permission_rules,
+            // Library relocation
+            library::relocate_library,
+            library::get_pending_library_relocation,
+            // Skill versions
+            skill_versions::list_skill_versions,
+            skill_versions::snapshot_skill,
+            skill_versions::diff_skill_versions,
+            skill_versions::rollback_skill,
+            skill_versions::pin_skill_version,
+            // Skill import from git repositories and archives
+            skill_sources::import_skills_from_source,
+            skill_sources::check_skill_source_update,
+            skill_sources::apply_skill_source_update,
+            // Duplicate skills
+            skill_duplicates::find_duplicate_skills,
+            skill_duplicates::merge_duplicate_skills,
             // Dialog
             dialog::select_folder,
             dialog::select_file,
@@ -137,6 +177,13 @@ pub fn run() {
             import::open_accessibility_settings,
             // Usage stats commands
             usage::scan_usage_stats,
+            usage::query_usage,
+            usage::get_mcp_health,
+            usage::get_invocation_usage,
+            usage_cost::get_cost_report,
+            usage_cost::export_cost_report_csv,
+            usage_report::get_unused_items_report,
+            usage_report::cleanup_unused_items,
             // Plugin commands
             plugins::detect_installed_plugins,
             plugins::detect_plugin_skills,
@@ -145,7 +192,8 @@ pub fn run() {
             plugins::import_plugin_mcps,
             plugins::check_plugins_enabled,
             // CLAUDE.md commands
-            claude_md::scan_claude_md_files,
+            claude_md_scan::scan_claude_md_files,
+            claude_md_scan::get_repo_claude_md_files,
             claude_md::import_claude_md,
             claude_md::read_claude_md,
             claude_md::get_claude_md_files,
@@ -153,12 +201,29 @@ pub fn run() {
             claude_md::delete_claude_md,
             claude_md::set_global_claude_md,
             claude_md::unset_global_claude_md,
+            claude_md::get_global_claude_md_entries,
+            claude_md::toggle_global_claude_md,
+            claude_md::reorder_global_claude_md,
+            claude_md::remove_global_claude_md,
             claude_md::distribute_claude_md,
             claude_md::distribute_scene_claude_md,
+            claude_md::preview_claude_md,
+            claude_md_analysis::analyze_claude_md,
+            // CLAUDE.md history commands
+            claude_md_history::list_claude_md_revisions,
+            claude_md_history::diff_claude_md_revisions,
+            claude_md_history::restore_claude_md_revision,
+            // CLAUDE.md sync commands
+            claude_md_sync::get_claude_md_sync_status,
+            claude_md_sync::diff_claude_md_distribution,
+            claude_md_sync::pull_claude_md_distribution,
+            claude_md_sync::push_claude_md_distribution,
             // Trash recovery commands
             trash::list_trashed_items,
             trash::restore_skill,
             trash::restore_mcp,
+            trash::restore_agent,
+            trash::restore_command,
             trash::restore_claude_md,
         ])
         .build(tauri::generate_context!())
@@ -1,5 +1,5 @@
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -31,6 +31,9 @@ pub struct Skill {
     pub marketplace: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub plugin_enabled: Option<bool>,
+    /// Import origin for skills imported from a git repository or archive
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub origin: Option<SkillOrigin>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -71,6 +74,56 @@ pub struct McpServer {
     pub plugin_enabled: Option<bool>,
 }
 
+/// Claude Code subagent definition (agents/{name}.md)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Agent {
+    pub id: String,
+    pub name: String,
+    pub description: String,
+    pub category: String,
+    pub tags: Vec<String>,
+    pub enabled: bool,
+    pub source_path: String,
+    pub scope: String, // "user" | "project"
+    /// Tools the subagent may use (None = inherits all tools)
+    pub tools: Option<Vec<String>>,
+    /// Model alias or ID ("inherit" = the session's model)
+    pub model: Option<String>,
+    /// System prompt (file body after the frontmatter)
+    pub instructions: String,
+    pub last_used: Option<String>,
+    pub usage_count: u32,
+    pub icon: Option<String>,
+    pub installed_at: Option<String>,
+}
+
+/// Claude Code custom slash command (commands/{namespace}/{name}.md)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SlashCommand {
+    pub id: String,
+    /// Invocation name without the slash; namespaced as "frontend:review"
+    pub name: String,
+    /// Subfolder path of namespaced commands ("frontend")
+    pub namespace: Option<String>,
+    pub description: String,
+    pub argument_hint: Option<String>,
+    pub allowed_tools: Option<Vec<String>>,
+    pub model: Option<String>,
+    pub category: String,
+    pub tags: Vec<String>,
+    pub enabled: bool,
+    pub source_path: String,
+    pub scope: String, // "user" | "project"
+    /// Prompt (file body after the frontmatter)
+    pub instructions: String,
+    pub last_used: Option<String>,
+    pub usage_count: u32,
+    pub icon: Option<String>,
+    pub installed_at: Option<String>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Tool {
@@ -87,11 +140,32 @@ pub struct Scene {
     pub icon: String,
     pub skill_ids: Vec<String>,
     pub mcp_ids: Vec<String>,
+    /// Subagent IDs (agent file paths)
+    #[serde(default)]
+    pub agent_ids: Vec<String>,
+    /// Slash command IDs (command file paths)
+    #[serde(default)]
+    pub command_ids: Vec<String>,
+    /// Settings fragment IDs merged into the project's Claude settings
+    #[serde(default)]
+    pub settings_fragment_ids: Vec<String>,
+    /// Reviewed `permissions.allow` rules written to settings.local.json on
+    /// sync; None leaves permissions to the user
+    #[serde(default)]
+    pub permission_rules: Option<Vec<String>>,
     pub created_at: String,
     pub last_used: Option<String>,
     /// Associated CLAUDE.md file IDs (excluding isGlobal=true files)
     #[serde(default)]
     pub claude_md_ids: Vec<String>,
+    /// CLAUDE.md file ID -> project subdirectory it is distributed to
+    /// (files without an entry go to the project root)
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub claude_md_subdirectories: HashMap<String, String>,
+    /// Skill deployment mode for projects using this scene: "symlink" | "copy"
+    /// (None = symlink)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deploy_mode: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -103,11 +177,23 @@ pub struct TrashedScene {
     pub icon: String,
     pub skill_ids: Vec<String>,
     pub mcp_ids: Vec<String>,
+    #[serde(default)]
+    pub agent_ids: Vec<String>,
+    #[serde(default)]
+    pub command_ids: Vec<String>,
+    #[serde(default)]
+    pub settings_fragment_ids: Vec<String>,
+    #[serde(default)]
+    pub permission_rules: Option<Vec<String>>,
     pub created_at: String,
     pub last_used: Option<String>,
     pub deleted_at: String,
     #[serde(default)]
     pub claude_md_ids: Vec<String>,
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub claude_md_subdirectories: HashMap<String, String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deploy_mode: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -118,6 +204,16 @@ pub struct Project {
     pub path: String,
     pub scene_id: String,
     pub last_synced: Option<String>,
+    /// Skills pinned to a specific version (skill name -> version ID);
+    /// pinned skills are deployed as copies instead of symlinks
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub pinned_skill_versions: HashMap<String, String>,
+    /// Skill deployment mode override: "symlink" | "copy" (None = use the scene's mode)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deploy_mode: Option<String>,
+    /// Template variables available to distributed CLAUDE.md files as `{{name}}`
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub variables: HashMap<String, String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -129,6 +225,12 @@ pub struct TrashedProject {
     pub scene_id: String,
     pub last_synced: Option<String>,
     pub deleted_at: String,
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub pinned_skill_versions: HashMap<String, String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deploy_mode: Option<String>,
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub variables: HashMap<String, String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -158,6 +260,13 @@ pub struct AppData {
     pub skill_metadata: HashMap<String, SkillMetadata>,
     pub mcp_metadata: HashMap<String, McpMetadata>,
     #[serde(default)]
+    pub agent_metadata: HashMap<String, AgentMetadata>,
+    #[serde(default)]
+    pub command_metadata: HashMap<String, CommandMetadata>,
+    /// Reusable Claude settings (hooks, permissions, env, model)
+    #[serde(default)]
+    pub settings_fragments: Vec<SettingsFragment>,
+    #[serde(default)]
     pub trashed_scenes: Vec<TrashedScene>,
     #[serde(default)]
     pub trashed_projects: Vec<TrashedProject>,
@@ -170,9 +279,16 @@ pub struct AppData {
     /// Managed CLAUDE.md files list
     #[serde(default)]
     pub claude_md_files: Vec<ClaudeMdFile>,
-    /// Current global CLAUDE.md file ID
+    /// First enabled entry of the global CLAUDE.md stack (kept for data
+    /// written before global entries could be stacked)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub global_claude_md_id: Option<String>,
+    /// Global CLAUDE.md entries, composed in order into ~/.claude/CLAUDE.md
+    #[serde(default)]
+    pub global_claude_md_entries: Vec<GlobalClaudeMdEntry>,
+    /// Distributed CLAUDE.md copies tracked for two-way sync
+    #[serde(default)]
+    pub claude_md_distributions: Vec<ClaudeMdDistribution>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -185,6 +301,41 @@ pub struct SkillMetadata {
     pub last_used: Option<String>,
     pub icon: Option<String>,
     pub scope: String, // "global" | "project"
+    /// Where the skill was imported from (git repository or archive)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub origin: Option<SkillOrigin>,
+}
+
+/// Metadata of a file-based library item (subagent or slash command)
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct FileItemMetadata {
+    pub category: String,
+    pub tags: Vec<String>,
+    pub enabled: bool,
+    pub usage_count: u32,
+    pub last_used: Option<String>,
+    pub icon: Option<String>,
+}
+
+pub type AgentMetadata = FileItemMetadata;
+pub type CommandMetadata = FileItemMetadata;
+
+/// Import origin of a skill, used to check for and apply updates
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SkillOrigin {
+    /// "git" | "archive"
+    pub source_type: String,
+    /// Repository path/URL or archive path
+    pub source: String,
+    /// Skill directory inside the source ("" = source root)
+    #[serde(default)]
+    pub subpath: String,
+    /// Git commit, or SHA-256 of the archive
+    pub revision: String,
+    /// Imported/updated time (ISO 8601)
+    pub imported_at: String,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -214,6 +365,20 @@ pub struct AppSettings {
     /// CLAUDE.md distribution target path
     #[serde(default = "default_claude_md_distribution_path")]
     pub claude_md_distribution_path: ClaudeMdDistributionPath,
+    /// Library root holding skills/, mcps/, claude-md/ and trash/
+    /// (None = application data directory)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub library_dir: Option<String>,
+    /// Revisions kept per managed CLAUDE.md (0 = unlimited)
+    #[serde(default = "default_claude_md_history_limit")]
+    pub claude_md_history_limit: u32,
+    /// Directories scanned for CLAUDE.md files
+    #[serde(default = "default_claude_md_scan_roots")]
+    pub claude_md_scan_roots: Vec<String>,
+    /// Directories skipped when scanning: bare names match anywhere,
+    /// paths match that directory only
+    #[serde(default = "default_claude_md_scan_excludes")]
+    pub claude_md_scan_excludes: Vec<String>,
 }
 
 fn default_warp_open_mode() -> String {
@@ -224,6 +389,27 @@ fn default_claude_md_distribution_path() -> ClaudeMdDistributionPath {
     ClaudeMdDistributionPath::ClaudeDir
 }
 
+/// Default number of revisions kept per managed CLAUDE.md
+pub const DEFAULT_CLAUDE_MD_HISTORY_LIMIT: u32 = 50;
+
+fn default_claude_md_history_limit() -> u32 {
+    DEFAULT_CLAUDE_MD_HISTORY_LIMIT
+}
+
+fn default_claude_md_scan_roots() -> Vec<String> {
+    ["~/Documents", "~/Projects", "~/Developer", "~/Code", "~/Workspace", "~/repos"]
+        .iter()
+        .map(|s| s.to_string())
+        .collect()
+}
+
+fn default_claude_md_scan_excludes() -> Vec<String> {
+    ["node_modules", "target", "build", "dist", "__pycache__", "venv"]
+        .iter()
+        .map(|s| s.to_string())
+        .collect()
+}
+
 impl Default for AppSettings {
     fn default() -> Self {
         Self {
@@ -237,6 +423,10 @@ impl Default for AppSettings {
             warp_open_mode: "window".to_string(),
             has_completed_import: false,
             claude_md_distribution_path: ClaudeMdDistributionPath::default(),
+            library_dir: None,
+            claude_md_history_limit: DEFAULT_CLAUDE_MD_HISTORY_LIMIT,
+            claude_md_scan_roots: default_claude_md_scan_roots(),
+            claude_md_scan_excludes: default_claude_md_scan_excludes(),
         }
     }
 }
@@ -308,6 +498,84 @@ pub struct ProjectConfigStatus {
     pub has_commands_md: bool,
     pub skill_count: u32,
     pub mcp_count: u32,
+    #[serde(default)]
+    pub agent_count: u32,
+    #[serde(default)]
+    pub command_count: u32,
+}
+
+// ============================================================================
+// Settings fragments
+// ============================================================================
+
+/// `permissions` rule lists of a Claude settings file
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct PermissionRules {
+    #[serde(default)]
+    pub allow: Vec<String>,
+    #[serde(default)]
+    pub deny: Vec<String>,
+    #[serde(default)]
+    pub ask: Vec<String>,
+}
+
+/// Settings keys Ensemble merges into .claude/settings.json or
+/// settings.local.json
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SettingsPatch {
+    /// Hook matcher groups by event ("PreToolUse" -> [{ matcher, hooks }])
+    #[serde(default)]
+    pub hooks: BTreeMap<String, Vec<serde_json::Value>>,
+    #[serde(default)]
+    pub permissions: PermissionRules,
+    #[serde(default)]
+    pub env: BTreeMap<String, String>,
+    pub model: Option<String>,
+}
+
+/// Reusable piece of Claude settings, added to Scenes and merged into
+/// project settings on sync
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SettingsFragment {
+    pub id: String,
+    pub name: String,
+    pub description: String,
+    /// "shared" (.claude/settings.json) | "local" (.claude/settings.local.json)
+    pub target: String,
+    #[serde(flatten)]
+    pub settings: SettingsPatch,
+    #[serde(default)]
+    pub category: String,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+/// A generated permission rule and the Scene items needing it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PermissionRuleSource {
+    /// `permissions.allow` rule ("mcp__github__create_issue", "Bash(git status:*)")
+    pub rule: String,
+    /// Items declaring it ("skill:commit", "mcp:github")
+    pub sources: Vec<String>,
+}
+
+/// Least-privilege `permissions.allow` list derived from a Scene's skills
+/// and MCP tools
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScenePermissions {
+    pub scene_id: String,
+    pub rules: Vec<PermissionRuleSource>,
+    /// MCP servers whose tool list is unknown, so no rules could be derived
+    pub mcps_without_tools: Vec<String>,
+    /// Rules currently saved on the Scene and written on sync
+    pub applied: Option<Vec<String>>,
 }
 
 // ============================================================================
@@ -563,6 +831,8 @@ pub enum ClaudeMdType {
     Global,
     Project,
     Local,
+    /// CLAUDE.md in a subdirectory of a repository (e.g. a monorepo package)
+    Subdirectory,
 }
 
 impl ClaudeMdType {
@@ -571,6 +841,7 @@ impl ClaudeMdType {
             ClaudeMdType::Global => "global",
             ClaudeMdType::Project => "project",
             ClaudeMdType::Local => "local",
+            ClaudeMdType::Subdirectory => "subdirectory",
         }
     }
 }
@@ -609,6 +880,9 @@ pub enum ClaudeMdConflictResolution {
     Overwrite,
     Backup,
     Skip,
+    /// Only replace the Ensemble-managed region (between begin/end markers),
+    /// keeping user text outside it
+    Managed,
 }
 
 impl Default for ClaudeMdConflictResolution {
@@ -617,6 +891,35 @@ impl Default for ClaudeMdConflictResolution {
     }
 }
 
+/// How `@path` imports are handled when distributing CLAUDE.md
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum ClaudeMdImportMode {
+    /// Leave references untouched
+    #[default]
+    Keep,
+    /// Replace references with the referenced file content
+    Inline,
+    /// Copy referenced files next to the target and rewrite the references
+    Copy,
+}
+
+/// An `@path` import referenced from a CLAUDE.md file
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ClaudeMdImportRef {
+    /// Reference as written, without the leading `@`
+    pub reference: String,
+
+    /// Resolved path of the referenced file (None if it could not be found)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub resolved_path: Option<String>,
+
+    /// Path of the copy in managed storage, relative to the file's directory
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub managed_path: Option<String>,
+}
+
 /// CLAUDE.md file info (managed file)
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -668,6 +971,10 @@ pub struct ClaudeMdFile {
     /// Custom icon name
     #[serde(skip_serializing_if = "Option::is_none")]
     pub icon: Option<String>,
+
+    /// `@path` imports referenced by the content (dependency tracking)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub imports: Vec<ClaudeMdImportRef>,
 }
 
 /// Scan result item
@@ -701,6 +1008,14 @@ pub struct ClaudeMdScanItem {
     /// Project name
     #[serde(skip_serializing_if = "Option::is_none")]
     pub project_name: Option<String>,
+
+    /// `@path` imports referenced by the file
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub imports: Vec<String>,
+
+    /// Root of the git repository containing the file
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub repo_root: Option<String>,
 }
 
 /// Scan result
@@ -719,6 +1034,52 @@ pub struct ClaudeMdScanResult {
     /// Error messages
     #[serde(default)]
     pub errors: Vec<String>,
+
+    /// Files reused from the previous scan because they were unchanged
+    #[serde(default)]
+    pub cached_files: u32,
+}
+
+/// CLAUDE.md files of one directory within a repository
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClaudeMdRepoDirectory {
+    /// Directory relative to the repository root ("" for the root)
+    pub relative_dir: String,
+
+    /// CLAUDE.md files found in the directory (including .claude/CLAUDE.md)
+    pub files: Vec<ClaudeMdScanItem>,
+
+    /// Managed CLAUDE.md IDs currently distributed into the directory
+    pub distributed_ids: Vec<String>,
+}
+
+/// All CLAUDE.md files of a repository, grouped by directory
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClaudeMdRepoView {
+    /// Repository root
+    pub repo_path: String,
+
+    /// Directories holding CLAUDE.md files, root first
+    pub directories: Vec<ClaudeMdRepoDirectory>,
+}
+
+/// Progress of a running CLAUDE.md scan (event `claude-md-scan-progress`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClaudeMdScanProgress {
+    /// Directories visited so far
+    pub scanned_dirs: u32,
+
+    /// CLAUDE.md files found so far
+    pub found: u32,
+
+    /// Directory being visited
+    pub current_path: String,
+
+    /// Whether the scan has finished
+    pub done: bool,
 }
 
 /// Import options
@@ -743,6 +1104,10 @@ pub struct ClaudeMdImportOptions {
     /// Tag ID list
     #[serde(default)]
     pub tag_ids: Vec<String>,
+
+    /// Copy files referenced with `@path` into managed storage
+    #[serde(default)]
+    pub include_imports: bool,
 }
 
 /// Import result
@@ -777,6 +1142,14 @@ pub struct ClaudeMdDistributionOptions {
     /// Conflict resolution strategy
     #[serde(default)]
     pub conflict_resolution: ClaudeMdConflictResolution,
+
+    /// How `@path` imports are handled
+    #[serde(default)]
+    pub import_mode: ClaudeMdImportMode,
+
+    /// Project subdirectory to distribute into (None = project root)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub subdirectory: Option<String>,
 }
 
 /// Distribution result
@@ -790,7 +1163,7 @@ pub struct ClaudeMdDistributionResult {
     pub target_path: String,
 
     /// Action performed
-    pub action: String, // "created" | "overwritten" | "backed_up" | "skipped"
+    pub action: String, // "created" | "overwritten" | "backed_up" | "skipped" | "merged"
 
     /// Backup path
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -801,6 +1174,25 @@ pub struct ClaudeMdDistributionResult {
     pub error: Option<String>,
 }
 
+/// Rendered CLAUDE.md preview
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClaudeMdPreview {
+    /// Rendered content, exactly as it would be distributed
+    pub content: String,
+    /// Template warnings (unknown variables, unbalanced tags)
+    pub warnings: Vec<String>,
+}
+
+/// An entry of the global CLAUDE.md stack
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct GlobalClaudeMdEntry {
+    pub claude_md_id: String,
+    /// Disabled entries stay in the stack but are left out of ~/.claude/CLAUDE.md
+    pub enabled: bool,
+}
+
 /// Set global result
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -808,7 +1200,7 @@ pub struct SetGlobalResult {
     /// Whether successful
     pub success: bool,
 
-    /// Previous global file ID
+    /// Previous first global file ID
     #[serde(skip_serializing_if = "Option::is_none")]
     pub previous_global_id: Option<String>,
 
@@ -816,7 +1208,8 @@ pub struct SetGlobalResult {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub backup_path: Option<String>,
 
-    /// Auto-imported file ID (when existing global was not managed)
+    /// Auto-imported file ID (when existing global was not managed; it is
+    /// kept as the first entry of the stack)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub auto_imported_id: Option<String>,
 
@@ -825,6 +1218,151 @@ pub struct SetGlobalResult {
     pub error: Option<String>,
 }
 
+/// A saved revision of a managed CLAUDE.md
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClaudeMdRevision {
+    /// Unique identifier (UUID)
+    pub id: String,
+
+    /// When the content was replaced (ISO 8601)
+    pub created_at: String,
+
+    /// Content size in bytes
+    pub size: u64,
+
+    /// Why the revision was saved (e.g. before a restore or pull)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+}
+
+/// A distributed copy of managed CLAUDE.md content, tracked for two-way sync
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClaudeMdDistribution {
+    /// Unique identifier (UUID)
+    pub id: String,
+
+    /// Managed files composed into the copy, in section order
+    pub claude_md_ids: Vec<String>,
+
+    /// Distributed file path
+    pub target_path: String,
+
+    /// Project the content was rendered for (None for ~/.claude/CLAUDE.md)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub project_path: Option<String>,
+
+    /// Whether Ensemble owns only the managed region of the target
+    #[serde(default)]
+    pub managed_region: bool,
+
+    /// Import handling used when distributing
+    #[serde(default)]
+    pub import_mode: ClaudeMdImportMode,
+
+    /// SHA-256 of the content as last distributed or pulled back
+    pub content_hash: String,
+
+    /// Last sync time (ISO 8601)
+    pub synced_at: String,
+}
+
+/// Sync state of a distributed CLAUDE.md copy
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClaudeMdSyncStatus {
+    pub distribution: ClaudeMdDistribution,
+
+    /// "in_sync" | "local_modified" | "source_modified" | "conflict" | "missing" | "error"
+    pub state: String,
+
+    /// Why the state could not be determined
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Three-way comparison of a distributed copy against its managed source
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClaudeMdSyncDiff {
+    pub status: ClaudeMdSyncStatus,
+
+    /// Content as last synced (common ancestor)
+    pub base: String,
+
+    /// Current content of the distributed copy (None if missing)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub local: Option<String>,
+
+    /// Content the managed source renders to now
+    pub source: String,
+
+    /// Unified diff base -> local
+    pub local_diff: String,
+
+    /// Unified diff base -> source
+    pub source_diff: String,
+}
+
+/// Estimated token count of one markdown section of a CLAUDE.md
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClaudeMdSectionTokens {
+    /// Heading text ("" for content before the first heading)
+    pub heading: String,
+    /// 1-based line of the heading
+    pub line: u32,
+    pub tokens: u32,
+}
+
+/// Estimated token count of a managed CLAUDE.md
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClaudeMdFileTokens {
+    pub id: String,
+    pub name: String,
+    /// Tokens of the file's own content
+    pub tokens: u32,
+    /// Tokens added by `@path` imports loaded with the file
+    pub import_tokens: u32,
+    pub sections: Vec<ClaudeMdSectionTokens>,
+}
+
+/// An instruction line referenced by an analysis finding
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ClaudeMdInstructionRef {
+    pub claude_md_id: String,
+    /// 1-based line in the stored content
+    pub line: u32,
+    pub text: String,
+}
+
+/// A finding of the CLAUDE.md quality analysis
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClaudeMdIssue {
+    /// "duplicate" | "contradiction" | "long_section"
+    pub kind: String,
+    pub message: String,
+    pub instructions: Vec<ClaudeMdInstructionRef>,
+}
+
+/// Token budget and quality analysis of CLAUDE.md files loaded together
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClaudeMdAnalysis {
+    pub files: Vec<ClaudeMdFileTokens>,
+    /// Tokens of the selected files composed as one distributed file
+    pub composed_tokens: u32,
+    /// Tokens of the global ~/.claude/CLAUDE.md loaded alongside (0 if none)
+    pub global_tokens: u32,
+    /// Tokens loaded into every session: composed plus global
+    pub total_tokens: u32,
+    pub issues: Vec<ClaudeMdIssue>,
+}
+
 // ============================================================================
 // Trash Recovery types
 // ============================================================================
@@ -840,6 +1378,29 @@ pub struct TrashedSkill {
     pub description: String,
 }
 
+/// Trashed subagent information
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TrashedAgent {
+    pub id: String,
+    pub name: String,
+    pub path: String,
+    pub deleted_at: String,
+    pub description: String,
+}
+
+/// Trashed slash command information
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TrashedCommand {
+    pub id: String,
+    /// Invocation name ("frontend:review")
+    pub name: String,
+    pub path: String,
+    pub deleted_at: String,
+    pub description: String,
+}
+
 /// Trashed MCP information
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -868,6 +1429,374 @@ pub struct TrashedItems {
     pub skills: Vec<TrashedSkill>,
     pub mcps: Vec<TrashedMcp>,
     pub claude_md_files: Vec<TrashedClaudeMd>,
+    #[serde(default)]
+    pub agents: Vec<TrashedAgent>,
+    #[serde(default)]
+    pub commands: Vec<TrashedCommand>,
+}
+
+// ============================================================================
+// Library relocation types
+// ============================================================================
+
+/// Journal of an in-progress library relocation (~/.ensemble/relocation.json)
+///
+/// Written before any file is moved and removed once every step has
+/// completed, so an interrupted relocation can be resumed.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LibraryRelocationJournal {
+    /// Library root before relocation
+    pub from_dir: String,
+    /// Library root after relocation
+    pub to_dir: String,
+    /// Skill source directory before relocation
+    pub old_skill_dir: String,
+    /// MCP source directory before relocation
+    pub old_mcp_dir: String,
+    /// Started time (ISO 8601)
+    pub started_at: String,
+    /// Completed steps: "move_files" | "rewrite_symlinks" | "update_data" | "update_settings"
+    #[serde(default)]
+    pub completed_steps: Vec<String>,
+}
+
+/// Library relocation result
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LibraryRelocationResult {
+    /// Whether successful
+    pub success: bool,
+    /// New library root
+    pub library_dir: String,
+    /// Number of skills, MCPs, CLAUDE.md and trash entries moved
+    pub moved_items: u32,
+    /// Number of project/global symlinks re-pointed to the new location
+    pub rewritten_symlinks: u32,
+    /// Number of ids and paths updated in data.json
+    pub updated_ids: u32,
+    /// Non-fatal errors (e.g. a symlink that could not be rewritten)
+    #[serde(default)]
+    pub errors: Vec<String>,
+}
+
+// ============================================================================
+// Skill version types
+// ============================================================================
+
+/// A file captured in a skill version
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SkillVersionFile {
+    /// Path relative to the skill directory (always '/'-separated)
+    pub path: String,
+    /// SHA-256 of the file content (object name in the version store)
+    pub hash: String,
+    /// File size in bytes
+    pub size: u64,
+}
+
+/// A snapshot of a skill directory
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SkillVersion {
+    /// Version ID
+    pub id: String,
+    /// Hash over the file list; identical content yields the same hash
+    pub content_hash: String,
+    /// Created time (ISO 8601)
+    pub created_at: String,
+    /// Files in this version, sorted by path
+    pub files: Vec<SkillVersionFile>,
+    /// Total size in bytes
+    pub total_size: u64,
+    /// Optional note (e.g. "Rolled back to {version id}")
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+}
+
+/// Version history of a single skill (~/.ensemble/skill-versions/history/{name}.json)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SkillVersionHistory {
+    /// Skill directory name
+    pub skill_name: String,
+    /// Versions, oldest first
+    #[serde(default)]
+    pub versions: Vec<SkillVersion>,
+    /// Cheap fingerprint (file count, size, latest mtime) of the last snapshot,
+    /// used to skip hashing unchanged skills
+    #[serde(default)]
+    pub fingerprint: String,
+}
+
+/// Per-file change between two skill versions
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SkillFileDiff {
+    /// Path relative to the skill directory
+    pub path: String,
+    /// "added" | "removed" | "modified"
+    pub status: String,
+    /// Unified diff for text files, None for binary files
+    pub diff: Option<String>,
+}
+
+/// Diff between two skill versions (or a version and the working copy)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SkillVersionDiff {
+    pub from_version: String,
+    /// None when diffing against the current skill directory
+    pub to_version: Option<String>,
+    pub files: Vec<SkillFileDiff>,
+}
+
+/// Stamp written into skill directories that Ensemble deploys as a copy
+/// instead of a symlink (.claude/skills/{name}/.ensemble-deploy.json)
+///
+/// The stamp marks the copy as managed so sync/clear can safely replace it,
+/// and records what was deployed so local edits can be detected.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SkillDeployStamp {
+    /// Library skill path the copy was made from
+    pub source_path: String,
+    /// Pinned version ID, if deployed from the version store
+    #[serde(default)]
+    pub version_id: Option<String>,
+    /// Content hash of the source at deploy time
+    #[serde(default)]
+    pub source_hash: String,
+    /// Deployed files (relative path -> SHA-256)
+    #[serde(default)]
+    pub files: BTreeMap<String, String>,
+    /// Deployed time (ISO 8601)
+    pub deployed_at: String,
+}
+
+/// Result of deploying skills into a .claude/skills directory
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SkillDeployResult {
+    /// Effective deployment mode: "symlink" | "copy"
+    pub mode: String,
+    /// Skills newly linked or copied
+    pub deployed: Vec<String>,
+    /// Copies refreshed because their source changed
+    pub updated: Vec<String>,
+    /// Copies already up to date
+    pub unchanged: Vec<String>,
+    /// Locally modified copies left untouched
+    pub skipped_modified: Vec<String>,
+    /// Agent files linked into .claude/agents
+    #[serde(default)]
+    pub agents: Vec<String>,
+    /// Slash commands deployed into .claude/commands ("frontend/review.md")
+    #[serde(default)]
+    pub commands: Vec<String>,
+    /// Locally modified command copies left untouched
+    #[serde(default)]
+    pub commands_skipped_modified: Vec<String>,
+    /// Settings fragment entries not applied because the project sets
+    /// them differently ("settings.json: env.API_URL")
+    #[serde(default)]
+    pub settings_conflicts: Vec<String>,
+}
+
+// ============================================================================
+// Skill source import types
+// ============================================================================
+
+/// Result of importing skills from a git repository or archive
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SkillSourceImportResult {
+    /// Imported skill IDs (paths in the skills directory)
+    pub imported: Vec<String>,
+    /// Revision the skills were imported at
+    pub revision: String,
+    /// Per-skill errors (e.g. name already exists)
+    #[serde(default)]
+    pub errors: Vec<String>,
+}
+
+/// Pending update of an imported skill
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SkillSourceUpdate {
+    pub skill_id: String,
+    pub current_revision: String,
+    pub latest_revision: String,
+    /// Whether applying the update would change any file
+    pub has_changes: bool,
+    /// Changes from the installed skill to the latest revision
+    pub files: Vec<SkillFileDiff>,
+}
+
+// ============================================================================
+// Duplicate skill types
+// ============================================================================
+
+/// A skill within a duplicate group
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SkillDuplicateMember {
+    pub id: String,
+    pub name: String,
+    /// Similarity to the first member of the group (0.0 - 1.0)
+    pub similarity: f64,
+}
+
+/// A group of skills with identical or near-identical content
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SkillDuplicateGroup {
+    /// "exact" (identical content) | "similar" (above the similarity threshold)
+    pub kind: String,
+    pub skills: Vec<SkillDuplicateMember>,
+}
+
+/// Result of merging duplicate skills into one
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SkillMergeResult {
+    /// Skill that was kept
+    pub kept_id: String,
+    /// Skills moved to trash
+    pub removed_ids: Vec<String>,
+    /// Scenes whose skill references were rewritten (projects using them need a re-sync)
+    pub updated_scene_ids: Vec<String>,
+    /// Skills that could not be moved to trash
+    #[serde(default)]
+    pub errors: Vec<String>,
+}
+
+// ============================================================================
+// Unused item report types
+// ============================================================================
+
+/// A skill or MCP that looks like dead weight
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UnusedItem {
+    /// Skill or MCP ID (path in the library)
+    pub id: String,
+    pub name: String,
+    /// "skill" | "mcp"
+    pub kind: String,
+    /// "global" | "project"
+    pub scope: String,
+    /// Calls across all indexed transcripts
+    pub total_calls: u32,
+    /// Calls within the report window
+    pub window_calls: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_used: Option<String>,
+    /// "never_used" | "rarely_used" | "unused_in_scenes" | "single_project_global"
+    pub reasons: Vec<String>,
+    /// Scenes containing the item whose projects did not invoke it in the window
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub idle_scene_ids: Vec<String>,
+    /// The only project directory a global item was used in
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub only_project: Option<String>,
+    /// Suggested cleanup: "trash" | "demote"
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub suggestion: Option<String>,
+}
+
+/// Report of unused and rarely used skills and MCPs
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UnusedItemsReport {
+    /// Window length in days
+    pub days: u32,
+    /// First day of the window (YYYY-MM-DD)
+    pub since: String,
+    pub items: Vec<UnusedItem>,
+}
+
+/// A cleanup action from the unused item report
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UnusedItemAction {
+    pub id: String,
+    /// "skill" | "mcp"
+    pub kind: String,
+    /// "trash" (move to trash) | "demote" (add to the project's Scene and
+    /// change scope to project)
+    pub action: String,
+    /// Project to keep a demoted item in (the report's `only_project`)
+    #[serde(default)]
+    pub project_path: Option<String>,
+}
+
+/// Result of a bulk cleanup
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UnusedItemsCleanupResult {
+    /// IDs the action was applied to
+    pub succeeded: Vec<String>,
+    /// Per-item errors
+    #[serde(default)]
+    pub errors: Vec<String>,
+}
+
+// ============================================================================
+// Token cost types
+// ============================================================================
+
+/// Token counts and their estimated cost
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenCost {
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    pub cache_creation_tokens: u64,
+    pub cache_read_tokens: u64,
+    /// Estimated cost in USD (models without a known price count as 0)
+    pub cost_usd: f64,
+}
+
+/// Tokens spent with one model in one project directory
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CostRow {
+    /// Directory the sessions ran in
+    pub project_dir: String,
+    /// Managed project the directory belongs to
+    pub project_id: Option<String>,
+    pub project_name: Option<String>,
+    /// Scene of that project
+    pub scene_id: Option<String>,
+    pub scene_name: Option<String>,
+    pub model: String,
+    pub usage: TokenCost,
+}
+
+/// Tokens rolled up to a project or Scene
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CostRollup {
+    pub id: String,
+    pub name: String,
+    pub usage: TokenCost,
+}
+
+/// Token usage and cost per project directory, project and Scene
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CostReport {
+    pub since: Option<String>,
+    pub until: Option<String>,
+    /// Most expensive first
+    pub rows: Vec<CostRow>,
+    pub projects: Vec<CostRollup>,
+    pub scenes: Vec<CostRollup>,
+    pub total: TokenCost,
+    /// Models seen without a known price
+    pub unpriced_models: Vec<String>,
 }
 
 #[cfg(test)]
@@ -879,6 +1808,7 @@ mod tests {
         assert_eq!(ClaudeMdType::Global.as_str(), "global");
         assert_eq!(ClaudeMdType::Project.as_str(), "project");
         assert_eq!(ClaudeMdType::Local.as_str(), "local");
+        assert_eq!(ClaudeMdType::Subdirectory.as_str(), "subdirectory");
     }
 
     #[test]
@@ -913,6 +1843,7 @@ mod tests {
         assert!(!settings.has_completed_import);
         assert!(settings.anthropic_api_key.is_none());
         assert_eq!(settings.claude_md_distribution_path, ClaudeMdDistributionPath::ClaudeDir);
+        assert!(settings.library_dir.is_none());
     }
 
     #[test]
@@ -930,6 +1861,7 @@ mod tests {
         assert!(data.imported_plugin_mcps.is_empty());
         assert!(data.claude_md_files.is_empty());
         assert!(data.global_claude_md_id.is_none());
+        assert!(data.global_claude_md_entries.is_empty());
     }
 
     #[test]
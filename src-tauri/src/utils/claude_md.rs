@@ -0,0 +1,349 @@
+//! Rendering helpers for distributed CLAUDE.md content
+
+/// A CLAUDE.md entry contributing a section to a composed file
+pub struct ClaudeMdSection<'a> {
+    pub id: &'a str,
+    pub name: &'a str,
+    pub content: &'a str,
+}
+
+/// Section name safe to put in a marker comment: `"` would end the
+/// attribute and `--` the comment, so quotes become `'` and runs of `-`
+/// are collapsed
+fn marker_name(name: &str) -> String {
+    let mut out = String::with_capacity(name.len());
+    for c in name.chars() {
+        if c == '-' && out.ends_with('-') {
+            continue;
+        }
+        out.push(if c == '"' { '\'' } else { c });
+    }
+    out
+}
+
+/// Compose several CLAUDE.md entries into one document
+///
+/// Each entry becomes a section delimited by HTML comment markers naming its
+/// source, in the given order. A single entry is returned unchanged.
+pub fn compose_sections(sections: &[ClaudeMdSection]) -> String {
+    if let [single] = sections {
+        return single.content.to_string();
+    }
+
+    sections
+        .iter()
+        .map(|s| {
+            format!(
+                "<!-- ensemble:section id=\"{}\" name=\"{}\" -->\n{}\n<!-- ensemble:end-section id=\"{}\" -->",
+                s.id,
+                marker_name(s.name),
+                s.content.trim_end(),
+                s.id
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n\n")
+        + "\n"
+}
+
+/// Split a composed document back into `(id, content)` sections
+///
+/// Returns None when the text contains no section markers.
+pub fn split_sections(text: &str) -> Option<Vec<(String, String)>> {
+    const OPEN: &str = "<!-- ensemble:section id=\"";
+    let mut sections = Vec::new();
+    let mut rest = text;
+
+    while let Some(start) = rest.find(OPEN) {
+        let after_open = &rest[start + OPEN.len()..];
+        let id = &after_open[..after_open.find('"')?];
+        let body_start = after_open.find("-->\n")? + 4;
+        let close = format!("\n<!-- ensemble:end-section id=\"{}\" -->", id);
+        let body_len = after_open[body_start..].find(&close)?;
+        sections.push((
+            id.to_string(),
+            after_open[body_start..body_start + body_len].to_string(),
+        ));
+        rest = &after_open[body_start + body_len + close.len()..];
+    }
+
+    if sections.is_empty() {
+        None
+    } else {
+        Some(sections)
+    }
+}
+
+/// Opening marker of the Ensemble-managed region in a distributed file
+pub const MANAGED_BEGIN: &str =
+    "<!-- ensemble:managed:begin - content up to the end marker is replaced on each sync -->";
+/// Closing marker of the Ensemble-managed region
+pub const MANAGED_END: &str = "<!-- ensemble:managed:end -->";
+
+/// Prefix used to recognise the opening marker regardless of its hint text
+const MANAGED_BEGIN_PREFIX: &str = "<!-- ensemble:managed:begin";
+
+/// Byte range of the managed region in `text`, markers included
+///
+/// A begin marker without an end marker extends to the end of the text.
+fn managed_region_range(text: &str) -> Option<(usize, usize)> {
+    let start = text.find(MANAGED_BEGIN_PREFIX)?;
+    let end = match text[start..].find(MANAGED_END) {
+        Some(offset) => start + offset + MANAGED_END.len(),
+        None => text.len(),
+    };
+    Some((start, end))
+}
+
+/// Wrap content in managed-region markers
+pub fn wrap_managed_region(content: &str) -> String {
+    format!("{}\n{}\n{}", MANAGED_BEGIN, content.trim_end(), MANAGED_END)
+}
+
+/// Remove the managed region from `text`, keeping the user's text
+///
+/// Returns None when `text` has no managed region.
+pub fn remove_managed_region(text: &str) -> Option<String> {
+    let (start, end) = managed_region_range(text)?;
+    let before = text[..start].trim_end();
+    let after = text[end..].trim_start();
+    Some(match (before.is_empty(), after.is_empty()) {
+        (true, true) => String::new(),
+        (false, true) => format!("{}\n", before),
+        (true, false) => after.to_string(),
+        (false, false) => format!("{}\n\n{}", before, after),
+    })
+}
+
+/// Content inside the managed region of `text`, without the markers
+pub fn managed_region_content(text: &str) -> Option<&str> {
+    let (start, end) = managed_region_range(text)?;
+    let region = &text[start..end];
+    let body = region.find('\n').map(|i| &region[i + 1..]).unwrap_or("");
+    Some(body.strip_suffix(MANAGED_END).unwrap_or(body).trim_end())
+}
+
+/// Put `content` into the managed region of `existing`
+///
+/// Replaces an existing region in place; otherwise the region is appended
+/// after the user's text. Text outside the region is never modified.
+pub fn apply_managed_region(existing: Option<&str>, content: &str) -> String {
+    let region = wrap_managed_region(content);
+    match existing {
+        None => format!("{}\n", region),
+        Some(text) => match managed_region_range(text) {
+            Some((start, end)) => {
+                let mut result = String::with_capacity(text.len() + content.len());
+                result.push_str(&text[..start]);
+                result.push_str(&region);
+                if end == text.len() {
+                    result.push('\n');
+                } else {
+                    result.push_str(&text[end..]);
+                }
+                result
+            }
+            None if text.trim().is_empty() => format!("{}\n", region),
+            None => format!("{}\n\n{}\n", text.trim_end(), region),
+        },
+    }
+}
+
+/// Whether an `@` token looks like a file path rather than a mention
+fn is_import_path(token: &str) -> bool {
+    !token.is_empty()
+        && !token.starts_with('@')
+        && (token.contains('/') || token.contains('.') || token.starts_with('~'))
+}
+
+/// Walk the `@path` import references in `content`, replacing each one
+/// (including its `@`) with the value returned by `replace`
+///
+/// References inside fenced code blocks and inline code spans are ignored,
+/// as are `@` signs not at the start of a word (e.g. email addresses).
+pub fn rewrite_import_references(
+    content: &str,
+    mut replace: impl FnMut(&str) -> Option<String>,
+) -> String {
+    let mut output = String::with_capacity(content.len());
+    let mut fence: Option<&str> = None;
+
+    for line in content.split_inclusive('\n') {
+        let trimmed = line.trim_start();
+        if let Some(marker) = ["```", "~~~"].into_iter().find(|m| trimmed.starts_with(m)) {
+            match fence {
+                Some(open) if open == marker => fence = None,
+                None => fence = Some(marker),
+                _ => {}
+            }
+            output.push_str(line);
+            continue;
+        }
+        if fence.is_some() {
+            output.push_str(line);
+            continue;
+        }
+
+        // Backtick run length of the open inline code span, 0 outside code
+        let mut code_ticks = 0;
+        let mut i = 0;
+        while let Some(c) = line[i..].chars().next() {
+            if c == '`' {
+                let run = line[i..].len() - line[i..].trim_start_matches('`').len();
+                if code_ticks == 0 {
+                    code_ticks = run;
+                } else if run == code_ticks {
+                    code_ticks = 0;
+                }
+                output.push_str(&line[i..i + run]);
+                i += run;
+                continue;
+            }
+            let at_word_start = i == 0 || line[..i].ends_with(char::is_whitespace);
+            if c == '@' && code_ticks == 0 && at_word_start {
+                let rest = &line[i + 1..];
+                let end = rest.find(char::is_whitespace).unwrap_or(rest.len());
+                let token = rest[..end].trim_end_matches(['.', ',', ';', ':', ')', '!', '?']);
+                if is_import_path(token) {
+                    if let Some(replacement) = replace(token) {
+                        output.push_str(&replacement);
+                        i += 1 + token.len();
+                        continue;
+                    }
+                }
+            }
+            output.push(c);
+            i += c.len_utf8();
+        }
+    }
+
+    output
+}
+
+/// List the `@path` import references in `content`, in order, without duplicates
+pub fn find_import_references(content: &str) -> Vec<String> {
+    let mut references: Vec<String> = Vec::new();
+    rewrite_import_references(content, |reference| {
+        if !references.iter().any(|r| r == reference) {
+            references.push(reference.to_string());
+        }
+        None
+    });
+    references
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compose_single_section_is_unchanged() {
+        let sections = [ClaudeMdSection { id: "a", name: "A", content: "# Rules\n" }];
+        assert_eq!(compose_sections(&sections), "# Rules\n");
+    }
+
+    #[test]
+    fn test_compose_multiple_sections_in_order() {
+        let sections = [
+            ClaudeMdSection { id: "team", name: "Team \"conventions\"", content: "Team\n\n" },
+            ClaudeMdSection { id: "rust", name: "Rust", content: "Rust" },
+        ];
+        assert_eq!(
+            compose_sections(&sections),
+            "<!-- ensemble:section id=\"team\" name=\"Team 'conventions'\" -->\nTeam\n<!-- ensemble:end-section id=\"team\" -->\n\n\
+             <!-- ensemble:section id=\"rust\" name=\"Rust\" -->\nRust\n<!-- ensemble:end-section id=\"rust\" -->\n"
+        );
+    }
+
+    #[test]
+    fn test_compose_escapes_comment_end_in_names() {
+        let sections = [
+            ClaudeMdSection { id: "a", name: "API --> notes", content: "First" },
+            ClaudeMdSection { id: "b", name: "B", content: "Second" },
+        ];
+        let composed = compose_sections(&sections);
+        assert!(composed.starts_with("<!-- ensemble:section id=\"a\" name=\"API -> notes\" -->\n"));
+        for line in composed.lines().filter(|l| l.starts_with("<!-- ensemble:section")) {
+            let name = line.split("name=\"").nth(1).and_then(|rest| rest.split('"').next()).unwrap();
+            assert!(!name.contains("--"), "{}", line);
+        }
+        assert_eq!(
+            split_sections(&composed).unwrap(),
+            vec![("a".to_string(), "First".to_string()), ("b".to_string(), "Second".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_split_sections_roundtrip() {
+        let sections = [
+            ClaudeMdSection { id: "a", name: "A", content: "First\n\nmore" },
+            ClaudeMdSection { id: "b", name: "B", content: "Second" },
+        ];
+        assert_eq!(
+            split_sections(&compose_sections(&sections)).unwrap(),
+            vec![
+                ("a".to_string(), "First\n\nmore".to_string()),
+                ("b".to_string(), "Second".to_string()),
+            ]
+        );
+        assert_eq!(split_sections("plain"), None);
+    }
+
+    #[test]
+    fn test_apply_managed_region_new_file() {
+        assert_eq!(
+            apply_managed_region(None, "Rules\n"),
+            format!("{}\nRules\n{}\n", MANAGED_BEGIN, MANAGED_END)
+        );
+    }
+
+    #[test]
+    fn test_apply_managed_region_appends_after_user_text() {
+        let result = apply_managed_region(Some("# My notes\n"), "Rules");
+        assert_eq!(result, format!("# My notes\n\n{}\nRules\n{}\n", MANAGED_BEGIN, MANAGED_END));
+    }
+
+    #[test]
+    fn test_apply_managed_region_replaces_only_region() {
+        let existing = format!("# Top\n\n{}\nold\n{}\n\n# Bottom\n", MANAGED_BEGIN, MANAGED_END);
+        let result = apply_managed_region(Some(&existing), "new");
+        assert_eq!(result, format!("# Top\n\n{}\nnew\n{}\n\n# Bottom\n", MANAGED_BEGIN, MANAGED_END));
+    }
+
+    #[test]
+    fn test_remove_managed_region_keeps_user_text() {
+        let existing = format!("# Top\n\n{}\nold\n{}\n\n# Bottom\n", MANAGED_BEGIN, MANAGED_END);
+        assert_eq!(remove_managed_region(&existing).unwrap(), "# Top\n\n# Bottom\n");
+        assert_eq!(remove_managed_region(&wrap_managed_region("only")).unwrap(), "");
+        assert_eq!(remove_managed_region("# Only user text"), None);
+    }
+
+    #[test]
+    fn test_managed_region_content() {
+        let existing = format!("# Top\n{}\nRules\n\n{}\n", MANAGED_BEGIN, MANAGED_END);
+        assert_eq!(managed_region_content(&existing), Some("Rules"));
+        assert_eq!(managed_region_content("# Top"), None);
+    }
+
+    #[test]
+    fn test_find_import_references_skips_code_and_mentions() {
+        let content = "See @docs/style.md and @README.md.\n\
+                       Ask @alice or mail bob@example.com.\n\
+                       Inline `@not/this.md` code.\n\
+                       ```\n@also/not.md\n```\n\
+                       @~/.claude/personal.md\n\
+                       Again @docs/style.md\n";
+        assert_eq!(
+            find_import_references(content),
+            vec!["docs/style.md", "README.md", "~/.claude/personal.md"]
+        );
+    }
+
+    #[test]
+    fn test_rewrite_import_references() {
+        let result = rewrite_import_references("Use @a.md, not @b.md\n", |r| {
+            (r == "a.md").then(|| "@imports/a.md".to_string())
+        });
+        assert_eq!(result, "Use @imports/a.md, not @b.md\n");
+    }
+}
@@ -0,0 +1,172 @@
+/// Above this many (old lines × new lines) the LCS table is skipped and the
+/// whole file is reported as replaced, to keep memory bounded.
+const MAX_LCS_CELLS: usize = 4_000_000;
+
+/// Lines of unchanged context around each hunk
+const CONTEXT_LINES: usize = 3;
+
+/// A single line in a line-based diff
+#[derive(Debug, Clone, PartialEq)]
+pub enum DiffLine {
+    Same(String),
+    Added(String),
+    Removed(String),
+}
+
+/// Compute a line diff between two texts (longest common subsequence)
+pub fn diff_lines(old: &str, new: &str) -> Vec<DiffLine> {
+    let old_lines: Vec<&str> = old.lines().collect();
+    let new_lines: Vec<&str> = new.lines().collect();
+    let (n, m) = (old_lines.len(), new_lines.len());
+
+    if n.saturating_mul(m) > MAX_LCS_CELLS {
+        let mut result: Vec<DiffLine> =
+            old_lines.iter().map(|l| DiffLine::Removed(l.to_string())).collect();
+        result.extend(new_lines.iter().map(|l| DiffLine::Added(l.to_string())));
+        return result;
+    }
+
+    // lcs[i][j] = LCS length of old_lines[i..] and new_lines[j..]
+    let mut lcs = vec![vec![0u32; m + 1]; n + 1];
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            lcs[i][j] = if old_lines[i] == new_lines[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut result = Vec::with_capacity(n.max(m));
+    let (mut i, mut j) = (0, 0);
+    while i < n && j < m {
+        if old_lines[i] == new_lines[j] {
+            result.push(DiffLine::Same(old_lines[i].to_string()));
+            i += 1;
+            j += 1;
+        } else if lcs[i + 1][j] >= lcs[i][j + 1] {
+            result.push(DiffLine::Removed(old_lines[i].to_string()));
+            i += 1;
+        } else {
+            result.push(DiffLine::Added(new_lines[j].to_string()));
+            j += 1;
+        }
+    }
+    result.extend(old_lines[i..].iter().map(|l| DiffLine::Removed(l.to_string())));
+    result.extend(new_lines[j..].iter().map(|l| DiffLine::Added(l.to_string())));
+    result
+}
+
+/// Render a unified diff (without file headers); empty when texts are equal
+pub fn unified_diff(old: &str, new: &str) -> String {
+    let lines = diff_lines(old, new);
+    if lines.iter().all(|l| matches!(l, DiffLine::Same(_))) {
+        return String::new();
+    }
+
+    // Mark every line within CONTEXT_LINES of a change as visible
+    let changed: Vec<usize> = lines
+        .iter()
+        .enumerate()
+        .filter(|(_, l)| !matches!(l, DiffLine::Same(_)))
+        .map(|(idx, _)| idx)
+        .collect();
+    let mut visible = vec![false; lines.len()];
+    for idx in changed {
+        let start = idx.saturating_sub(CONTEXT_LINES);
+        let end = (idx + CONTEXT_LINES + 1).min(lines.len());
+        for v in visible.iter_mut().take(end).skip(start) {
+            *v = true;
+        }
+    }
+
+    let mut output = String::new();
+    let (mut old_no, mut new_no) = (1usize, 1usize);
+    let mut idx = 0;
+    while idx < lines.len() {
+        if !visible[idx] {
+            match lines[idx] {
+                DiffLine::Same(_) => {
+                    old_no += 1;
+                    new_no += 1;
+                }
+                DiffLine::Removed(_) => old_no += 1,
+                DiffLine::Added(_) => new_no += 1,
+            }
+            idx += 1;
+            continue;
+        }
+
+        // Collect one hunk of consecutive visible lines
+        let hunk_start = idx;
+        while idx < lines.len() && visible[idx] {
+            idx += 1;
+        }
+        let hunk = &lines[hunk_start..idx];
+        let old_count = hunk.iter().filter(|l| !matches!(l, DiffLine::Added(_))).count();
+        let new_count = hunk.iter().filter(|l| !matches!(l, DiffLine::Removed(_))).count();
+        output.push_str(&format!(
+            "@@ -{},{} +{},{} @@\n",
+            if old_count == 0 { old_no - 1 } else { old_no },
+            old_count,
+            if new_count == 0 { new_no - 1 } else { new_no },
+            new_count
+        ));
+        for line in hunk {
+            match line {
+                DiffLine::Same(text) => output.push_str(&format!(" {}\n", text)),
+                DiffLine::Removed(text) => output.push_str(&format!("-{}\n", text)),
+                DiffLine::Added(text) => output.push_str(&format!("+{}\n", text)),
+            }
+        }
+        old_no += old_count;
+        new_no += new_count;
+    }
+
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diff_lines_identical() {
+        let lines = diff_lines("a\nb\n", "a\nb\n");
+        assert_eq!(lines, vec![DiffLine::Same("a".into()), DiffLine::Same("b".into())]);
+    }
+
+    #[test]
+    fn test_diff_lines_insert_and_remove() {
+        let lines = diff_lines("a\nb\nc", "a\nc\nd");
+        assert_eq!(
+            lines,
+            vec![
+                DiffLine::Same("a".into()),
+                DiffLine::Removed("b".into()),
+                DiffLine::Same("c".into()),
+                DiffLine::Added("d".into()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_unified_diff_empty_when_equal() {
+        assert_eq!(unified_diff("same\n", "same\n"), "");
+    }
+
+    #[test]
+    fn test_unified_diff_hunk_header_and_context() {
+        let old = "1\n2\n3\n4\n5\n6\n7\n8\n9\n10\n";
+        let new = "1\n2\n3\n4\n5\nsix\n7\n8\n9\n10\n";
+        let diff = unified_diff(old, new);
+        assert_eq!(diff, "@@ -3,7 +3,7 @@\n 3\n 4\n 5\n-6\n+six\n 7\n 8\n 9\n");
+    }
+
+    #[test]
+    fn test_unified_diff_from_empty() {
+        let diff = unified_diff("", "new line\n");
+        assert_eq!(diff, "@@ -0,0 +1,1 @@\n+new line\n");
+    }
+}
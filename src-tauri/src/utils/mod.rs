@@ -1,5 +1,8 @@
+pub mod claude_md;
+pub mod diff;
 pub mod parser;
 pub mod path;
+pub mod template;
 
 pub use parser::*;
 pub use path::*;
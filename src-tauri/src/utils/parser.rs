@@ -8,6 +8,12 @@ pub struct SkillFrontmatter {
     pub name: Option<String>,
     pub description: Option<String>,
     pub allowed_tools: Option<Vec<String>>,
+    /// Subagent `tools` list
+    pub tools: Option<Vec<String>>,
+    /// Subagent or slash command `model`
+    pub model: Option<String>,
+    /// Slash command `argument-hint`
+    pub argument_hint: Option<String>,
     pub license: Option<String>,
     pub metadata: HashMap<String, String>,
 }
@@ -66,15 +72,10 @@ fn parse_yaml_frontmatter(yaml: &str) -> SkillFrontmatter {
                     "name" => frontmatter.name = Some(value),
                     "description" => frontmatter.description = Some(value),
                     "license" => frontmatter.license = Some(value),
-                    "allowed-tools" => {
-                        frontmatter.allowed_tools = Some(
-                            value
-                                .split(',')
-                                .map(|s| s.trim().to_string())
-                                .filter(|s| !s.is_empty())
-                                .collect(),
-                        );
-                    }
+                    "allowed-tools" => frontmatter.allowed_tools = Some(split_list(&value)),
+                    "tools" => frontmatter.tools = Some(split_list(&value)),
+                    "model" => frontmatter.model = Some(value),
+                    "argument-hint" => frontmatter.argument_hint = Some(value),
                     _ => {}
                 }
             }
@@ -84,6 +85,15 @@ fn parse_yaml_frontmatter(yaml: &str) -> SkillFrontmatter {
     frontmatter
 }
 
+/// Split a comma-separated frontmatter list
+fn split_list(value: &str) -> Vec<String> {
+    value
+        .split(',')
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+        .collect()
+}
+
 /// Parse a simple key: value line
 fn parse_key_value(line: &str) -> Option<(String, String)> {
     if let Some(colon_pos) = line.find(':') {
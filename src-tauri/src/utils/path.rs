@@ -83,6 +83,30 @@ pub fn get_relative_path(path: &Path, base: &Path) -> Option<PathBuf> {
     path.strip_prefix(base).ok().map(|p| p.to_path_buf())
 }
 
+/// Find the root of the git repository containing `path` (the nearest
+/// ancestor with a `.git` entry)
+pub fn find_repo_root(path: &Path) -> Option<PathBuf> {
+    path.ancestors()
+        .find(|dir| dir.join(".git").exists())
+        .map(|dir| dir.to_path_buf())
+}
+
+/// Validate a project-relative subdirectory ("" for the project root)
+///
+/// Absolute paths and `..` components are rejected so targets stay inside
+/// the project.
+pub fn validate_subdirectory(subdir: &str) -> Result<PathBuf, String> {
+    let path = PathBuf::from(subdir.trim().trim_matches('/'));
+    let inside = path
+        .components()
+        .all(|c| matches!(c, std::path::Component::Normal(_) | std::path::Component::CurDir));
+    if inside {
+        Ok(path)
+    } else {
+        Err(format!("Subdirectory must be relative to the project: {}", subdir))
+    }
+}
+
 /// Check if a path is a symlink
 pub fn is_symlink(path: &Path) -> bool {
     path.symlink_metadata()
@@ -90,6 +114,13 @@ pub fn is_symlink(path: &Path) -> bool {
         .unwrap_or(false)
 }
 
+/// Check if a path is a symlink pointing into `dir`
+pub fn is_symlink_into(path: &Path, dir: &Path) -> bool {
+    std::fs::read_link(path)
+        .map(|target| target.starts_with(dir))
+        .unwrap_or(false)
+}
+
 /// Get symlink target
 pub fn get_symlink_target(path: &Path) -> Option<PathBuf> {
     std::fs::read_link(path).ok()
@@ -301,4 +332,22 @@ mod tests {
         assert_eq!(get_config_path(), get_settings_file_path());
         assert_eq!(get_ensemble_dir(), get_app_data_dir());
     }
+
+    #[test]
+    fn test_find_repo_root() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let nested = tmp.path().join("repo/packages/api");
+        std::fs::create_dir_all(&nested).unwrap();
+        std::fs::create_dir_all(tmp.path().join("repo/.git")).unwrap();
+        assert_eq!(find_repo_root(&nested), Some(tmp.path().join("repo")));
+        assert_eq!(find_repo_root(tmp.path()), None);
+    }
+
+    #[test]
+    fn test_validate_subdirectory() {
+        assert_eq!(validate_subdirectory("packages/api/").unwrap(), PathBuf::from("packages/api"));
+        assert_eq!(validate_subdirectory("").unwrap(), PathBuf::new());
+        assert!(validate_subdirectory("../outside").is_err());
+        assert!(validate_subdirectory("a/../../b").is_err());
+    }
 }
@@ -0,0 +1,257 @@
+use std::collections::HashMap;
+
+/// A parsed template token
+#[derive(Debug, PartialEq)]
+enum Token {
+    Text(String),
+    /// `{{name}}` (raw tag kept for unknown variables)
+    Var { name: String, raw: String },
+    /// `{{#if expr}}`
+    If(String),
+    /// `{{else}}`
+    Else,
+    /// `{{/if}}`
+    EndIf,
+}
+
+/// Template syntax tree
+#[derive(Debug)]
+enum Node {
+    Text(String),
+    Var { name: String, raw: String },
+    If { expr: String, then: Vec<Node>, otherwise: Vec<Node> },
+}
+
+/// Split a template into tokens
+///
+/// `\{{` produces a literal `{{`. Block tags (`#if`, `else`, `/if`) that sit
+/// alone on a line consume that line, so they do not leave blank lines behind.
+fn tokenize(input: &str) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    let mut text = String::new();
+    let mut rest = input;
+
+    while let Some(pos) = rest.find("{{") {
+        if pos > 0 && rest.as_bytes()[pos - 1] == b'\\' {
+            text.push_str(&rest[..pos - 1]);
+            text.push_str("{{");
+            rest = &rest[pos + 2..];
+            continue;
+        }
+        let Some(close) = rest[pos + 2..].find("}}") else {
+            break;
+        };
+        text.push_str(&rest[..pos]);
+        let raw = &rest[pos..pos + 2 + close + 2];
+        let inner = rest[pos + 2..pos + 2 + close].trim();
+        rest = &rest[pos + 2 + close + 2..];
+
+        let block = if let Some(expr) = inner.strip_prefix("#if ") {
+            Some(Token::If(expr.trim().to_string()))
+        } else if inner == "else" {
+            Some(Token::Else)
+        } else if inner == "/if" {
+            Some(Token::EndIf)
+        } else {
+            None
+        };
+
+        match block {
+            Some(token) => {
+                // Standalone block tag: drop its indentation and trailing newline
+                let line_start = text.rfind('\n').map(|i| i + 1).unwrap_or(0);
+                let line_end = rest.find('\n');
+                let alone_before = text[line_start..].trim().is_empty();
+                let alone_after = rest[..line_end.unwrap_or(rest.len())].trim().is_empty();
+                if alone_before && alone_after {
+                    text.truncate(line_start);
+                    rest = &rest[line_end.map(|i| i + 1).unwrap_or(rest.len())..];
+                }
+                if !text.is_empty() {
+                    tokens.push(Token::Text(std::mem::take(&mut text)));
+                }
+                tokens.push(token);
+            }
+            None => {
+                if !text.is_empty() {
+                    tokens.push(Token::Text(std::mem::take(&mut text)));
+                }
+                tokens.push(Token::Var {
+                    name: inner.to_string(),
+                    raw: raw.to_string(),
+                });
+            }
+        }
+    }
+
+    text.push_str(rest);
+    if !text.is_empty() {
+        tokens.push(Token::Text(text));
+    }
+    tokens
+}
+
+/// Build the syntax tree; unbalanced tags are reported in `warnings`
+fn parse(tokens: Vec<Token>, warnings: &mut Vec<String>) -> Vec<Node> {
+    // Stack of open blocks: (expr, then-branch, else-branch, in_else)
+    let mut stack: Vec<(String, Vec<Node>, Vec<Node>, bool)> = Vec::new();
+    let mut root: Vec<Node> = Vec::new();
+
+    fn current<'a>(
+        root: &'a mut Vec<Node>,
+        stack: &'a mut [(String, Vec<Node>, Vec<Node>, bool)],
+    ) -> &'a mut Vec<Node> {
+        match stack.last_mut() {
+            Some((_, then, otherwise, in_else)) => {
+                if *in_else {
+                    otherwise
+                } else {
+                    then
+                }
+            }
+            None => root,
+        }
+    }
+
+    for token in tokens {
+        match token {
+            Token::Text(text) => current(&mut root, &mut stack).push(Node::Text(text)),
+            Token::Var { name, raw } => current(&mut root, &mut stack).push(Node::Var { name, raw }),
+            Token::If(expr) => stack.push((expr, Vec::new(), Vec::new(), false)),
+            Token::Else => match stack.last_mut() {
+                Some(block) => block.3 = true,
+                None => warnings.push("{{else}} without matching {{#if}}".to_string()),
+            },
+            Token::EndIf => match stack.pop() {
+                Some((expr, then, otherwise, _)) => {
+                    current(&mut root, &mut stack).push(Node::If { expr, then, otherwise })
+                }
+                None => warnings.push("{{/if}} without matching {{#if}}".to_string()),
+            },
+        }
+    }
+
+    // Close unterminated blocks at the end of the input
+    while let Some((expr, then, otherwise, _)) = stack.pop() {
+        warnings.push(format!("Unclosed {{{{#if {}}}}}", expr));
+        current(&mut root, &mut stack).push(Node::If { expr, then, otherwise });
+    }
+
+    root
+}
+
+fn is_truthy(value: Option<&String>) -> bool {
+    match value {
+        Some(v) => !v.is_empty() && v != "false" && v != "0",
+        None => false,
+    }
+}
+
+/// Evaluate a condition: `name`, `!name`, `name == "value"`, `name != "value"`
+fn evaluate(expr: &str, vars: &HashMap<String, String>) -> bool {
+    for (op, negate) in [("==", false), ("!=", true)] {
+        if let Some((name, value)) = expr.split_once(op) {
+            let expected = value.trim().trim_matches('"').trim_matches('\'');
+            let matches = vars.get(name.trim()).map(|v| v == expected).unwrap_or(false);
+            return matches != negate;
+        }
+    }
+    match expr.strip_prefix('!') {
+        Some(name) => !is_truthy(vars.get(name.trim())),
+        None => is_truthy(vars.get(expr)),
+    }
+}
+
+fn render_nodes(
+    nodes: &[Node],
+    vars: &HashMap<String, String>,
+    output: &mut String,
+    warnings: &mut Vec<String>,
+) {
+    for node in nodes {
+        match node {
+            Node::Text(text) => output.push_str(text),
+            Node::Var { name, raw } => match vars.get(name) {
+                Some(value) => output.push_str(value),
+                None => {
+                    // Unknown variables are left untouched so nothing is silently lost
+                    output.push_str(raw);
+                    let warning = format!("Unknown variable: {}", name);
+                    if !warnings.contains(&warning) {
+                        warnings.push(warning);
+                    }
+                }
+            },
+            Node::If { expr, then, otherwise } => {
+                let branch = if evaluate(expr, vars) { then } else { otherwise };
+                render_nodes(branch, vars, output, warnings);
+            }
+        }
+    }
+}
+
+/// Render a template with `{{variable}}` substitution and
+/// `{{#if cond}}...{{else}}...{{/if}}` conditionals
+///
+/// Returns the rendered text and warnings (unknown variables, unbalanced tags).
+pub fn render_template(input: &str, vars: &HashMap<String, String>) -> (String, Vec<String>) {
+    if !input.contains("{{") {
+        return (input.to_string(), Vec::new());
+    }
+
+    let mut warnings = Vec::new();
+    let nodes = parse(tokenize(input), &mut warnings);
+    let mut output = String::with_capacity(input.len());
+    render_nodes(&nodes, vars, &mut output, &mut warnings);
+    (output, warnings)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vars() -> HashMap<String, String> {
+        HashMap::from([
+            ("project.name".to_string(), "ensemble".to_string()),
+            ("language".to_string(), "rust".to_string()),
+            ("test_command".to_string(), "cargo test".to_string()),
+            ("legacy".to_string(), "false".to_string()),
+        ])
+    }
+
+    #[test]
+    fn test_render_variables() {
+        let (out, warnings) = render_template("# {{project.name}}\nRun `{{ test_command }}`.", &vars());
+        assert_eq!(out, "# ensemble\nRun `cargo test`.");
+        assert!(warnings.is_empty());
+    }
+
+    #[test]
+    fn test_unknown_variable_is_kept_and_reported() {
+        let (out, warnings) = render_template("Path: {{project.path}}", &vars());
+        assert_eq!(out, "Path: {{project.path}}");
+        assert_eq!(warnings, vec!["Unknown variable: project.path"]);
+    }
+
+    #[test]
+    fn test_conditionals_with_else_and_comparison() {
+        let template = "Intro\n{{#if language == \"rust\"}}\nUse clippy.\n{{else}}\nUse a linter.\n{{/if}}\n{{#if legacy}}\nLegacy!\n{{/if}}\n{{#if !legacy}}\nModern.\n{{/if}}\nEnd";
+        let (out, warnings) = render_template(template, &vars());
+        assert_eq!(out, "Intro\nUse clippy.\nModern.\nEnd");
+        assert!(warnings.is_empty());
+    }
+
+    #[test]
+    fn test_nested_and_inline_conditionals() {
+        let template = "a {{#if language}}b {{#if missing}}x{{else}}c{{/if}}{{/if}} d";
+        let (out, _) = render_template(template, &vars());
+        assert_eq!(out, "a b c d");
+    }
+
+    #[test]
+    fn test_escaped_braces_and_unbalanced_tags() {
+        let (out, warnings) = render_template("\\{{project.name}} {{/if}}", &vars());
+        assert_eq!(out, "{{project.name}} ");
+        assert_eq!(warnings, vec!["{{/if}} without matching {{#if}}"]);
+    }
+}
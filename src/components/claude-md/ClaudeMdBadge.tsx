@@ -1,7 +1,7 @@
 // src/components/claude-md/ClaudeMdBadge.tsx
 
 import React from 'react';
-import { Globe, Folder, User } from 'lucide-react';
+import { Globe, Folder, FolderTree, User } from 'lucide-react';
 import type { ClaudeMdType } from '@/types/claudeMd';
 
 /**
@@ -20,6 +20,7 @@ interface ClaudeMdBadgeProps {
  * - GLOBAL: #7C3AED (紫色) + globe 图标
  * - PROJECT: #0EA5E9 (青色) + folder 图标
  * - LOCAL: #F59E0B (橙色) + user 图标
+ * - SUBDIRECTORY: #14B8A6 (青绿色) + folder-tree 图标
  * - Size: 16x16
  * - cornerRadius: 8px (圆形)
  * - Border: 2px white
@@ -41,6 +42,10 @@ const badgeConfig: Record<
     bgColor: '#F59E0B',
     Icon: User,
   },
+  subdirectory: {
+    bgColor: '#14B8A6',
+    Icon: FolderTree,
+  },
 };
 
 /**
@@ -51,6 +56,7 @@ const badgeConfig: Record<
  * - Global: purple (#7C3AED) + globe icon
  * - Project: cyan (#0EA5E9) + folder icon
  * - Local: orange (#F59E0B) + user icon
+ * - Subdirectory: teal (#14B8A6) + folder-tree icon
  *
  * Design specs (from P3AWE):
  * - Size: 16x16
@@ -46,6 +46,8 @@ function getTypeLabel(type: ClaudeMdType): string {
       return 'Project Configuration';
     case 'local':
       return 'Local Configuration';
+    case 'subdirectory':
+      return 'Subdirectory Configuration';
     default:
       return 'Configuration';
   }
@@ -121,7 +123,7 @@ export function ClaudeMdDetailPanel({ file, isOpen, onClose }: ClaudeMdDetailPan
     files,
     updateFile,
     setGlobal,
-    unsetGlobal,
+    removeGlobal,
     isSetting,
   } = useClaudeMdStore();
 
@@ -244,7 +246,7 @@ export function ClaudeMdDetailPanel({ file, isOpen, onClose }: ClaudeMdDetailPan
     if (enabled) {
       await setGlobal(selectedFile.id);
     } else {
-      await unsetGlobal();
+      await removeGlobal(selectedFile.id);
     }
   };
 
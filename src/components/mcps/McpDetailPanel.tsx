@@ -22,6 +22,7 @@ import { IconPicker, ICON_MAP, Dropdown, ScopeSelector, Button } from '@/compone
 import { useMcpsStore } from '@/stores/mcpsStore';
 import { useAppStore } from '@/stores/appStore';
 import { useScenesStore } from '@/stores/scenesStore';
+import { McpHealthSection } from './McpHealthSection';
 import { safeInvoke } from '@/utils/tauri';
 import type { McpServer, Tool } from '@/types';
 
@@ -523,6 +524,9 @@ export function McpDetailPanel({ mcp, isOpen, onClose }: McpDetailPanelProps) {
         </div>
       </section>
 
+      {/* Health Section */}
+      <McpHealthSection name={selectedMcp.name} />
+
       {/* Source Configuration Section */}
       <section className="flex flex-col gap-4">
         <h3 className="text-sm font-semibold text-[#18181B]">
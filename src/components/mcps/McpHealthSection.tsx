@@ -0,0 +1,137 @@
+import React, { useEffect } from 'react';
+import { Activity, AlertCircle } from 'lucide-react';
+import { useMcpsStore } from '@/stores/mcpsStore';
+import type { McpHealth } from '@/types';
+
+// ============================================================================
+// Helpers
+// ============================================================================
+
+const formatRate = (rate: number): string => `${(rate * 100).toFixed(1)}%`;
+
+// Latencies come from buckets, so they are upper bounds
+const formatLatency = (ms: number | null): string => {
+  if (ms === null) return '—';
+  return ms >= 1000 ? `≤ ${(ms / 1000).toFixed(1)} s` : `≤ ${ms} ms`;
+};
+
+const formatTimestamp = (timestamp: string | null): string => {
+  if (!timestamp) return 'Unknown time';
+  const date = new Date(timestamp);
+  return isNaN(date.getTime()) ? timestamp : date.toLocaleString('en-US');
+};
+
+// ============================================================================
+// McpHealthSection Component
+// ============================================================================
+
+export interface McpHealthSectionProps {
+  /** MCP server name */
+  name: string;
+}
+
+// Error rate, latency and recent errors of an MCP server, from the
+// transcripts under the Claude config directory
+export const McpHealthSection: React.FC<McpHealthSectionProps> = ({ name }) => {
+  const loadMcpHealth = useMcpsStore((state) => state.loadMcpHealth);
+  const health: McpHealth | null | undefined = useMcpsStore((state) => state.mcpHealth[name]);
+
+  useEffect(() => {
+    loadMcpHealth(name);
+  }, [name, loadMcpHealth]);
+
+  return (
+    <section className="flex flex-col gap-4">
+      <h3 className="text-sm font-semibold text-[#18181B]">Health</h3>
+      {!health ? (
+        <div className="flex items-center gap-3 rounded-lg border border-[#E5E5E5] px-3.5 py-3">
+          <div className="flex h-8 w-8 shrink-0 items-center justify-center rounded-md bg-[#F4F4F5]">
+            <Activity className="h-3.5 w-3.5 text-[#A1A1AA]" />
+          </div>
+          <span className="text-[13px] text-[#71717A]">
+            {health === undefined ? 'Loading…' : 'No completed calls found in transcripts'}
+          </span>
+        </div>
+      ) : (
+        <>
+          {/* Summary Row */}
+          <div className="flex gap-8">
+            <div className="flex flex-1 flex-col gap-1">
+              <span className="text-[11px] font-medium text-[#71717A]">Error Rate</span>
+              <span className="text-[13px] font-medium text-[#18181B]">
+                {formatRate(health.error_rate)} ({health.errors} of {health.calls.toLocaleString()})
+              </span>
+            </div>
+            <div className="flex flex-1 flex-col gap-1">
+              <span className="text-[11px] font-medium text-[#71717A]">Median Latency</span>
+              <span className="text-[13px] font-medium text-[#18181B]">
+                {formatLatency(health.median_latency_ms)}
+              </span>
+            </div>
+            <div className="flex flex-1 flex-col gap-1">
+              <span className="text-[11px] font-medium text-[#71717A]">P90 Latency</span>
+              <span className="text-[13px] font-medium text-[#18181B]">
+                {formatLatency(health.p90_latency_ms)}
+              </span>
+            </div>
+          </div>
+
+          {/* Per-tool Table */}
+          {health.tools.length > 0 && (
+            <div className="overflow-hidden rounded-lg border border-[#E5E5E5]">
+              {health.tools.map((tool, index) => (
+                <div
+                  key={tool.tool}
+                  className={`flex items-center gap-3 px-3.5 py-2.5 ${
+                    index < health.tools.length - 1 ? 'border-b border-[#E5E5E5]' : ''
+                  }`}
+                >
+                  <span className="flex-1 truncate text-[13px] font-medium text-[#18181B]">
+                    {tool.tool}
+                  </span>
+                  <span className="w-28 text-right text-xs text-[#71717A]">
+                    {tool.calls.toLocaleString()} calls
+                  </span>
+                  <span
+                    className={`w-20 text-right text-xs ${
+                      tool.errors > 0 ? 'text-[#DC2626]' : 'text-[#71717A]'
+                    }`}
+                  >
+                    {formatRate(tool.error_rate)}
+                  </span>
+                  <span className="w-20 text-right text-xs text-[#71717A]">
+                    {formatLatency(tool.median_latency_ms)}
+                  </span>
+                </div>
+              ))}
+            </div>
+          )}
+
+          {/* Recent Errors */}
+          {health.recent_errors.length > 0 && (
+            <div className="overflow-hidden rounded-lg border border-[#E5E5E5]">
+              {health.recent_errors.map((error, index) => (
+                <div
+                  key={`${error.timestamp}-${index}`}
+                  className={`flex items-start gap-3 px-3.5 py-3 ${
+                    index < health.recent_errors.length - 1 ? 'border-b border-[#E5E5E5]' : ''
+                  }`}
+                >
+                  <AlertCircle className="mt-0.5 h-3.5 w-3.5 shrink-0 text-[#DC2626]" />
+                  <div className="flex min-w-0 flex-1 flex-col gap-0.5">
+                    <span className="text-[11px] font-medium text-[#71717A]">
+                      {error.tool ?? 'Unknown tool'} · {formatTimestamp(error.timestamp)}
+                    </span>
+                    <span className="break-words text-xs text-[#18181B]">{error.message}</span>
+                  </div>
+                </div>
+              ))}
+            </div>
+          )}
+        </>
+      )}
+    </section>
+  );
+};
+
+export default McpHealthSection;
@@ -2,3 +2,4 @@
 export { McpItem, McpItemCompact } from './McpItem';
 export { McpDetailPanel } from './McpDetailPanel';
 export type { McpDetailPanelProps } from './McpDetailPanel';
+export { McpHealthSection } from './McpHealthSection';
@@ -1,11 +1,11 @@
 import { useEffect, useCallback, useRef, useState, useMemo } from 'react';
 import { createPortal } from 'react-dom';
-import { X, Check, Info, Globe, Folder, User, Loader2 } from 'lucide-react';
+import { X, Check, Info, Globe, Folder, FolderTree, User, Loader2 } from 'lucide-react';
 import { useClaudeMdStore } from '@/stores/claudeMdStore';
 import { Tooltip } from '@/components/common/Tooltip';
 import type { ClaudeMdScanItem, ClaudeMdType } from '@/types/claudeMd';
 
-type TabType = 'user' | 'project' | 'local';
+type TabType = 'user' | 'project' | 'local' | 'package';
 
 interface ScanClaudeMdModalProps {
   isOpen: boolean;
@@ -42,6 +42,7 @@ export function ScanClaudeMdModal({
   const {
     scanFiles,
     scanResult,
+    scanProgress,
     isScanning,
     isImporting,
     importFile,
@@ -59,6 +60,7 @@ export function ScanClaudeMdModal({
         user: 'global',
         project: 'project',
         local: 'local',
+        package: 'subdirectory',
       };
       return unimportedItems.filter((item) => item.type === typeMap[type]);
     },
@@ -69,6 +71,7 @@ export function ScanClaudeMdModal({
   const userCount = getItemsByType('user').length;
   const projectCount = getItemsByType('project').length;
   const localCount = getItemsByType('local').length;
+  const packageCount = getItemsByType('package').length;
 
   // Current tab items
   const currentItems = getItemsByType(activeTab);
@@ -344,6 +347,39 @@ export function ScanClaudeMdModal({
                 {isScanning ? '-' : localCount}
               </span>
             </button>
+            {/* Package Tab (subdirectory CLAUDE.md files, e.g. monorepo packages) */}
+            <button
+              onClick={() => setActiveTab('package')}
+              className={`flex items-center gap-2 py-3 px-4 border-b-2 transition-colors ${
+                activeTab === 'package'
+                  ? 'border-[#18181B]'
+                  : 'border-transparent'
+              }`}
+            >
+              <FolderTree
+                className={`w-3.5 h-3.5 ${
+                  activeTab === 'package' ? 'text-[#18181B]' : 'text-[#71717A]'
+                }`}
+              />
+              <span
+                className={`text-[13px] ${
+                  activeTab === 'package'
+                    ? 'font-semibold text-[#18181B]'
+                    : 'font-normal text-[#71717A]'
+                }`}
+              >
+                Package
+              </span>
+              <span
+                className={`rounded-[10px] px-2 py-0.5 text-[11px] font-medium ${
+                  activeTab === 'package'
+                    ? 'bg-[#F4F4F5] text-[#52525B]'
+                    : 'bg-[#F4F4F5] text-[#A1A1AA]'
+                }`}
+              >
+                {isScanning ? '-' : packageCount}
+              </span>
+            </button>
           </div>
 
           {/* Right side: Divider + Count + Divider + All Checkbox */}
@@ -380,13 +416,22 @@ export function ScanClaudeMdModal({
               <Loader2 className="h-8 w-8 animate-spin text-[#71717A]" />
               <div className="text-center">
                 <p className="text-[14px] font-medium text-[#18181B]">Scanning system...</p>
-                <p className="text-[13px] text-[#71717A]">Looking for CLAUDE.md files</p>
+                <p className="text-[13px] text-[#71717A]">
+                  {scanProgress
+                    ? `${scanProgress.scannedDirs.toLocaleString()} directories scanned, ${scanProgress.found} found`
+                    : 'Looking for CLAUDE.md files'}
+                </p>
+                {scanProgress?.currentPath && (
+                  <p className="max-w-[440px] truncate text-[12px] text-[#A1A1AA]">
+                    {scanProgress.currentPath}
+                  </p>
+                )}
               </div>
             </div>
           ) : currentItems.length === 0 ? (
             <div className="flex items-center justify-center h-full">
               <span className="text-[13px] text-[#71717A]">
-                No {activeTab === 'user' ? 'user' : activeTab === 'project' ? 'project' : 'local'} files found
+                No {activeTab === 'user' ? 'user' : activeTab === 'project' ? 'project' : activeTab === 'local' ? 'local' : 'package'} files found
               </span>
             </div>
           ) : (
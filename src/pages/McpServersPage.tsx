@@ -31,6 +31,7 @@ import {
   Button,
 } from '@/components/common';
 import { McpListItem } from '@/components/mcps/McpListItem';
+import { McpHealthSection } from '@/components/mcps/McpHealthSection';
 import { ImportMcpModal } from '@/components/modals/ImportMcpModal';
 import { useMcpsStore } from '@/stores/mcpsStore';
 import { useAppStore } from '@/stores/appStore';
@@ -592,6 +593,9 @@ export const McpServersPage: React.FC = () => {
         </div>
       </section>
 
+      {/* Health Section */}
+      <McpHealthSection name={selectedMcp.name} />
+
       {/* Source Configuration Section */}
       <section className="flex flex-col gap-4">
         <h3 className="text-sm font-semibold text-[#18181B]">Source Configuration</h3>
@@ -7,6 +7,44 @@ import { useSettingsStore, useSkillsStore, useMcpsStore } from '@/stores';
 import { useClaudeMdStore } from '@/stores/claudeMdStore';
 import { safeInvoke } from '@/utils/tauri';
 
+interface ListInputProps {
+  value: string[];
+  onChange: (value: string[]) => void;
+  placeholder?: string;
+}
+
+// Comma-separated list, saved when the input loses focus
+function ListInput({ value, onChange, placeholder }: ListInputProps) {
+  const [draft, setDraft] = useState(value.join(', '));
+
+  useEffect(() => {
+    setDraft(value.join(', '));
+  }, [value]);
+
+  const handleBlur = () => {
+    const next = draft
+      .split(',')
+      .map((item) => item.trim())
+      .filter((item) => item.length > 0);
+    if (next.join(',') !== value.join(',')) {
+      onChange(next);
+    } else {
+      setDraft(value.join(', '));
+    }
+  };
+
+  return (
+    <input
+      type="text"
+      value={draft}
+      onChange={(e) => setDraft(e.target.value)}
+      onBlur={handleBlur}
+      placeholder={placeholder}
+      className="h-9 w-[240px] px-3 rounded-md border border-[#E5E5E5] text-[13px] font-mono text-[#18181B] focus:outline-none focus:ring-1 focus:ring-[#18181B]"
+    />
+  );
+}
+
 // ============================================================================
 // Settings Page
 // ============================================================================
@@ -203,10 +241,14 @@ export function SettingsPage() {
     claudeCommand,
     warpOpenMode,
     claudeMdDistributionPath,
+    claudeMdScanRoots,
+    claudeMdScanExcludes,
     setTerminalApp,
     setClaudeCommand,
     setWarpOpenMode,
     setClaudeMdDistributionPath,
+    setClaudeMdScanRoots,
+    setClaudeMdScanExcludes,
   } = useSettingsStore();
 
   // Get reload functions from stores to refresh data after recovery
@@ -268,11 +310,11 @@ export function SettingsPage() {
           <section>
             <SectionHeader
               title="CLAUDE.md"
-              description="Configure how CLAUDE.md files are distributed to projects"
+              description="Configure how CLAUDE.md files are distributed to projects and found by scans"
             />
             <Card>
               {/* Default Distribution Path */}
-              <Row noBorder>
+              <Row>
                 <div className="flex flex-col gap-1">
                   <span className="text-[13px] font-medium text-[#18181B]">
                     Default Distribution Path
@@ -293,6 +335,40 @@ export function SettingsPage() {
                   ]}
                 />
               </Row>
+
+              {/* Scan Directories */}
+              <Row>
+                <div className="flex flex-col gap-0.5">
+                  <span className="text-[13px] font-medium text-[#18181B]">
+                    Scan Directories
+                  </span>
+                  <span className="text-xs text-[#71717A]">
+                    Searched for CLAUDE.md files, comma-separated
+                  </span>
+                </div>
+                <ListInput
+                  value={claudeMdScanRoots}
+                  onChange={setClaudeMdScanRoots}
+                  placeholder="~/Projects, ~/Code"
+                />
+              </Row>
+
+              {/* Scan Excludes */}
+              <Row noBorder>
+                <div className="flex flex-col gap-0.5">
+                  <span className="text-[13px] font-medium text-[#18181B]">
+                    Excluded Directories
+                  </span>
+                  <span className="text-xs text-[#71717A]">
+                    Names skip matching directories anywhere; paths skip that directory
+                  </span>
+                </div>
+                <ListInput
+                  value={claudeMdScanExcludes}
+                  onChange={setClaudeMdScanExcludes}
+                  placeholder="node_modules, target"
+                />
+              </Row>
             </Card>
           </section>
 
@@ -1,15 +1,18 @@
 // src/stores/claudeMdStore.ts
 
 import { create } from 'zustand';
+import { listen, UnlistenFn } from '@tauri-apps/api/event';
 import {
   ClaudeMdFile,
   ClaudeMdScanResult,
+  ClaudeMdScanProgress,
   ClaudeMdScanItem,
   ClaudeMdImportOptions,
   ClaudeMdImportResult,
   ClaudeMdDistributionOptions,
   ClaudeMdDistributionResult,
   SetGlobalResult,
+  GlobalClaudeMdEntry,
 } from '@/types/claudeMd';
 import { ClassifyItem, ClassifyResult } from '@/types';
 import { ICON_NAMES } from '@/components/common/IconPicker';
@@ -30,10 +33,12 @@ interface ClaudeMdFilter {
 interface ClaudeMdState {
   // Data
   files: ClaudeMdFile[];
-  globalFileId: string | null;
+  // Global stack, in the order the files are composed into ~/.claude/CLAUDE.md
+  globalEntries: GlobalClaudeMdEntry[];
 
   // Scan state
   scanResult: ClaudeMdScanResult | null;
+  scanProgress: ClaudeMdScanProgress | null;
   isScanning: boolean;
 
   // Selection
@@ -73,6 +78,9 @@ interface ClaudeMdState {
 
   // Global actions
   setGlobal: (id: string) => Promise<SetGlobalResult | null>;
+  removeGlobal: (id: string) => Promise<void>;
+  toggleGlobal: (id: string, enabled: boolean) => Promise<void>;
+  reorderGlobal: (orderedIds: string[]) => Promise<void>;
   unsetGlobal: () => Promise<void>;
 
   // Distribution actions
@@ -90,7 +98,7 @@ interface ClaudeMdState {
 
   // Computed
   getFilteredFiles: () => ClaudeMdFile[];
-  getGlobalFile: () => ClaudeMdFile | undefined;
+  getGlobalFiles: () => ClaudeMdFile[];
   getNonGlobalFiles: () => ClaudeMdFile[];
   getSelectedFile: () => ClaudeMdFile | undefined;
   getUnimportedScanItems: () => ClaudeMdScanItem[];
@@ -100,6 +108,47 @@ interface ClaudeMdState {
 // Initial State
 // ============================================================================
 
+// Mark the files of the enabled stack entries as global, as the backend does
+const applyGlobalEntries = (
+  files: ClaudeMdFile[],
+  entries: GlobalClaudeMdEntry[]
+): ClaudeMdFile[] => {
+  const enabledIds = new Set(entries.filter((e) => e.enabled).map((e) => e.claudeMdId));
+  return files.map((f) =>
+    f.isGlobal === enabledIds.has(f.id) ? f : { ...f, isGlobal: enabledIds.has(f.id) }
+  );
+};
+
+type SetClaudeMdState = (
+  partial: Partial<ClaudeMdState> | ((state: ClaudeMdState) => Partial<ClaudeMdState>)
+) => void;
+
+// Run a global stack command and apply the stack it returns
+const updateGlobalEntries = async (
+  set: SetClaudeMdState,
+  command: string,
+  args: Record<string, unknown>
+): Promise<void> => {
+  if (!isTauri()) {
+    console.warn('ClaudeMdStore: Cannot update the global stack in browser mode');
+    return;
+  }
+
+  set({ isSetting: true, error: null });
+
+  try {
+    const entries = (await safeInvoke<GlobalClaudeMdEntry[]>(command, args)) || [];
+    set((state) => ({
+      files: applyGlobalEntries(state.files, entries),
+      globalEntries: entries,
+      isSetting: false,
+    }));
+  } catch (error) {
+    const message = typeof error === 'string' ? error : String(error);
+    set({ error: message, isSetting: false });
+  }
+};
+
 const initialFilter: ClaudeMdFilter = {
   search: '',
   categoryId: null,
@@ -114,8 +163,9 @@ const initialFilter: ClaudeMdFilter = {
 export const useClaudeMdStore = create<ClaudeMdState>((set, get) => ({
   // Initial state
   files: [],
-  globalFileId: null,
+  globalEntries: [],
   scanResult: null,
+  scanProgress: null,
   isScanning: false,
   selectedFileId: null,
   filter: initialFilter,
@@ -142,12 +192,14 @@ export const useClaudeMdStore = create<ClaudeMdState>((set, get) => ({
     set({ isLoading: true, error: null });
 
     try {
-      const files = await safeInvoke<ClaudeMdFile[]>('get_claude_md_files');
-      const globalFile = files?.find(f => f.isGlobal);
+      const [files, globalEntries] = await Promise.all([
+        safeInvoke<ClaudeMdFile[]>('get_claude_md_files'),
+        safeInvoke<GlobalClaudeMdEntry[]>('get_global_claude_md_entries'),
+      ]);
 
       set({
         files: files || [],
-        globalFileId: globalFile?.id || null,
+        globalEntries: globalEntries || [],
         isLoading: false,
       });
     } catch (error) {
@@ -157,10 +209,7 @@ export const useClaudeMdStore = create<ClaudeMdState>((set, get) => ({
     }
   },
 
-  setFiles: (files) => {
-    const globalFile = files.find(f => f.isGlobal);
-    set({ files, globalFileId: globalFile?.id || null });
-  },
+  setFiles: (files) => set({ files }),
 
   selectFile: (id) => set({ selectedFileId: id }),
 
@@ -173,9 +222,14 @@ export const useClaudeMdStore = create<ClaudeMdState>((set, get) => ({
       return;
     }
 
-    set({ isScanning: true, error: null });
+    set({ isScanning: true, scanProgress: null, error: null });
 
+    let unlisten: UnlistenFn | undefined;
     try {
+      unlisten = await listen<ClaudeMdScanProgress>('claude-md-scan-progress', (event) => {
+        set({ scanProgress: event.payload });
+      });
+
       const result = await safeInvoke<ClaudeMdScanResult>('scan_claude_md_files', {
         scanPaths,
         includeHome,
@@ -185,6 +239,9 @@ export const useClaudeMdStore = create<ClaudeMdState>((set, get) => ({
     } catch (error) {
       const message = typeof error === 'string' ? error : String(error);
       set({ error: message, isScanning: false });
+    } finally {
+      unlisten?.();
+      set({ scanProgress: null });
     }
   },
 
@@ -287,12 +344,12 @@ export const useClaudeMdStore = create<ClaudeMdState>((set, get) => ({
     // and move to trash without touching ~/.claude/CLAUDE.md
 
     // Optimistic update
-    // If deleting global file, also clear globalFileId
-    const isGlobal = file.isGlobal;
+    // If deleting a global file, also drop it from the global stack
+    const globalEntries = get().globalEntries;
     set((state) => ({
       files: state.files.filter((f) => f.id !== id),
       selectedFileId: state.selectedFileId === id ? null : state.selectedFileId,
-      globalFileId: isGlobal ? null : state.globalFileId,
+      globalEntries: state.globalEntries.filter((e) => e.claudeMdId !== id),
     }));
 
     try {
@@ -303,7 +360,7 @@ export const useClaudeMdStore = create<ClaudeMdState>((set, get) => ({
       set((state) => ({
         files: [...state.files, file],
         error: message,
-        globalFileId: isGlobal ? id : state.globalFileId,
+        globalEntries,
       }));
     }
   },
@@ -343,7 +400,22 @@ export const useClaudeMdStore = create<ClaudeMdState>((set, get) => ({
   },
 
   // ========================================================================
-  // Unset global
+  // Global stack
+  // ========================================================================
+  removeGlobal: async (id) => {
+    await updateGlobalEntries(set, 'remove_global_claude_md', { id });
+  },
+
+  toggleGlobal: async (id, enabled) => {
+    await updateGlobalEntries(set, 'toggle_global_claude_md', { id, enabled });
+  },
+
+  reorderGlobal: async (orderedIds) => {
+    await updateGlobalEntries(set, 'reorder_global_claude_md', { orderedIds });
+  },
+
+  // ========================================================================
+  // Unset global (clears the whole stack)
   // ========================================================================
   unsetGlobal: async () => {
     if (!isTauri()) {
@@ -351,8 +423,7 @@ export const useClaudeMdStore = create<ClaudeMdState>((set, get) => ({
       return;
     }
 
-    const { globalFileId } = get();
-    if (!globalFileId) return;
+    if (get().globalEntries.length === 0) return;
 
     set({ isSetting: true, error: null });
 
@@ -361,11 +432,8 @@ export const useClaudeMdStore = create<ClaudeMdState>((set, get) => ({
 
       // Update local state
       set((state) => ({
-        files: state.files.map((f) => ({
-          ...f,
-          isGlobal: false,
-        })),
-        globalFileId: null,
+        files: applyGlobalEntries(state.files, []),
+        globalEntries: [],
         isSetting: false,
       }));
     } catch (error) {
@@ -587,9 +655,12 @@ export const useClaudeMdStore = create<ClaudeMdState>((set, get) => ({
     return filtered;
   },
 
-  getGlobalFile: () => {
-    const { files, globalFileId } = get();
-    return files.find((f) => f.id === globalFileId);
+  getGlobalFiles: () => {
+    const { files, globalEntries } = get();
+    return globalEntries
+      .filter((e) => e.enabled)
+      .map((e) => files.find((f) => f.id === e.claudeMdId))
+      .filter((f): f is ClaudeMdFile => f !== undefined);
   },
 
   getNonGlobalFiles: () => {
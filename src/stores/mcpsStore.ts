@@ -1,5 +1,5 @@
 import { create } from 'zustand';
-import { McpServer, FetchMcpToolsResult, McpUsage, UsageStats, ClassifyItem, ClassifyResult } from '@/types';
+import { McpServer, FetchMcpToolsResult, McpUsage, McpHealth, UsageStats, ClassifyItem, ClassifyResult } from '@/types';
 import { useSettingsStore } from './settingsStore';
 import { usePluginsStore } from './pluginsStore';
 import { useAppStore } from './appStore';
@@ -25,6 +25,7 @@ interface McpsState {
   // Usage stats
   usageStats: Record<string, McpUsage>;
   isLoadingUsage: boolean;
+  mcpHealth: Record<string, McpHealth | null>; // Keyed by MCP name; null = no completed calls
 
   // Classification
   isClassifying: boolean;
@@ -45,6 +46,7 @@ interface McpsState {
   clearError: () => void;
   fetchMcpTools: (mcpId: string, showSuccessAnimation?: boolean) => Promise<FetchMcpToolsResult>;
   loadUsageStats: () => Promise<void>;
+  loadMcpHealth: (name: string) => Promise<void>;
   autoClassify: () => Promise<void>;
 
   // Computed getters (via selectors)
@@ -68,6 +70,7 @@ export const useMcpsStore = create<McpsState>((set, get) => ({
   mcpFetchErrors: {},
   usageStats: {},
   isLoadingUsage: false,
+  mcpHealth: {},
   isClassifying: false,
   classifySuccess: false,
   isFadingOut: false,
@@ -356,6 +359,28 @@ export const useMcpsStore = create<McpsState>((set, get) => ({
     }
   },
 
+  loadMcpHealth: async (name: string) => {
+    if (!isTauri()) {
+      return;
+    }
+
+    const { claudeConfigDir } = useSettingsStore.getState();
+    // Transcripts name servers as in mcp__server__tool
+    const server = name.replace(/[^A-Za-z0-9_-]/g, '_');
+
+    try {
+      const health = await safeInvoke<McpHealth[]>('get_mcp_health', {
+        claudeDir: claudeConfigDir || '~/.claude',
+        server,
+      });
+      set((state) => ({
+        mcpHealth: { ...state.mcpHealth, [name]: health?.[0] ?? null },
+      }));
+    } catch (error) {
+      console.error('Failed to load MCP health:', error);
+    }
+  },
+
   autoClassify: async () => {
     if (!isTauri()) {
       console.warn('McpsStore: Cannot auto-classify in browser mode');
@@ -34,6 +34,8 @@ export interface SettingsState {
 
   // CLAUDE.md settings
   claudeMdDistributionPath: ClaudeMdDistributionPath;
+  claudeMdScanRoots: string[];
+  claudeMdScanExcludes: string[];
 
   // Import state
   hasCompletedImport: boolean;
@@ -55,6 +57,8 @@ export interface SettingsState {
   setClaudeCommand: (command: string) => void;
   setWarpOpenMode: (mode: 'tab' | 'window') => void;
   setClaudeMdDistributionPath: (path: ClaudeMdDistributionPath) => void;
+  setClaudeMdScanRoots: (roots: string[]) => void;
+  setClaudeMdScanExcludes: (excludes: string[]) => void;
   setHasCompletedImport: (completed: boolean) => void;
   setStats: (stats: Partial<SettingsStats>) => void;
 
@@ -79,6 +83,8 @@ const defaultSettings = {
   claudeCommand: 'claude',
   warpOpenMode: 'window' as const,
   claudeMdDistributionPath: '.claude/CLAUDE.md' as ClaudeMdDistributionPath,
+  claudeMdScanRoots: ['~/Documents', '~/Projects', '~/Developer', '~/Code', '~/Workspace', '~/repos'],
+  claudeMdScanExcludes: ['node_modules', 'target', 'build', 'dist', '__pycache__', 'venv'],
   hasCompletedImport: false,
   stats: {
     skillsCount: 0,
@@ -140,6 +146,16 @@ export const useSettingsStore = create<SettingsState>()((set, get) => ({
     get().saveSettings();
   },
 
+  setClaudeMdScanRoots: (roots: string[]) => {
+    set({ claudeMdScanRoots: roots });
+    get().saveSettings();
+  },
+
+  setClaudeMdScanExcludes: (excludes: string[]) => {
+    set({ claudeMdScanExcludes: excludes });
+    get().saveSettings();
+  },
+
   setHasCompletedImport: (completed: boolean) => {
     set({ hasCompletedImport: completed });
     get().saveSettings();
@@ -173,6 +189,8 @@ export const useSettingsStore = create<SettingsState>()((set, get) => ({
           claudeCommand: settings.claudeCommand || 'claude',
           warpOpenMode: settings.warpOpenMode || 'window',
           claudeMdDistributionPath: settings.claudeMdDistributionPath || '.claude/CLAUDE.md',
+          claudeMdScanRoots: settings.claudeMdScanRoots ?? defaultSettings.claudeMdScanRoots,
+          claudeMdScanExcludes: settings.claudeMdScanExcludes ?? defaultSettings.claudeMdScanExcludes,
           hasCompletedImport: settings.hasCompletedImport || false,
           isLoading: false,
         });
@@ -206,6 +224,8 @@ export const useSettingsStore = create<SettingsState>()((set, get) => ({
           claudeCommand: state.claudeCommand,
           warpOpenMode: state.warpOpenMode,
           claudeMdDistributionPath: state.claudeMdDistributionPath,
+          claudeMdScanRoots: state.claudeMdScanRoots,
+          claudeMdScanExcludes: state.claudeMdScanExcludes,
           hasCompletedImport: state.hasCompletedImport,
         },
       });
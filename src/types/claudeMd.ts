@@ -5,8 +5,9 @@
  * - global: 用户级全局 (~/.claude/CLAUDE.md)
  * - project: 项目级 (./CLAUDE.md 或 ./.claude/CLAUDE.md)
  * - local: 本地级 (./CLAUDE.local.md)
+ * - subdirectory: 仓库子目录 (如 monorepo 中的 packages/api/CLAUDE.md)
  */
-export type ClaudeMdType = 'global' | 'project' | 'local';
+export type ClaudeMdType = 'global' | 'project' | 'local' | 'subdirectory';
 
 /**
  * CLAUDE.md 分发目标路径
@@ -116,6 +117,23 @@ export interface ClaudeMdScanResult {
   errors: string[];
 }
 
+/**
+ * 扫描进度 (事件 claude-md-scan-progress)
+ */
+export interface ClaudeMdScanProgress {
+  /** 已扫描的目录数量 */
+  scannedDirs: number;
+
+  /** 已找到的 CLAUDE.md 数量 */
+  found: number;
+
+  /** 当前扫描的目录 */
+  currentPath: string;
+
+  /** 扫描是否已结束 */
+  done: boolean;
+}
+
 /**
  * 导入选项
  */
@@ -187,6 +205,17 @@ export interface ClaudeMdDistributionResult {
   error?: string;
 }
 
+/**
+ * 全局 CLAUDE.md 栈中的一项
+ */
+export interface GlobalClaudeMdEntry {
+  /** CLAUDE.md 文件 ID */
+  claudeMdId: string;
+
+  /** 是否启用 (禁用的项保留在栈中但不写入 ~/.claude/CLAUDE.md) */
+  enabled: boolean;
+}
+
 /**
  * 设置全局结果
  */
@@ -108,6 +108,10 @@ export interface AppSettings {
   warpOpenMode: 'tab' | 'window';  // Warp 打开模式：新 Tab 或新窗口
   /** CLAUDE.md 分发目标路径 */
   claudeMdDistributionPath?: ClaudeMdDistributionPath;
+  /** 扫描 CLAUDE.md 的目录 */
+  claudeMdScanRoots?: string[];
+  /** 扫描时跳过的目录 (纯名称匹配任意位置，路径匹配该目录) */
+  claudeMdScanExcludes?: string[];
 }
 
 export interface ConfigStatus {
@@ -256,6 +260,41 @@ export interface McpUsage {
   last_used: string | null;
 }
 
+/**
+ * 失败的 MCP 工具调用
+ */
+export interface McpToolError {
+  tool: string | null;
+  timestamp: string | null;
+  message: string;
+}
+
+/**
+ * 单个 MCP 工具的错误率与延迟
+ */
+export interface McpToolHealth {
+  tool: string;
+  calls: number;
+  errors: number;
+  error_rate: number;
+  median_latency_ms: number | null;
+  p90_latency_ms: number | null;
+}
+
+/**
+ * MCP Server 的错误率与延迟（整体及按工具）
+ */
+export interface McpHealth {
+  server: string;
+  calls: number;
+  errors: number;
+  error_rate: number;
+  median_latency_ms: number | null;
+  p90_latency_ms: number | null;
+  tools: McpToolHealth[];
+  recent_errors: McpToolError[];
+}
+
 /**
  * 完整使用统计数据
  */